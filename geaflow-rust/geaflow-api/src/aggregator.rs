@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AggregateOp {
+    Sum,
+    Min,
+    Max,
+    Count,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AggregateValue {
+    Long(i64),
+    Double(f64),
+}
+
+impl AggregateValue {
+    pub fn as_i64(&self) -> i64 {
+        match self {
+            AggregateValue::Long(v) => *v,
+            AggregateValue::Double(v) => *v as i64,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            AggregateValue::Long(v) => *v as f64,
+            AggregateValue::Double(v) => *v,
+        }
+    }
+}
+
+impl From<i64> for AggregateValue {
+    fn from(v: i64) -> Self {
+        AggregateValue::Long(v)
+    }
+}
+
+/// Values above `i64::MAX` saturate rather than wrap negative.
+impl From<u64> for AggregateValue {
+    fn from(v: u64) -> Self {
+        AggregateValue::Long(i64::try_from(v).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<f64> for AggregateValue {
+    fn from(v: f64) -> Self {
+        AggregateValue::Double(v)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregatorSpec {
+    pub name: String,
+    pub op: AggregateOp,
+}
+
+impl AggregatorSpec {
+    pub fn new(name: impl Into<String>, op: AggregateOp) -> Self {
+        Self {
+            name: name.into(),
+            op,
+        }
+    }
+}
+
+pub type AggregateValues = BTreeMap<String, AggregateValue>;
+
+fn reduce(op: AggregateOp, a: AggregateValue, b: AggregateValue) -> AggregateValue {
+    use AggregateValue::{Double, Long};
+    match (op, a, b) {
+        (AggregateOp::Sum | AggregateOp::Count, Long(x), Long(y)) => Long(x.saturating_add(y)),
+        (AggregateOp::Sum | AggregateOp::Count, x, y) => Double(x.as_f64() + y.as_f64()),
+        (AggregateOp::Min, Long(x), Long(y)) => Long(x.min(y)),
+        (AggregateOp::Min, x, y) => Double(x.as_f64().min(y.as_f64())),
+        (AggregateOp::Max, Long(x), Long(y)) => Long(x.max(y)),
+        (AggregateOp::Max, x, y) => Double(x.as_f64().max(y.as_f64())),
+    }
+}
+
+/// Named global reductions; values fed during one superstep become readable in the next
+#[derive(Debug, Clone, Default)]
+pub struct Aggregators {
+    ops: BTreeMap<String, AggregateOp>,
+    current: AggregateValues,
+    previous: AggregateValues,
+}
+
+impl Aggregators {
+    pub fn new(specs: &[AggregatorSpec]) -> Self {
+        Self {
+            ops: specs.iter().map(|s| (s.name.clone(), s.op)).collect(),
+            current: AggregateValues::new(),
+            previous: AggregateValues::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Values for unregistered names are dropped.
+    pub fn aggregate(&mut self, name: &str, value: AggregateValue) {
+        let Some(op) = self.ops.get(name).copied() else {
+            return;
+        };
+        let value = match op {
+            AggregateOp::Count => AggregateValue::Long(1),
+            _ => value,
+        };
+        self.accumulate(name, op, value);
+    }
+
+    pub fn get_aggregated(&self, name: &str) -> Option<AggregateValue> {
+        self.previous.get(name).copied()
+    }
+
+    /// Folds partial results (e.g. from another worker) into the current superstep.
+    pub fn merge(&mut self, partial: &AggregateValues) {
+        for (name, value) in partial {
+            if let Some(op) = self.ops.get(name).copied() {
+                self.accumulate(name, op, *value);
+            }
+        }
+    }
+
+    pub fn take_current(&mut self) -> AggregateValues {
+        std::mem::take(&mut self.current)
+    }
+
+    pub fn previous(&self) -> &AggregateValues {
+        &self.previous
+    }
+
+    pub fn set_previous(&mut self, values: AggregateValues) {
+        self.previous = values;
+    }
+
+    /// Closes the superstep: current values become the readable ones.
+    pub fn advance(&mut self) {
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    fn accumulate(&mut self, name: &str, op: AggregateOp, value: AggregateValue) {
+        match self.current.get_mut(name) {
+            Some(slot) => *slot = reduce(op, *slot, value),
+            None => {
+                self.current.insert(name.to_string(), value);
+            }
+        }
+    }
+}
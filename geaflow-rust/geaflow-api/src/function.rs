@@ -1,3 +1,6 @@
+use crate::aggregator::{AggregateValue, AggregatorSpec};
+use crate::graph::{Edge, EdgeDirection};
+use crate::master::MasterCompute;
 use std::iter::Iterator;
 
 pub trait Function: Send + Sync + 'static {}
@@ -31,16 +34,136 @@ where
     }
 }
 
+pub trait FlatMapFunction<T, R>: Function {
+    fn flat_map(&self, value: T) -> Vec<R>;
+}
+
+impl<T, R, F> FlatMapFunction<T, R> for F
+where
+    F: Fn(T) -> Vec<R> + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+    R: Send + Sync + 'static,
+{
+    fn flat_map(&self, value: T) -> Vec<R> {
+        (self)(value)
+    }
+}
+
+pub trait KeySelector<T, K>: Function {
+    fn get_key(&self, value: &T) -> K;
+}
+
+impl<T, K, F> KeySelector<T, K> for F
+where
+    F: Fn(&T) -> K + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+    K: Send + Sync + 'static,
+{
+    fn get_key(&self, value: &T) -> K {
+        (self)(value)
+    }
+}
+
+pub trait ReduceFunction<T>: Function {
+    fn reduce(&self, acc: T, value: T) -> T;
+}
+
+impl<T, F> ReduceFunction<T> for F
+where
+    F: Fn(T, T) -> T + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    fn reduce(&self, acc: T, value: T) -> T {
+        (self)(acc, value)
+    }
+}
+
+/// Folds the values of one key into an `ACC`, then turns it into the result.
+pub trait AggregateFunction<T, ACC, R>: Function {
+    fn create_accumulator(&self) -> ACC;
+    fn add(&self, acc: &mut ACC, value: T);
+    fn get_result(&self, acc: ACC) -> R;
+}
+
+pub trait JoinFunction<L, R, O>: Function {
+    fn join(&self, left: &L, right: &R) -> O;
+}
+
+impl<L, R, O, F> JoinFunction<L, R, O> for F
+where
+    F: Fn(&L, &R) -> O + Send + Sync + 'static,
+    L: Send + Sync + 'static,
+    R: Send + Sync + 'static,
+    O: Send + Sync + 'static,
+{
+    fn join(&self, left: &L, right: &R) -> O {
+        (self)(left, right)
+    }
+}
+
+/// Reads the event time of a record.
+pub trait TimestampExtractor<T>: Function {
+    fn extract_timestamp(&self, value: &T) -> u64;
+}
+
+impl<T, F> TimestampExtractor<T> for F
+where
+    F: Fn(&T) -> u64 + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    fn extract_timestamp(&self, value: &T) -> u64 {
+        (self)(value)
+    }
+}
+
 /// Context for Vertex Centric Compute
 pub trait VertexCentricComputeFuncContext<K, VV, EV, M> {
     fn vertex_value(&self) -> Option<&VV>;
     fn set_new_vertex_value(&mut self, value: VV);
     fn send_message(&mut self, target_id: K, message: M);
-    fn edges(&self) -> Box<dyn Iterator<Item = &crate::graph::Edge<K, EV>> + '_>;
+    fn edges(&self) -> Box<dyn Iterator<Item = &Edge<K, EV>> + '_>;
+    /// Edges pointing at this vertex; the neighbour is `src_id`.
+    fn in_edges(&self) -> Box<dyn Iterator<Item = &Edge<K, EV>> + '_>;
+
+    /// Out-edges of this vertex across the whole graph. Under a vertex-cut, `edges()` only
+    /// yields the ones stored with this replica.
+    fn out_degree(&self) -> usize {
+        self.edges().count()
+    }
+
+    fn both_edges(&self) -> Box<dyn Iterator<Item = &Edge<K, EV>> + '_> {
+        Box::new(self.edges().chain(self.in_edges()))
+    }
+
+    fn edges_in_direction(
+        &self,
+        direction: EdgeDirection,
+    ) -> Box<dyn Iterator<Item = &Edge<K, EV>> + '_> {
+        match direction {
+            EdgeDirection::Out => self.edges(),
+            EdgeDirection::In => self.in_edges(),
+            EdgeDirection::Both => self.both_edges(),
+        }
+    }
+
     fn iteration(&self) -> u64;
+    fn aggregate(&mut self, name: &str, value: AggregateValue);
+    fn get_aggregated(&self, name: &str) -> Option<AggregateValue>;
+    /// Skip this vertex in later supersteps until a message arrives for it. The job stops once
+    /// every vertex has voted and no messages are pending.
+    fn vote_to_halt(&mut self);
+    fn phase(&self) -> u64;
+    fn get_broadcast(&self, name: &str) -> Option<AggregateValue>;
+    /// Topology changes take effect at the next superstep barrier.
+    fn add_vertex(&mut self, id: K, value: VV);
+    fn remove_vertex(&mut self, id: K);
+    fn add_edge(&mut self, src_id: K, target_id: K, value: EV);
+    fn remove_edge(&mut self, src_id: K, target_id: K);
 }
 
 pub trait VertexCentricComputeFunction<K, VV, EV, M>: Function {
+    /// Runs on every copy of the function before its first superstep, so once per partition
+    /// of a parallel run; the context has no current vertex.
     fn init(&mut self, _context: &mut dyn VertexCentricComputeFuncContext<K, VV, EV, M>) {}
 
     fn compute(
@@ -50,13 +173,112 @@ pub trait VertexCentricComputeFunction<K, VV, EV, M>: Function {
         context: &mut dyn VertexCentricComputeFuncContext<K, VV, EV, M>,
     );
 
+    /// Runs on every copy of the function after the last superstep, like `init`.
     fn finish(&mut self, _context: &mut dyn VertexCentricComputeFuncContext<K, VV, EV, M>) {}
+
+    /// Runs instead of `compute` in the first superstep of an incremental run, once for every
+    /// vertex the delta touched.
+    fn evolve(
+        &mut self,
+        vertex_id: &K,
+        context: &mut dyn VertexCentricComputeFuncContext<K, VV, EV, M>,
+    ) {
+        self.compute(vertex_id, &mut std::iter::empty(), context);
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        Vec::new()
+    }
+}
+
+/// Merges two messages addressed to the same target vertex before delivery
+pub trait MessageCombiner<K, M>: Function {
+    fn combine(&self, target_id: &K, old_message: M, new_message: M) -> M;
+}
+
+impl<K, M, F> MessageCombiner<K, M> for F
+where
+    F: Fn(M, M) -> M + Send + Sync + 'static,
+    K: Send + Sync + 'static,
+    M: Send + Sync + 'static,
+{
+    fn combine(&self, _target_id: &K, old_message: M, new_message: M) -> M {
+        (self)(old_message, new_message)
+    }
 }
 
 pub trait VertexCentricComputeAlgorithm<K, VV, EV, M>: Send + Sync + 'static {
     fn name(&self) -> &str;
     fn iterations(&self) -> u64;
     fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<K, VV, EV, M>>;
+
+    fn combiner(&self) -> Option<Box<dyn MessageCombiner<K, M>>> {
+        None
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        None
+    }
+
+    /// Whether removing part of a vertex holding `touched` makes a vertex holding `value` stale
+    /// in an incremental run.
+    fn invalidates(&self, _touched: &VV, _value: &VV) -> bool {
+        true
+    }
+
+    /// The value a stale vertex restarts from; `None` keeps the stored value.
+    fn reset_value(&self, _vertex_id: &K) -> Option<VV> {
+        None
+    }
+}
+
+/// A traversal started from `root`; its messages and responses carry `request_id`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraversalRequest<K> {
+    pub request_id: u64,
+    pub root: K,
+}
+
+impl<K> TraversalRequest<K> {
+    pub fn new(request_id: u64, root: K) -> Self {
+        Self { request_id, root }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraversalResponse<R> {
+    pub request_id: u64,
+    pub response: R,
+}
+
+/// Context for Vertex Centric Traversal; vertex values are read-only
+pub trait VertexCentricTraversalFuncContext<K, VV, EV, M, R> {
+    /// The request the current messages belong to.
+    fn request(&self) -> &TraversalRequest<K>;
+    fn vertex_value(&self) -> Option<&VV>;
+    fn edges(&self) -> Box<dyn Iterator<Item = &Edge<K, EV>> + '_>;
+    fn iteration(&self) -> u64;
+    /// Sends a message on behalf of the current request.
+    fn send_message(&mut self, target_id: K, message: M);
+    fn take_response(&mut self, response: R);
+}
+
+pub trait VertexCentricTraversalFunction<K, VV, EV, M, R>: Function {
+    /// Runs at the root of every request in the first superstep.
+    fn init(&mut self, context: &mut dyn VertexCentricTraversalFuncContext<K, VV, EV, M, R>);
+
+    fn compute(
+        &mut self,
+        vertex_id: &K,
+        message_iterator: &mut dyn Iterator<Item = M>,
+        context: &mut dyn VertexCentricTraversalFuncContext<K, VV, EV, M, R>,
+    );
+}
+
+pub trait VertexCentricTraversalAlgorithm<K, VV, EV, M, R>: Send + Sync + 'static {
+    fn name(&self) -> &str;
+    fn iterations(&self) -> u64;
+    fn create_function(&self) -> Box<dyn VertexCentricTraversalFunction<K, VV, EV, M, R>>;
 }
 
 impl<F> Function for F where F: Send + Sync + 'static {}
@@ -1,12 +1,28 @@
-use crate::function::{VertexCentricComputeAlgorithm, VertexCentricComputeFunction};
+use crate::function::{
+    TraversalRequest, TraversalResponse, VertexCentricComputeAlgorithm,
+    VertexCentricComputeFunction, VertexCentricTraversalAlgorithm,
+};
+use crate::mutation::GraphMutation;
 pub use geaflow_common::types::{Edge, Vertex};
 
+/// Which edges of a vertex a computation walks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeDirection {
+    /// Edges whose `src_id` is the vertex
+    Out,
+    /// Edges whose `target_id` is the vertex
+    In,
+    Both,
+}
+
 /// Represents a windowed graph stream
 pub trait PGraphWindow<K, VV, EV>: Sized {
-    /// Trigger vertex centric computation
+    /// Trigger vertex centric computation; each of the `parallelism` workers runs its own clone
+    /// of `compute_function`, calling its `init` and `finish` hooks once. Stops at the first
+    /// superstep that sends no messages; halted vertices are skipped until then
     fn compute<M, F>(self, compute_function: F, parallelism: usize) -> Self
     where
-        F: VertexCentricComputeFunction<K, VV, EV, M>,
+        F: VertexCentricComputeFunction<K, VV, EV, M> + Clone,
         M: Send + Sync + 'static + Clone;
 
     fn compute_algorithm<M, A>(self, algorithm: &A, parallelism: usize) -> Self
@@ -14,6 +30,29 @@ pub trait PGraphWindow<K, VV, EV>: Sized {
         A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
         M: Send + Sync + 'static + Clone;
 
+    /// Apply `delta` to an already computed graph and re-run from the vertices it touches
+    fn compute_incremental<M, A>(
+        self,
+        algorithm: &A,
+        delta: Vec<GraphMutation<K, VV, EV>>,
+        parallelism: usize,
+    ) -> Self
+    where
+        A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
+        M: Send + Sync + 'static + Clone;
+
+    /// Run `algorithm` once per request against this graph, leaving vertex values untouched
+    fn traverse<M, R, A>(
+        &self,
+        algorithm: &A,
+        requests: Vec<TraversalRequest<K>>,
+        parallelism: usize,
+    ) -> Vec<TraversalResponse<R>>
+    where
+        A: VertexCentricTraversalAlgorithm<K, VV, EV, M, R>,
+        M: Send + Sync + 'static + Clone,
+        R: Send + 'static;
+
     /// Get the vertices as a collection (for simple verification)
     fn vertices(&self) -> Vec<Vertex<K, VV>>;
 
@@ -1,5 +1,8 @@
+pub mod aggregator;
 pub mod function;
 pub mod graph;
+pub mod master;
+pub mod mutation;
 pub mod pipeline;
 pub mod stream;
 pub mod window;
@@ -0,0 +1,123 @@
+use crate::aggregator::{AggregateValue, AggregateValues};
+use serde::{Deserialize, Serialize};
+
+/// Superstep-level state owned by the master and visible to every vertex.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MasterState {
+    pub phase: u64,
+    pub halted: bool,
+    pub broadcast: AggregateValues,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MasterStep {
+    Halt,
+    Continue { wake_all: bool },
+}
+
+impl MasterState {
+    pub fn get_broadcast(&self, name: &str) -> Option<AggregateValue> {
+        self.broadcast.get(name).copied()
+    }
+
+    /// Decides whether superstep `iteration` runs. Without a master the job stops once no
+    /// messages are pending and every vertex has voted to halt; a master may halt earlier, or
+    /// keep going by changing the phase, which wakes every vertex.
+    pub fn before_superstep(
+        &mut self,
+        master: &mut Option<Box<dyn MasterCompute>>,
+        iteration: u64,
+        messages_pending: bool,
+        active_vertices: u64,
+        aggregated: &AggregateValues,
+    ) -> MasterStep {
+        let quiet = iteration > 1 && !messages_pending && active_vertices == 0;
+        let Some(master) = master.as_mut() else {
+            return if quiet {
+                MasterStep::Halt
+            } else {
+                MasterStep::Continue { wake_all: false }
+            };
+        };
+
+        let phase = self.phase;
+        master.compute(&mut MasterContext {
+            iteration,
+            messages_pending,
+            active_vertices,
+            aggregated,
+            state: self,
+        });
+        if self.halted {
+            return MasterStep::Halt;
+        }
+        let wake_all = self.phase != phase;
+        if quiet && !wake_all {
+            return MasterStep::Halt;
+        }
+        MasterStep::Continue { wake_all }
+    }
+}
+
+pub struct MasterContext<'a> {
+    iteration: u64,
+    messages_pending: bool,
+    active_vertices: u64,
+    aggregated: &'a AggregateValues,
+    state: &'a mut MasterState,
+}
+
+impl<'a> MasterContext<'a> {
+    /// The superstep about to run.
+    pub fn iteration(&self) -> u64 {
+        self.iteration
+    }
+
+    pub fn messages_pending(&self) -> bool {
+        self.messages_pending
+    }
+
+    /// Vertices that have not voted to halt since they last computed.
+    pub fn active_vertices(&self) -> u64 {
+        self.active_vertices
+    }
+
+    pub fn get_aggregated(&self, name: &str) -> Option<AggregateValue> {
+        self.aggregated.get(name).copied()
+    }
+
+    pub fn phase(&self) -> u64 {
+        self.state.phase
+    }
+
+    pub fn set_phase(&mut self, phase: u64) {
+        self.state.phase = phase;
+    }
+
+    /// A value broadcast so far, including by earlier supersteps.
+    pub fn get_broadcast(&self, name: &str) -> Option<AggregateValue> {
+        self.state.get_broadcast(name)
+    }
+
+    pub fn broadcast(&mut self, name: &str, value: AggregateValue) {
+        self.state.broadcast.insert(name.to_string(), value);
+    }
+
+    pub fn halt(&mut self) {
+        self.state.halted = true;
+    }
+}
+
+/// Runs on the driver before every superstep
+pub trait MasterCompute: Send {
+    fn compute(&mut self, context: &mut MasterContext);
+}
+
+impl<F> MasterCompute for F
+where
+    F: FnMut(&mut MasterContext) + Send,
+{
+    fn compute(&mut self, context: &mut MasterContext) {
+        (self)(context)
+    }
+}
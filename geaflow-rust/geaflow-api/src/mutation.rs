@@ -0,0 +1,39 @@
+use geaflow_common::types::{Edge, Vertex};
+use serde::{Deserialize, Serialize};
+
+/// A topology change requested by a vertex program, applied at the next superstep barrier.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GraphMutation<K, VV, EV> {
+    AddVertex(Vertex<K, VV>),
+    RemoveVertex(K),
+    AddEdge(Edge<K, EV>),
+    RemoveEdge { src_id: K, target_id: K },
+}
+
+impl<K, VV, EV> GraphMutation<K, VV, EV> {
+    /// The vertex whose partition owns this mutation.
+    pub fn owner(&self) -> &K {
+        match self {
+            GraphMutation::AddVertex(v) => &v.id,
+            GraphMutation::RemoveVertex(id) => id,
+            GraphMutation::AddEdge(e) => &e.src_id,
+            GraphMutation::RemoveEdge { src_id, .. } => src_id,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            GraphMutation::RemoveEdge { .. } => 0,
+            GraphMutation::RemoveVertex(_) => 1,
+            GraphMutation::AddVertex(_) => 2,
+            GraphMutation::AddEdge(_) => 3,
+        }
+    }
+}
+
+/// Puts mutations into barrier order: edge removals, vertex removals, vertex additions, then
+/// edge additions. Removing a vertex drops its out-edges; adding a vertex that already exists
+/// keeps the current value.
+pub fn resolve_mutations<K, VV, EV>(mutations: &mut [GraphMutation<K, VV, EV>]) {
+    mutations.sort_by_key(|m| m.rank());
+}
@@ -1,15 +1,63 @@
-use crate::function::{FilterFunction, MapFunction};
+use crate::function::{
+    AggregateFunction, FilterFunction, FlatMapFunction, JoinFunction, KeySelector, MapFunction,
+    ReduceFunction, TimestampExtractor,
+};
+use crate::window::EventTimeWindow;
+use std::hash::Hash;
 
 pub trait PStream<T>: Sized {
     type Output<R>: PStream<R>;
+    type Keyed<K: Eq + Hash + Clone>: PKeyedStream<K, T>;
 
     fn map<R, F>(self, func: F) -> Self::Output<R>
     where
         F: MapFunction<T, R>;
 
+    fn flat_map<R, F>(self, func: F) -> Self::Output<R>
+    where
+        F: FlatMapFunction<T, R>;
+
     fn filter<F>(self, func: F) -> Self
     where
         F: FilterFunction<T>;
 
+    fn key_by<K, F>(self, func: F) -> Self::Keyed<K>
+    where
+        K: Eq + Hash + Clone,
+        F: KeySelector<T, K>;
+
+    /// Appends `other` after this stream's records.
+    fn union(self, other: Self) -> Self;
+
     fn collect(self) -> Vec<T>;
 }
+
+/// A stream partitioned by key; results come out in order of each key's first record.
+pub trait PKeyedStream<K: Eq + Hash + Clone, T>: Sized {
+    type Output<R>: PStream<R>;
+    type Keyed<U>: PKeyedStream<K, U>;
+
+    fn reduce<F>(self, func: F) -> Self::Output<(K, T)>
+    where
+        F: ReduceFunction<T>;
+
+    fn aggregate<ACC, R, F>(self, func: F) -> Self::Output<(K, R)>
+    where
+        F: AggregateFunction<T, ACC, R>;
+
+    /// Joins records with equal keys whose event times fall in the same window, so the result
+    /// does not depend on how either side arrives. Output is ordered by window, then by each
+    /// key's first record. Windows are used as assigned: session gaps are not merged.
+    fn join<U, O, W, TL, TR, F>(
+        self,
+        other: Self::Keyed<U>,
+        window: W,
+        timestamps: (TL, TR),
+        func: F,
+    ) -> Self::Output<O>
+    where
+        W: EventTimeWindow,
+        TL: TimestampExtractor<T>,
+        TR: TimestampExtractor<U>,
+        F: JoinFunction<T, U, O>;
+}
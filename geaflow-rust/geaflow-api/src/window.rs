@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 pub enum WindowType {
     Tumbling,
     Sliding,
@@ -17,3 +19,116 @@ impl Window for SizeTumblingWindow {
         WindowType::Tumbling
     }
 }
+
+/// The half-open event-time range `[start, end)` a window covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl TimeWindow {
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+
+    pub fn intersects(&self, other: &TimeWindow) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    pub fn cover(&self, other: &TimeWindow) -> TimeWindow {
+        TimeWindow::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+/// A window over event time; session windows are merged by the runtime as records arrive.
+/// Window ends that would pass `u64::MAX` are clamped to it.
+pub trait EventTimeWindow: Window {
+    fn assign_windows(&self, timestamp: u64) -> Vec<TimeWindow>;
+}
+
+pub struct TimeTumblingWindow {
+    pub size: u64,
+}
+
+impl Window for TimeTumblingWindow {
+    fn get_type(&self) -> WindowType {
+        WindowType::Tumbling
+    }
+}
+
+impl EventTimeWindow for TimeTumblingWindow {
+    fn assign_windows(&self, timestamp: u64) -> Vec<TimeWindow> {
+        let size = self.size.max(1);
+        let start = timestamp - timestamp % size;
+        vec![TimeWindow::new(start, start.saturating_add(size))]
+    }
+}
+
+pub struct SlidingWindow {
+    pub size: u64,
+    pub slide: u64,
+}
+
+impl Window for SlidingWindow {
+    fn get_type(&self) -> WindowType {
+        WindowType::Sliding
+    }
+}
+
+impl EventTimeWindow for SlidingWindow {
+    fn assign_windows(&self, timestamp: u64) -> Vec<TimeWindow> {
+        let size = self.size.max(1);
+        let slide = self.slide.max(1);
+        let mut start = timestamp - timestamp % slide;
+        let mut out = Vec::new();
+        while start.checked_add(size).is_none_or(|end| end > timestamp) {
+            out.push(TimeWindow::new(start, start.saturating_add(size)));
+            if start < slide {
+                break;
+            }
+            start -= slide;
+        }
+        out.reverse();
+        out
+    }
+}
+
+/// Groups records that are less than `gap` apart.
+pub struct SessionWindow {
+    pub gap: u64,
+}
+
+impl Window for SessionWindow {
+    fn get_type(&self) -> WindowType {
+        WindowType::Session
+    }
+}
+
+impl EventTimeWindow for SessionWindow {
+    fn assign_windows(&self, timestamp: u64) -> Vec<TimeWindow> {
+        vec![TimeWindow::new(
+            timestamp,
+            timestamp.saturating_add(self.gap.max(1)),
+        )]
+    }
+}
+
+/// Bounded out-of-orderness: the watermark trails the largest timestamp seen by
+/// `max_out_of_orderness`, and a window closes once the watermark reaches its end.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WatermarkStrategy {
+    pub max_out_of_orderness: u64,
+}
+
+impl WatermarkStrategy {
+    pub fn bounded_out_of_orderness(max_out_of_orderness: u64) -> Self {
+        Self {
+            max_out_of_orderness,
+        }
+    }
+
+    pub fn watermark(&self, max_timestamp: u64) -> u64 {
+        max_timestamp.saturating_sub(self.max_out_of_orderness)
+    }
+}
@@ -1,3 +1,5 @@
 pub mod config;
 pub mod error;
+pub mod property;
+pub mod schema;
 pub mod types;
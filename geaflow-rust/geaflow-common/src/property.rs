@@ -0,0 +1,127 @@
+use crate::error::{GeaFlowError, GeaFlowResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PropertyType {
+    Int,
+    Double,
+    String,
+    Bool,
+    /// Milliseconds since the Unix epoch.
+    Timestamp,
+}
+
+impl fmt::Display for PropertyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PropertyType::Int => "int",
+            PropertyType::Double => "double",
+            PropertyType::String => "string",
+            PropertyType::Bool => "bool",
+            PropertyType::Timestamp => "timestamp",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PropertyValue {
+    Int(i64),
+    Double(f64),
+    String(String),
+    Bool(bool),
+    Timestamp(i64),
+}
+
+impl PropertyValue {
+    pub fn property_type(&self) -> PropertyType {
+        match self {
+            PropertyValue::Int(_) => PropertyType::Int,
+            PropertyValue::Double(_) => PropertyType::Double,
+            PropertyValue::String(_) => PropertyType::String,
+            PropertyValue::Bool(_) => PropertyType::Bool,
+            PropertyValue::Timestamp(_) => PropertyType::Timestamp,
+        }
+    }
+
+    /// Parses a textual cell, e.g. from CSV, as `ty`.
+    pub fn parse(ty: PropertyType, text: &str) -> GeaFlowResult<Self> {
+        let bad = |e: &dyn fmt::Display| {
+            GeaFlowError::InvalidArgument(format!("{ty} property parse {text:?}: {e}"))
+        };
+        Ok(match ty {
+            PropertyType::Int => PropertyValue::Int(text.parse().map_err(|e| bad(&e))?),
+            PropertyType::Double => PropertyValue::Double(text.parse().map_err(|e| bad(&e))?),
+            PropertyType::String => PropertyValue::String(text.to_string()),
+            PropertyType::Bool => PropertyValue::Bool(text.parse().map_err(|e| bad(&e))?),
+            PropertyType::Timestamp => PropertyValue::Timestamp(text.parse().map_err(|e| bad(&e))?),
+        })
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            PropertyValue::Int(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_double(&self) -> Option<f64> {
+        match self {
+            PropertyValue::Double(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_timestamp(&self) -> Option<i64> {
+        match self {
+            PropertyValue::Timestamp(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+/// A label plus named property values; used as the value of a `Vertex` or `Edge` in a
+/// property graph.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Properties {
+    pub label: String,
+    pub values: BTreeMap<String, PropertyValue>,
+}
+
+impl Properties {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            values: BTreeMap::new(),
+        }
+    }
+
+    pub fn with(mut self, name: impl Into<String>, value: PropertyValue) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PropertyValue> {
+        self.values.get(name)
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: PropertyValue) {
+        self.values.insert(name.into(), value);
+    }
+}
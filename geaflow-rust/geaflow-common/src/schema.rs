@@ -0,0 +1,111 @@
+use crate::error::{GeaFlowError, GeaFlowResult};
+use crate::property::{Properties, PropertyType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PropertySchema {
+    pub name: String,
+    pub property_type: PropertyType,
+    pub nullable: bool,
+}
+
+impl PropertySchema {
+    pub fn new(name: impl Into<String>, property_type: PropertyType) -> Self {
+        Self {
+            name: name.into(),
+            property_type,
+            nullable: true,
+        }
+    }
+
+    pub fn required(name: impl Into<String>, property_type: PropertyType) -> Self {
+        Self {
+            nullable: false,
+            ..Self::new(name, property_type)
+        }
+    }
+}
+
+/// The properties one vertex or edge label may carry, in CSV column order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelSchema {
+    pub label: String,
+    pub properties: Vec<PropertySchema>,
+}
+
+impl LabelSchema {
+    pub fn new(label: impl Into<String>, properties: Vec<PropertySchema>) -> Self {
+        Self {
+            label: label.into(),
+            properties,
+        }
+    }
+
+    pub fn property(&self, name: &str) -> Option<&PropertySchema> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    pub fn validate(&self, value: &Properties) -> GeaFlowResult<()> {
+        let label = &self.label;
+        for (name, v) in &value.values {
+            let column = self.property(name).ok_or_else(|| {
+                GeaFlowError::InvalidArgument(format!("label {label} has no property {name}"))
+            })?;
+            if column.property_type != v.property_type() {
+                return Err(GeaFlowError::InvalidArgument(format!(
+                    "label {label} property {name}: expected {}, got {}",
+                    column.property_type,
+                    v.property_type()
+                )));
+            }
+        }
+        if let Some(missing) = self
+            .properties
+            .iter()
+            .find(|p| !p.nullable && !value.values.contains_key(&p.name))
+        {
+            return Err(GeaFlowError::InvalidArgument(format!(
+                "label {label} property {} is required",
+                missing.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphSchema {
+    pub vertex_labels: Vec<LabelSchema>,
+    pub edge_labels: Vec<LabelSchema>,
+}
+
+impl GraphSchema {
+    pub fn new(vertex_labels: Vec<LabelSchema>, edge_labels: Vec<LabelSchema>) -> Self {
+        Self {
+            vertex_labels,
+            edge_labels,
+        }
+    }
+
+    pub fn vertex_label(&self, label: &str) -> GeaFlowResult<&LabelSchema> {
+        self.vertex_labels
+            .iter()
+            .find(|l| l.label == label)
+            .ok_or_else(|| GeaFlowError::InvalidArgument(format!("unknown vertex label {label}")))
+    }
+
+    pub fn edge_label(&self, label: &str) -> GeaFlowResult<&LabelSchema> {
+        self.edge_labels
+            .iter()
+            .find(|l| l.label == label)
+            .ok_or_else(|| GeaFlowError::InvalidArgument(format!("unknown edge label {label}")))
+    }
+
+    pub fn validate_vertex(&self, value: &Properties) -> GeaFlowResult<()> {
+        self.vertex_label(&value.label)?.validate(value)
+    }
+
+    pub fn validate_edge(&self, value: &Properties) -> GeaFlowResult<()> {
+        self.edge_label(&value.label)?.validate(value)
+    }
+}
@@ -27,4 +27,16 @@ impl<K, EV> Edge<K, EV> {
             value,
         }
     }
+
+    /// The endpoint opposite `vertex_id`, for walking edges in either direction.
+    pub fn neighbor(&self, vertex_id: &K) -> &K
+    where
+        K: PartialEq,
+    {
+        if &self.src_id == vertex_id {
+            &self.target_id
+        } else {
+            &self.src_id
+        }
+    }
 }
@@ -0,0 +1,472 @@
+use geaflow_api::aggregator::{AggregateOp, AggregateValue, AggregatorSpec};
+use geaflow_api::function::{
+    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+};
+use geaflow_api::graph::EdgeDirection;
+use geaflow_api::master::{MasterCompute, MasterContext};
+use serde::{Deserialize, Serialize};
+
+/// Level and parent of a vertex not reached by the search.
+pub const UNREACHED: u64 = u64::MAX;
+
+/// A vertex's place in the BFS tree: its distance in hops from the source and the vertex it was
+/// reached from. The source is its own parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BfsValue {
+    pub level: u64,
+    pub parent: u64,
+}
+
+impl BfsValue {
+    pub fn unreached() -> Self {
+        Self {
+            level: UNREACHED,
+            parent: UNREACHED,
+        }
+    }
+
+    pub fn is_reached(&self) -> bool {
+        self.level != UNREACHED
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BfsMessage {
+    /// `id`, at `level`, offers to be the receiver's parent.
+    Parent { id: u64, level: u64 },
+    /// Unreached `from` asks an in-neighbour whether it is in the tree.
+    Request { from: u64 },
+}
+
+/// Phase in which reached vertices push to their out-neighbours.
+pub const BFS_TOP_DOWN: u64 = 0;
+/// Phase in which unreached vertices pull from their in-neighbours. Each level runs rounds of a
+/// request superstep and a reply superstep; in round `r` a vertex asks its next `2^r`
+/// in-neighbours and stops once one adopts it, so on a dense frontier most vertices ask one.
+pub const BFS_BOTTOM_UP: u64 = 1;
+
+/// Vertices in the graph, counted in the first superstep.
+pub const BFS_VERTICES: &str = "bfs_vertices";
+/// Out-edges in the graph, counted in the first superstep.
+pub const BFS_EDGES: &str = "bfs_edges";
+/// Vertices reached in a superstep.
+pub const BFS_FRONTIER: &str = "bfs_frontier";
+/// Edges of the vertices reached in a superstep, in the direction being searched.
+pub const BFS_FRONTIER_EDGES: &str = "bfs_frontier_edges";
+/// Deepest level reached in a superstep.
+pub const BFS_LEVEL: &str = "bfs_level";
+/// Vertices in a request superstep with in-neighbours left to ask in a later round.
+pub const BFS_PROBING: &str = "bfs_probing";
+/// Broadcast: the superstep the current phase, or bottom-up level, started in.
+pub const BFS_PHASE_START: &str = "bfs_phase_start";
+/// Broadcast: the level whose vertices push when the search turns top-down again.
+pub const BFS_PUSH_LEVEL: &str = "bfs_push_level";
+/// Broadcast: the level whose vertices answer requests in the current bottom-up level.
+pub const BFS_PULL_LEVEL: &str = "bfs_pull_level";
+
+pub fn bfs_aggregators() -> Vec<AggregatorSpec> {
+    vec![
+        AggregatorSpec::new(BFS_VERTICES, AggregateOp::Sum),
+        AggregatorSpec::new(BFS_EDGES, AggregateOp::Sum),
+        AggregatorSpec::new(BFS_FRONTIER, AggregateOp::Sum),
+        AggregatorSpec::new(BFS_FRONTIER_EDGES, AggregateOp::Sum),
+        AggregatorSpec::new(BFS_LEVEL, AggregateOp::Max),
+        AggregatorSpec::new(BFS_PROBING, AggregateOp::Sum),
+    ]
+}
+
+/// What a BFS superstep does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BfsMode {
+    /// Vertices reached in this superstep push to their out-neighbours; in the first superstep
+    /// after a bottom-up phase, so do the vertices at `push_level`.
+    TopDown { push_level: Option<u64> },
+    /// Vertices still unreached ask their next `2^round` in-neighbours to adopt them.
+    Request { round: u32 },
+    /// Vertices at `level` answer the requests they got.
+    Reply { level: u64 },
+}
+
+impl BfsMode {
+    /// `broadcast` reads the master's broadcasts by name.
+    pub fn new(
+        phase: u64,
+        iteration: u64,
+        broadcast: impl Fn(&str) -> Option<AggregateValue>,
+    ) -> Self {
+        let read = |name| broadcast(name).map(|v| v.as_i64() as u64);
+        let since_start = read(BFS_PHASE_START).map(|s| iteration.saturating_sub(s));
+        if phase == BFS_BOTTOM_UP {
+            let since_start = since_start.unwrap_or(0);
+            if since_start.is_multiple_of(2) {
+                BfsMode::Request {
+                    round: u32::try_from(since_start / 2).unwrap_or(u32::MAX),
+                }
+            } else {
+                BfsMode::Reply {
+                    level: read(BFS_PULL_LEVEL).unwrap_or(0),
+                }
+            }
+        } else {
+            BfsMode::TopDown {
+                push_level: read(BFS_PUSH_LEVEL).filter(|_| since_start == Some(0)),
+            }
+        }
+    }
+
+    /// The edges a vertex needs in this superstep.
+    pub fn edge_direction(&self) -> EdgeDirection {
+        match self {
+            BfsMode::Request { .. } => EdgeDirection::In,
+            _ => EdgeDirection::Out,
+        }
+    }
+
+    /// Runs one vertex: `value` is its stored value, ignored in the first superstep.
+    pub fn step(
+        &self,
+        source: u64,
+        id: u64,
+        iteration: u64,
+        value: Option<BfsValue>,
+        messages: impl IntoIterator<Item = BfsMessage>,
+    ) -> BfsStep {
+        let first = iteration == 1;
+        let mut value = match value {
+            Some(v) if !first => v,
+            _ => BfsValue::unreached(),
+        };
+        let mut parent: Option<(u64, u64)> = None;
+        let mut requests = Vec::new();
+        for m in messages {
+            match m {
+                BfsMessage::Parent { id, level } => {
+                    parent = Some(parent.map_or((level, id), |p| p.min((level, id))));
+                }
+                BfsMessage::Request { from } => requests.push(from),
+            }
+        }
+
+        let mut reached = false;
+        if first && id == source {
+            value = BfsValue {
+                level: 0,
+                parent: id,
+            };
+            reached = true;
+        } else if let Some((level, parent)) = parent.filter(|_| !value.is_reached()) {
+            value = BfsValue {
+                level: level + 1,
+                parent,
+            };
+            reached = true;
+        }
+
+        let offer = BfsMessage::Parent {
+            id,
+            level: value.level,
+        };
+        let send = match *self {
+            BfsMode::TopDown { push_level } => {
+                if reached || (value.is_reached() && push_level == Some(value.level)) {
+                    BfsSend::OutNeighbours(offer)
+                } else {
+                    BfsSend::None
+                }
+            }
+            BfsMode::Request { round } if !value.is_reached() => {
+                let take = 1usize.checked_shl(round).unwrap_or(usize::MAX);
+                BfsSend::InNeighbours {
+                    message: BfsMessage::Request { from: id },
+                    skip: take - 1,
+                    take,
+                }
+            }
+            BfsMode::Reply { level } if value.level == level && !requests.is_empty() => {
+                BfsSend::Vertices(requests, offer)
+            }
+            _ => BfsSend::None,
+        };
+        BfsStep {
+            value,
+            changed: first || reached,
+            reached,
+            send,
+            // Bottom-up supersteps run every unreached vertex, top-down ones only those messaged.
+            halt: value.is_reached() || matches!(self, BfsMode::TopDown { .. }),
+        }
+    }
+}
+
+/// Where a vertex's BFS messages go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BfsSend {
+    None,
+    OutNeighbours(BfsMessage),
+    /// `take` in-neighbours after the first `skip`, in edge order.
+    InNeighbours {
+        message: BfsMessage,
+        skip: usize,
+        take: usize,
+    },
+    Vertices(Vec<u64>, BfsMessage),
+}
+
+/// The outcome of `BfsMode::step`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BfsStep {
+    pub value: BfsValue,
+    /// `value` differs from the stored one.
+    pub changed: bool,
+    /// The vertex joined the tree in this superstep.
+    pub reached: bool,
+    pub send: BfsSend,
+    pub halt: bool,
+}
+
+impl BfsStep {
+    /// What the vertex contributes to the master's statistics; `degree` counts its edges in
+    /// `BfsMode::edge_direction`.
+    pub fn aggregates(&self, iteration: u64, degree: u64) -> Vec<(&'static str, AggregateValue)> {
+        let mut out = Vec::new();
+        if iteration == 1 {
+            out.push((BFS_VERTICES, 1u64.into()));
+            out.push((BFS_EDGES, degree.into()));
+        }
+        if self.reached {
+            out.push((BFS_FRONTIER, 1u64.into()));
+            out.push((BFS_FRONTIER_EDGES, degree.into()));
+            out.push((BFS_LEVEL, self.value.level.into()));
+        }
+        if let BfsSend::InNeighbours { skip, take, .. } = self.send {
+            if degree > skip.saturating_add(take) as u64 {
+                out.push((BFS_PROBING, 1u64.into()));
+            }
+        }
+        out
+    }
+}
+
+/// Runs on the driver between supersteps and picks the search direction per level, after
+/// Beamer et al.: bottom-up once the frontier's edges exceed `1 / alpha` of the unexplored ones,
+/// top-down again once a shrinking frontier holds fewer than `1 / beta` of the vertices. The
+/// frontier seen is the one reached in the previous superstep, which in top-down has already
+/// pushed, so a switch takes effect one level late. A bottom-up level ends after the first
+/// round that leaves nobody probing, and its frontier sums all of its rounds.
+pub struct BfsMaster {
+    pub alpha: u64,
+    pub beta: u64,
+    vertices: u64,
+    edges: u64,
+    explored: u64,
+    previous: u64,
+    /// Level answering requests in bottom-up.
+    pull_level: u64,
+    /// Vertices left probing by the last request superstep.
+    probing: u64,
+    /// Frontier and frontier edges of the current bottom-up level so far.
+    level_frontier: u64,
+    level_frontier_edges: u64,
+}
+
+impl BfsMaster {
+    pub fn new(alpha: u64, beta: u64) -> Self {
+        Self {
+            alpha,
+            beta,
+            vertices: 0,
+            edges: 0,
+            explored: 0,
+            previous: 0,
+            pull_level: 0,
+            probing: 0,
+            level_frontier: 0,
+            level_frontier_edges: 0,
+        }
+    }
+}
+
+impl Default for BfsMaster {
+    fn default() -> Self {
+        Self::new(14, 24)
+    }
+}
+
+impl MasterCompute for BfsMaster {
+    fn compute(&mut self, context: &mut MasterContext) {
+        let iteration = context.iteration();
+        let aggregated = |name| {
+            context
+                .get_aggregated(name)
+                .map_or(0, |v| v.as_i64() as u64)
+        };
+        if iteration == 1 {
+            return;
+        }
+        if iteration == 2 {
+            self.vertices = aggregated(BFS_VERTICES);
+            self.edges = aggregated(BFS_EDGES);
+        }
+        let frontier = aggregated(BFS_FRONTIER);
+        let frontier_edges = aggregated(BFS_FRONTIER_EDGES);
+        let level = aggregated(BFS_LEVEL);
+        let probing = aggregated(BFS_PROBING);
+        let phase_start = context.get_broadcast(BFS_PHASE_START);
+        let mode = BfsMode::new(context.phase(), iteration, |name| {
+            context.get_broadcast(name)
+        });
+        match mode {
+            BfsMode::TopDown { .. } => {
+                if frontier == 0 {
+                    return;
+                }
+                self.explored += frontier_edges;
+                let growing = frontier > self.previous;
+                self.previous = frontier;
+                if context.messages_pending()
+                    && growing
+                    && frontier_edges * self.alpha > self.edges.saturating_sub(self.explored)
+                {
+                    context.set_phase(BFS_BOTTOM_UP);
+                    context.broadcast(BFS_PHASE_START, iteration.into());
+                    self.pull_level = level;
+                    self.probing = 0;
+                }
+            }
+            // A request superstep after a round that left nobody probing starts the next level.
+            BfsMode::Request { .. } => {
+                if self.probing == 0 {
+                    context.broadcast(BFS_PHASE_START, iteration.into());
+                }
+            }
+            BfsMode::Reply { .. } => {
+                self.level_frontier += frontier;
+                self.level_frontier_edges += frontier_edges;
+                self.probing = probing;
+                // Request supersteps reach the vertices; a level's first one reaches the last of
+                // its frontier, which is then complete.
+                if phase_start.map(|s| s.as_i64() as u64) != Some(iteration - 1) {
+                    return;
+                }
+                let frontier = std::mem::take(&mut self.level_frontier);
+                self.explored += std::mem::take(&mut self.level_frontier_edges);
+                self.pull_level += 1;
+                let growing = frontier > self.previous;
+                self.previous = frontier;
+                if frontier == 0 {
+                    context.halt();
+                } else if !growing && frontier * self.beta < self.vertices {
+                    context.set_phase(BFS_TOP_DOWN);
+                    context.broadcast(BFS_PHASE_START, iteration.into());
+                    context.broadcast(BFS_PUSH_LEVEL, self.pull_level.into());
+                } else {
+                    context.broadcast(BFS_PULL_LEVEL, self.pull_level.into());
+                }
+            }
+        }
+    }
+}
+
+/// Breadth-first search from `source`, switching between pushing along out-edges and pulling
+/// along in-edges as the frontier grows and shrinks. Each vertex ends up holding its level and
+/// BFS-tree parent, `UNREACHED` for both if the source cannot reach it.
+#[derive(Clone)]
+pub struct BfsAlgorithm {
+    pub source: u64,
+    pub iterations: u64,
+}
+
+impl BfsAlgorithm {
+    pub fn new(source: u64, iterations: u64) -> Self {
+        Self { source, iterations }
+    }
+}
+
+#[derive(Clone)]
+pub struct BfsFunction {
+    source: u64,
+}
+
+impl VertexCentricComputeFunction<u64, BfsValue, u8, BfsMessage> for BfsFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        message_iterator: &mut dyn Iterator<Item = BfsMessage>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, BfsValue, u8, BfsMessage>,
+    ) {
+        let iteration = context.iteration();
+        let mode = BfsMode::new(context.phase(), iteration, |name| {
+            context.get_broadcast(name)
+        });
+        let step = mode.step(
+            self.source,
+            *vertex_id,
+            iteration,
+            context.vertex_value().copied(),
+            message_iterator,
+        );
+
+        let degree = match mode.edge_direction() {
+            EdgeDirection::In => context.in_edges().count(),
+            _ => context.out_degree(),
+        };
+        for (name, value) in step.aggregates(iteration, degree as u64) {
+            context.aggregate(name, value);
+        }
+        if step.changed {
+            context.set_new_vertex_value(step.value);
+        }
+        let (targets, message): (Vec<u64>, _) = match step.send {
+            BfsSend::None => (Vec::new(), None),
+            BfsSend::OutNeighbours(m) => (context.edges().map(|e| e.target_id).collect(), Some(m)),
+            BfsSend::InNeighbours {
+                message,
+                skip,
+                take,
+            } => (
+                context
+                    .in_edges()
+                    .skip(skip)
+                    .take(take)
+                    .map(|e| e.src_id)
+                    .collect(),
+                Some(message),
+            ),
+            BfsSend::Vertices(targets, m) => (targets, Some(m)),
+        };
+        if let Some(message) = message {
+            for target in targets {
+                context.send_message(target, message);
+            }
+        }
+        if step.halt {
+            context.vote_to_halt();
+        }
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        bfs_aggregators()
+    }
+}
+
+impl VertexCentricComputeAlgorithm<u64, BfsValue, u8, BfsMessage> for BfsAlgorithm {
+    fn name(&self) -> &str {
+        "bfs"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    fn create_function(
+        &self,
+    ) -> Box<dyn VertexCentricComputeFunction<u64, BfsValue, u8, BfsMessage>> {
+        Box::new(BfsFunction {
+            source: self.source,
+        })
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(BfsMaster::default()))
+    }
+}
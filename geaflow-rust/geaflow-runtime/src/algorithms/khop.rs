@@ -0,0 +1,92 @@
+use geaflow_api::function::{
+    VertexCentricTraversalAlgorithm, VertexCentricTraversalFuncContext,
+    VertexCentricTraversalFunction,
+};
+use std::collections::HashSet;
+
+/// Responds with `(vertex_id, hops)` for every vertex within `k` hops of a request root.
+#[derive(Clone)]
+pub struct KHopAlgorithm {
+    pub k: u64,
+}
+
+impl KHopAlgorithm {
+    pub fn new(k: u64) -> Self {
+        Self { k }
+    }
+}
+
+pub struct KHopFunction {
+    k: u64,
+    visited: HashSet<(u64, u64)>,
+}
+
+impl KHopFunction {
+    fn expand<VV>(
+        &self,
+        hops: u64,
+        context: &mut dyn VertexCentricTraversalFuncContext<u64, VV, u8, u64, (u64, u64)>,
+    ) {
+        if hops >= self.k {
+            return;
+        }
+        let targets: Vec<u64> = context.edges().map(|e| e.target_id).collect();
+        for t in targets {
+            context.send_message(t, hops + 1);
+        }
+    }
+}
+
+impl<VV> VertexCentricTraversalFunction<u64, VV, u8, u64, (u64, u64)> for KHopFunction
+where
+    VV: Send + Sync + 'static,
+{
+    fn init(
+        &mut self,
+        context: &mut dyn VertexCentricTraversalFuncContext<u64, VV, u8, u64, (u64, u64)>,
+    ) {
+        let request = context.request().clone();
+        self.visited.insert((request.request_id, request.root));
+        context.take_response((request.root, 0));
+        self.expand(0, context);
+    }
+
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricTraversalFuncContext<u64, VV, u8, u64, (u64, u64)>,
+    ) {
+        let request_id = context.request().request_id;
+        let Some(hops) = message_iterator.min() else {
+            return;
+        };
+        if !self.visited.insert((request_id, *vertex_id)) {
+            return;
+        }
+        context.take_response((*vertex_id, hops));
+        self.expand(hops, context);
+    }
+}
+
+impl<VV> VertexCentricTraversalAlgorithm<u64, VV, u8, u64, (u64, u64)> for KHopAlgorithm
+where
+    VV: Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        "khop"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.k + 1
+    }
+
+    fn create_function(
+        &self,
+    ) -> Box<dyn VertexCentricTraversalFunction<u64, VV, u8, u64, (u64, u64)>> {
+        Box::new(KHopFunction {
+            k: self.k,
+            visited: HashSet::new(),
+        })
+    }
+}
@@ -1,2 +1,5 @@
+pub mod bfs;
+pub mod khop;
 pub mod pagerank;
+pub mod sssp;
 pub mod wcc;
@@ -1,16 +1,72 @@
+use geaflow_api::aggregator::{AggregateOp, AggregateValue, AggregatorSpec};
 use geaflow_api::function::{
-    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+    MessageCombiner, VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext,
+    VertexCentricComputeFunction,
 };
+use geaflow_api::master::{MasterCompute, MasterContext};
+
+/// L1 change of the ranks in a superstep: the residual once the job stops.
+pub const PAGERANK_DELTA: &str = "pagerank_delta";
+/// Rank held by vertices without out-edges, spread over every vertex in the next superstep.
+pub const PAGERANK_DANGLING: &str = "pagerank_dangling";
+pub const PAGERANK_VERTICES: &str = "pagerank_vertices";
+
+pub fn pagerank_aggregators() -> Vec<AggregatorSpec> {
+    vec![
+        AggregatorSpec::new(PAGERANK_DELTA, AggregateOp::Sum),
+        AggregatorSpec::new(PAGERANK_DANGLING, AggregateOp::Sum),
+        AggregatorSpec::new(PAGERANK_VERTICES, AggregateOp::Count),
+    ]
+}
+
+/// Each vertex's share of the rank the previous superstep's dangling vertices held.
+pub fn dangling_share(dangling: Option<AggregateValue>, vertices: Option<AggregateValue>) -> f64 {
+    match (dangling, vertices) {
+        (Some(dangling), Some(vertices)) if vertices.as_i64() > 0 => {
+            dangling.as_f64() / vertices.as_i64() as f64
+        }
+        _ => 0.0,
+    }
+}
+
+/// Stops the job once a superstep changes the ranks by less than `tolerance` in total.
+pub struct PageRankMaster {
+    pub tolerance: Option<f64>,
+}
+
+impl MasterCompute for PageRankMaster {
+    fn compute(&mut self, context: &mut MasterContext) {
+        let (Some(tolerance), Some(delta)) =
+            (self.tolerance, context.get_aggregated(PAGERANK_DELTA))
+        else {
+            return;
+        };
+        if delta.as_f64() < tolerance {
+            context.halt();
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct PageRankAlgorithm {
     pub iterations: u64,
     pub alpha: f64,
+    pub tolerance: Option<f64>,
 }
 
 impl PageRankAlgorithm {
     pub fn new(iterations: u64, alpha: f64) -> Self {
-        Self { iterations, alpha }
+        Self {
+            iterations,
+            alpha,
+            tolerance: None,
+        }
+    }
+
+    /// Stops before `iterations` once the L1 change of a superstep drops below `tolerance`.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = Some(tolerance);
+        self
     }
 }
 
@@ -29,35 +85,39 @@ impl VertexCentricComputeFunction<u64, f64, u8, f64> for PageRankFunction {
     ) {
         let vertex_value = context.vertex_value().cloned().unwrap_or(0.0);
         let edges: Vec<_> = context.edges().cloned().collect();
-        let out_degree = edges.len() as f64;
+        let out_degree = context.out_degree() as f64;
         if self.teleport.is_none() && vertex_value > 0.0 {
             self.teleport = Some((1.0 - self.alpha) * vertex_value);
         }
+        context.aggregate(PAGERANK_VERTICES, 1u64.into());
 
-        if context.iteration() == 1 {
-            if out_degree > 0.0 {
-                let msg = vertex_value / out_degree;
-                for e in edges {
-                    context.send_message(e.target_id, msg);
-                }
-            }
-            return;
-        }
-
-        let mut sum = 0.0;
-        for m in message_iterator {
-            sum += m;
-        }
-        let pr = sum * self.alpha + self.teleport.unwrap_or(1.0 - self.alpha);
-        context.set_new_vertex_value(pr);
+        let pr = if context.iteration() == 1 {
+            vertex_value
+        } else {
+            let dangling = dangling_share(
+                context.get_aggregated(PAGERANK_DANGLING),
+                context.get_aggregated(PAGERANK_VERTICES),
+            );
+            let sum: f64 = message_iterator.sum();
+            let pr = (sum + dangling) * self.alpha + self.teleport.unwrap_or(1.0 - self.alpha);
+            context.set_new_vertex_value(pr);
+            context.aggregate(PAGERANK_DELTA, (pr - vertex_value).abs().into());
+            pr
+        };
 
         if out_degree > 0.0 {
             let msg = pr / out_degree;
             for e in edges {
                 context.send_message(e.target_id, msg);
             }
+        } else {
+            context.aggregate(PAGERANK_DANGLING, pr.into());
         }
     }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        pagerank_aggregators()
+    }
 }
 
 impl VertexCentricComputeAlgorithm<u64, f64, u8, f64> for PageRankAlgorithm {
@@ -75,4 +135,14 @@ impl VertexCentricComputeAlgorithm<u64, f64, u8, f64> for PageRankAlgorithm {
             teleport: None,
         })
     }
+
+    fn combiner(&self) -> Option<Box<dyn MessageCombiner<u64, f64>>> {
+        Some(Box::new(|a: f64, b: f64| a + b))
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(PageRankMaster {
+            tolerance: self.tolerance,
+        }))
+    }
 }
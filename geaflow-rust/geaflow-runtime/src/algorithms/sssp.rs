@@ -0,0 +1,355 @@
+use geaflow_api::aggregator::{AggregateOp, AggregateValue, AggregatorSpec};
+use geaflow_api::function::{
+    MessageCombiner, VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext,
+    VertexCentricComputeFunction,
+};
+use geaflow_api::master::{MasterCompute, MasterContext};
+use serde::{Deserialize, Serialize};
+
+/// What an edge costs in a shortest path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SsspWeights {
+    /// Every edge costs 1, so distances are hop counts.
+    Unit,
+    /// The edge value is the cost.
+    #[default]
+    EdgeValue,
+}
+
+impl SsspWeights {
+    pub fn weight(&self, edge_value: u8) -> f64 {
+        match self {
+            SsspWeights::Unit => 1.0,
+            SsspWeights::EdgeValue => edge_value as f64,
+        }
+    }
+}
+
+/// Parent of a vertex not reached by a shortest-path search.
+pub const NO_PARENT: u64 = u64::MAX;
+
+/// A vertex's distance from the source and its parent in the shortest-path tree; the source is
+/// its own parent.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SsspTreeValue {
+    pub distance: f64,
+    pub parent: u64,
+}
+
+impl SsspTreeValue {
+    pub fn unreached() -> Self {
+        Self {
+            distance: f64::INFINITY,
+            parent: NO_PARENT,
+        }
+    }
+}
+
+/// Single-source shortest paths along out-edges. Each vertex ends up holding its distance from
+/// `source`, `f64::INFINITY` if unreachable.
+#[derive(Clone)]
+pub struct SsspAlgorithm {
+    pub source: u64,
+    pub iterations: u64,
+    pub weights: SsspWeights,
+}
+
+impl SsspAlgorithm {
+    pub fn new(source: u64, iterations: u64, weights: SsspWeights) -> Self {
+        Self {
+            source,
+            iterations,
+            weights,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SsspFunction {
+    source: u64,
+    weights: SsspWeights,
+}
+
+impl SsspFunction {
+    fn relax(
+        &self,
+        distance: f64,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, f64, u8, f64>,
+    ) {
+        let edges: Vec<(u64, u8)> = context.edges().map(|e| (e.target_id, e.value)).collect();
+        for (target, value) in edges {
+            context.send_message(target, distance + self.weights.weight(value));
+        }
+    }
+}
+
+impl VertexCentricComputeFunction<u64, f64, u8, f64> for SsspFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        message_iterator: &mut dyn Iterator<Item = f64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, f64, u8, f64>,
+    ) {
+        context.vote_to_halt();
+        let first = context.iteration() == 1;
+        let current = if first {
+            f64::INFINITY
+        } else {
+            context.vertex_value().copied().unwrap_or(f64::INFINITY)
+        };
+        let start = if first && *vertex_id == self.source {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+        let best = message_iterator.fold(start, f64::min);
+
+        if best < current {
+            context.set_new_vertex_value(best);
+            self.relax(best, context);
+        } else if first {
+            context.set_new_vertex_value(f64::INFINITY);
+        }
+    }
+
+    fn evolve(
+        &mut self,
+        _vertex_id: &u64,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, f64, u8, f64>,
+    ) {
+        context.vote_to_halt();
+        let current = context.vertex_value().copied().unwrap_or(f64::INFINITY);
+        if current.is_finite() {
+            self.relax(current, context);
+        }
+    }
+}
+
+impl VertexCentricComputeAlgorithm<u64, f64, u8, f64> for SsspAlgorithm {
+    fn name(&self) -> &str {
+        "sssp"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, f64, u8, f64>> {
+        Box::new(SsspFunction {
+            source: self.source,
+            weights: self.weights,
+        })
+    }
+
+    fn combiner(&self) -> Option<Box<dyn MessageCombiner<u64, f64>>> {
+        Some(Box::new(|a: f64, b: f64| a.min(b)))
+    }
+
+    /// A removal can lengthen any path, so every distance is recomputed from the source.
+    fn reset_value(&self, vertex_id: &u64) -> Option<f64> {
+        Some(if *vertex_id == self.source {
+            0.0
+        } else {
+            f64::INFINITY
+        })
+    }
+}
+
+/// Smallest tentative distance seen beyond the current bucket.
+pub const DELTA_NEXT: &str = "delta_next";
+/// Broadcast: the superstep the current phase started in.
+pub const DELTA_PHASE_START: &str = "delta_phase_start";
+/// Broadcast: `DELTA_NEXT` folded over the heavy phase so far.
+pub const DELTA_PENDING: &str = "delta_pending";
+
+/// Where a delta-stepping superstep stands. Phase `2b` settles bucket `b`, distances in
+/// `[b * delta, (b + 1) * delta)`, by relaxing light edges until no messages are left; phase
+/// `2b + 1` relaxes the bucket's heavy edges once.
+#[derive(Debug, Clone, Copy)]
+pub struct DeltaPhase {
+    delta: f64,
+    lo: f64,
+    hi: f64,
+    heavy: bool,
+    /// First superstep of the phase, when every vertex runs.
+    woken: bool,
+}
+
+impl DeltaPhase {
+    pub fn new(
+        delta: f64,
+        phase: u64,
+        iteration: u64,
+        phase_start: Option<AggregateValue>,
+    ) -> Self {
+        let bucket = (phase / 2) as f64;
+        Self {
+            delta,
+            lo: bucket * delta,
+            hi: (bucket + 1.0) * delta,
+            heavy: phase % 2 == 1,
+            woken: iteration == phase_start.map_or(1, |s| s.as_i64() as u64),
+        }
+    }
+
+    /// Whether a vertex at `distance` relaxes its edges in this superstep.
+    pub fn relaxes(&self, distance: f64, improved: bool) -> bool {
+        let in_bucket = distance >= self.lo && distance < self.hi;
+        in_bucket && (self.woken || (improved && !self.heavy))
+    }
+
+    /// Whether this phase relaxes an edge of `weight`.
+    pub fn takes(&self, weight: f64) -> bool {
+        (weight > self.delta) == self.heavy
+    }
+
+    /// `distance`, if the master needs it to pick the next bucket.
+    pub fn pending(&self, distance: f64, improved: bool) -> Option<f64> {
+        (distance.is_finite() && distance >= self.hi && (improved || self.woken))
+            .then_some(distance)
+    }
+}
+
+/// Runs on the driver between supersteps: moves to the heavy phase once a bucket's light
+/// relaxations stop, then on to the next non-empty bucket, halting when there is none.
+pub struct DeltaSteppingMaster {
+    pub delta: f64,
+}
+
+impl MasterCompute for DeltaSteppingMaster {
+    fn compute(&mut self, context: &mut MasterContext) {
+        let phase = context.phase();
+        let iteration = context.iteration();
+        if phase % 2 == 1 {
+            let seen = context
+                .get_aggregated(DELTA_NEXT)
+                .map_or(f64::INFINITY, |v| v.as_f64());
+            let pending = context
+                .get_broadcast(DELTA_PENDING)
+                .map_or(f64::INFINITY, |v| v.as_f64())
+                .min(seen);
+            context.broadcast(DELTA_PENDING, pending.into());
+            if context.messages_pending() {
+                return;
+            }
+            if pending.is_infinite() {
+                context.halt();
+                return;
+            }
+            let next = ((pending / self.delta).floor() as u64).max(phase / 2 + 1);
+            context.set_phase(2 * next);
+            context.broadcast(DELTA_PHASE_START, iteration.into());
+        } else if iteration > 1 && !context.messages_pending() {
+            context.set_phase(phase + 1);
+            context.broadcast(DELTA_PENDING, f64::INFINITY.into());
+            context.broadcast(DELTA_PHASE_START, iteration.into());
+        }
+    }
+}
+
+/// Single-source shortest paths by delta-stepping: distances are settled bucket by bucket of
+/// width `delta`, so a superstep only relaxes edges out of the current bucket instead of every
+/// improved vertex. Results match `SsspAlgorithm`.
+#[derive(Clone)]
+pub struct DeltaSteppingAlgorithm {
+    pub source: u64,
+    pub iterations: u64,
+    pub weights: SsspWeights,
+    pub delta: f64,
+}
+
+impl DeltaSteppingAlgorithm {
+    pub fn new(source: u64, iterations: u64, weights: SsspWeights, delta: f64) -> Self {
+        Self {
+            source,
+            iterations,
+            weights,
+            delta,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DeltaSteppingFunction {
+    source: u64,
+    weights: SsspWeights,
+    delta: f64,
+}
+
+impl VertexCentricComputeFunction<u64, f64, u8, f64> for DeltaSteppingFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        message_iterator: &mut dyn Iterator<Item = f64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, f64, u8, f64>,
+    ) {
+        context.vote_to_halt();
+        let iteration = context.iteration();
+        let phase = DeltaPhase::new(
+            self.delta,
+            context.phase(),
+            iteration,
+            context.get_broadcast(DELTA_PHASE_START),
+        );
+        let current = if iteration == 1 {
+            f64::INFINITY
+        } else {
+            context.vertex_value().copied().unwrap_or(f64::INFINITY)
+        };
+        let start = if iteration == 1 && *vertex_id == self.source {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+        let best = message_iterator.fold(start, f64::min);
+        let improved = best < current;
+        let distance = best.min(current);
+        if improved || iteration == 1 {
+            context.set_new_vertex_value(distance);
+        }
+
+        if let Some(pending) = phase.pending(distance, improved) {
+            context.aggregate(DELTA_NEXT, pending.into());
+        }
+        if phase.relaxes(distance, improved) {
+            let edges: Vec<(u64, f64)> = context
+                .edges()
+                .map(|e| (e.target_id, self.weights.weight(e.value)))
+                .filter(|(_, weight)| phase.takes(*weight))
+                .collect();
+            for (target, weight) in edges {
+                context.send_message(target, distance + weight);
+            }
+        }
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        vec![AggregatorSpec::new(DELTA_NEXT, AggregateOp::Min)]
+    }
+}
+
+impl VertexCentricComputeAlgorithm<u64, f64, u8, f64> for DeltaSteppingAlgorithm {
+    fn name(&self) -> &str {
+        "delta_stepping"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, f64, u8, f64>> {
+        Box::new(DeltaSteppingFunction {
+            source: self.source,
+            weights: self.weights,
+            delta: self.delta,
+        })
+    }
+
+    fn combiner(&self) -> Option<Box<dyn MessageCombiner<u64, f64>>> {
+        Some(Box::new(|a: f64, b: f64| a.min(b)))
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(DeltaSteppingMaster { delta: self.delta }))
+    }
+}
@@ -1,7 +1,11 @@
+use geaflow_api::aggregator::{AggregateOp, AggregatorSpec};
 use geaflow_api::function::{
-    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+    MessageCombiner, VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext,
+    VertexCentricComputeFunction,
 };
 
+pub const UPDATED_VERTICES: &str = "updated_vertices";
+
 #[derive(Clone)]
 pub struct WccAlgorithm {
     pub iterations: u64,
@@ -16,6 +20,17 @@ impl WccAlgorithm {
 #[derive(Clone)]
 pub struct WccFunction;
 
+/// Components are weak, so labels travel along in-edges as well as out-edges.
+fn neighbors(
+    vertex_id: &u64,
+    context: &dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
+) -> Vec<u64> {
+    context
+        .both_edges()
+        .map(|e| *e.neighbor(vertex_id))
+        .collect()
+}
+
 impl VertexCentricComputeFunction<u64, u64, u8, u64> for WccFunction {
     fn compute(
         &mut self,
@@ -24,13 +39,14 @@ impl VertexCentricComputeFunction<u64, u64, u8, u64> for WccFunction {
         context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
     ) {
         let current = context.vertex_value().cloned().unwrap_or(*vertex_id);
+        context.vote_to_halt();
 
         if context.iteration() == 1 {
             context.set_new_vertex_value(*vertex_id);
+            context.aggregate(UPDATED_VERTICES, 1u64.into());
             let msg = *vertex_id;
-            let edges: Vec<_> = context.edges().cloned().collect();
-            for e in edges {
-                context.send_message(e.target_id, msg);
+            for target in neighbors(vertex_id, context) {
+                context.send_message(target, msg);
             }
             return;
         }
@@ -44,12 +60,28 @@ impl VertexCentricComputeFunction<u64, u64, u8, u64> for WccFunction {
 
         if min_comp < current {
             context.set_new_vertex_value(min_comp);
-            let edges: Vec<_> = context.edges().cloned().collect();
-            for e in edges {
-                context.send_message(e.target_id, min_comp);
+            context.aggregate(UPDATED_VERTICES, 1u64.into());
+            for target in neighbors(vertex_id, context) {
+                context.send_message(target, min_comp);
             }
         }
     }
+
+    fn evolve(
+        &mut self,
+        vertex_id: &u64,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
+    ) {
+        let current = context.vertex_value().cloned().unwrap_or(*vertex_id);
+        context.vote_to_halt();
+        for target in neighbors(vertex_id, context) {
+            context.send_message(target, current);
+        }
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        vec![AggregatorSpec::new(UPDATED_VERTICES, AggregateOp::Count)]
+    }
 }
 
 impl VertexCentricComputeAlgorithm<u64, u64, u8, u64> for WccAlgorithm {
@@ -64,4 +96,16 @@ impl VertexCentricComputeAlgorithm<u64, u64, u8, u64> for WccAlgorithm {
     fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, u64, u8, u64>> {
         Box::new(WccFunction)
     }
+
+    fn combiner(&self) -> Option<Box<dyn MessageCombiner<u64, u64>>> {
+        Some(Box::new(|a: u64, b: u64| a.min(b)))
+    }
+
+    fn invalidates(&self, touched: &u64, value: &u64) -> bool {
+        touched == value
+    }
+
+    fn reset_value(&self, vertex_id: &u64) -> Option<u64> {
+        Some(*vertex_id)
+    }
 }
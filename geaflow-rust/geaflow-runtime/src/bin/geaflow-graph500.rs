@@ -1,12 +1,16 @@
 use clap::{Parser, Subcommand};
 use dashmap::DashMap;
 use geaflow_common::error::GeaFlowResult;
+use geaflow_runtime::algorithms::bfs::BfsValue;
+use geaflow_runtime::algorithms::pagerank::PAGERANK_DELTA;
+use geaflow_runtime::algorithms::sssp::SsspTreeValue;
+use geaflow_runtime::distributed::algorithm::{BfsParams, SsspTreeParams};
 use geaflow_runtime::distributed::driver::DistributedDriver;
 use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
 use geaflow_runtime::http::{serve_http_v2, HttpRequest, HttpResponse};
 use geaflow_runtime::observability::init_tracing;
 use geaflow_runtime::plan::job_spec::{
-    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, JobMode, JobSpec,
+    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, IdSpec, JobMode, JobSpec, PartitionerSpec,
 };
 use geaflow_runtime::scheduler::cycle_scheduler::CycleScheduler;
 use std::fs::File;
@@ -59,6 +63,38 @@ enum Command {
         #[arg(long, default_value_t = 0.85)]
         alpha: f64,
     },
+    /// Graph500 kernel 2: BFS from sampled search keys, each tree validated.
+    RunBfs {
+        #[arg(long, default_value = "/tmp/graph500-22")]
+        dir: PathBuf,
+        #[arg(long, default_value_t = 4)]
+        workers: usize,
+        #[arg(long, default_value = "/tmp/geaflow-graph500")]
+        out_dir: PathBuf,
+        #[arg(long, default_value_t = 64)]
+        keys: usize,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        #[arg(long, default_value_t = 1000)]
+        iterations: u64,
+    },
+    /// Graph500 kernel 3: SSSP from sampled search keys over generated weights in [0, 1), each
+    /// tree validated.
+    RunSssp {
+        #[arg(long, default_value = "/tmp/graph500-22")]
+        dir: PathBuf,
+        #[arg(long, default_value_t = 4)]
+        workers: usize,
+        #[arg(long, default_value = "/tmp/geaflow-graph500")]
+        out_dir: PathBuf,
+        #[arg(long, default_value_t = 64)]
+        keys: usize,
+        /// Seeds both the key sample and the edge weights.
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        #[arg(long, default_value_t = 1000)]
+        iterations: u64,
+    },
     RunAll {
         #[arg(long, default_value = "/tmp/graph500-22")]
         dir: PathBuf,
@@ -160,7 +196,7 @@ fn read_properties_path(prop_path: &Path) -> anyhow::Result<Graph500Meta> {
     let dir = prop_path
         .parent()
         .ok_or_else(|| anyhow::anyhow!("bad properties path"))?;
-    let f = File::open(&prop_path)?;
+    let f = File::open(prop_path)?;
     let r = BufReader::new(f);
     let mut kv = std::collections::HashMap::<String, String>::new();
     for line in r.lines() {
@@ -447,6 +483,8 @@ struct PageRankReport {
     missing: u64,
     max_abs_diff: f64,
     l1_diff: f64,
+    /// L1 change of the ranks in the last superstep.
+    residual: f64,
 }
 
 async fn run_wcc_meta(
@@ -460,7 +498,6 @@ async fn run_wcc_meta(
         "Graph500 meta: scale={} vertex_count={} meta.vertices={} edges={} directed={}",
         meta.scale, vertex_count, meta.vertices, meta.edges, meta.directed
     );
-    let undirected = !meta.directed;
 
     let db_dir = out_dir.join("db_wcc");
     let out_wcc_dir = out_dir.join("out_wcc");
@@ -477,7 +514,8 @@ async fn run_wcc_meta(
             |id| bincode::serialize(&id).unwrap(),
             50_000,
             50_000,
-            undirected,
+            // WCC follows in-edges itself, so undirected graphs load each edge once.
+            false,
         )
         .await)?;
     println!("WCC load done in {:.2}s", t0.elapsed().as_secs_f64());
@@ -493,6 +531,7 @@ async fn run_wcc_meta(
             edges: FileSource::Csv {
                 path: meta.edge_file.to_string_lossy().to_string(),
             },
+            ids: IdSpec::U64,
         },
         algorithm: AlgorithmSpec::Wcc { iterations },
         checkpoint: CheckpointSpec {
@@ -500,6 +539,7 @@ async fn run_wcc_meta(
             interval_iters: 0,
             base_dir: "/tmp/geaflow-checkpoints".to_string(),
         },
+        partitioner: PartitionerSpec::Hash,
     };
 
     gf(driver
@@ -582,16 +622,25 @@ async fn run_pagerank_meta(
             edges: FileSource::Csv {
                 path: meta.edge_file.to_string_lossy().to_string(),
             },
+            ids: IdSpec::U64,
+        },
+        algorithm: AlgorithmSpec::PageRank {
+            iterations,
+            alpha,
+            tolerance: None,
         },
-        algorithm: AlgorithmSpec::PageRank { iterations, alpha },
         checkpoint: CheckpointSpec {
             enabled: false,
             interval_iters: 0,
             base_dir: "/tmp/geaflow-checkpoints".to_string(),
         },
+        partitioner: PartitionerSpec::Hash,
     };
 
-    let params = geaflow_runtime::distributed::algorithm::PageRankParams { alpha };
+    let params = geaflow_runtime::distributed::algorithm::PageRankParams {
+        alpha,
+        tolerance: None,
+    };
     gf(driver
         .set_algorithm(
             "pagerank".to_string(),
@@ -601,7 +650,14 @@ async fn run_pagerank_meta(
         .await)?;
     let t1 = std::time::Instant::now();
     gf(CycleScheduler::run(&mut driver, &job).await)?;
-    println!("PR compute done in {:.2}s", t1.elapsed().as_secs_f64());
+    let residual = driver
+        .aggregated()
+        .get(PAGERANK_DELTA)
+        .map_or(f64::NAN, |v| v.as_f64());
+    println!(
+        "PR compute done in {:.2}s residual={residual:.3e}",
+        t1.elapsed().as_secs_f64()
+    );
 
     let out_parts = gf(driver.dump_vertices_csv(&out_pr_dir, "pr").await)?;
 
@@ -629,6 +685,7 @@ async fn run_pagerank_meta(
         missing,
         max_abs_diff: max_abs,
         l1_diff: l1,
+        residual,
     })
 }
 
@@ -667,6 +724,485 @@ async fn run_pagerank(
     Ok(())
 }
 
+/// Calls `f` with the endpoints of every edge in a Graph500 edge file.
+fn for_each_edge(path: &Path, mut f: impl FnMut(u64, u64)) -> anyhow::Result<()> {
+    let r = BufReader::new(File::open(path)?);
+    for line in r.lines() {
+        let line = line?;
+        let s = line.trim();
+        if s.is_empty() || s.starts_with('#') {
+            continue;
+        }
+        let mut it = s.split_whitespace();
+        let src: u64 = it
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("bad edge line"))?
+            .parse()?;
+        let dst: u64 = it
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("bad edge line"))?
+            .parse()?;
+        f(src, dst);
+    }
+    Ok(())
+}
+
+/// Up to `keys` distinct search keys, drawn with a fixed seed from the vertices with an edge
+/// other than a self-loop, as the spec requires.
+fn sample_search_keys(edge_file: &Path, keys: usize, seed: u64) -> anyhow::Result<Vec<u64>> {
+    let mut candidates = std::collections::HashSet::new();
+    for_each_edge(edge_file, |src, dst| {
+        if src != dst {
+            candidates.insert(src);
+            candidates.insert(dst);
+        }
+    })?;
+    let mut candidates: Vec<u64> = candidates.into_iter().collect();
+    candidates.sort_unstable();
+    let mut state = seed;
+    let mut out = Vec::with_capacity(keys.min(candidates.len()));
+    while out.len() < keys && !candidates.is_empty() {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let i = ((state >> 33) % candidates.len() as u64) as usize;
+        out.push(candidates.swap_remove(i));
+    }
+    Ok(out)
+}
+
+/// Validates a BFS tree from `root` against the edge file, following the Graph500 spec: the root
+/// is its own parent at level 0, every tree edge joins levels one apart and is an input edge,
+/// every input edge joins levels at most one apart, and the tree spans exactly the root's
+/// component. Returns the input edges inside the component, the count TEPS is based on.
+fn validate_bfs_tree(
+    root: u64,
+    tree: &std::collections::HashMap<u64, BfsValue>,
+    edge_file: &Path,
+    directed: bool,
+) -> anyhow::Result<u64> {
+    let reached = |id: &u64| tree.get(id).filter(|v| v.is_reached());
+    match reached(&root) {
+        Some(v) if v.level == 0 && v.parent == root => {}
+        other => return Err(anyhow::anyhow!("root {root} has bad tree entry {other:?}")),
+    }
+    for (id, v) in tree.iter().filter(|(_, v)| v.is_reached()) {
+        if *id == root {
+            continue;
+        }
+        match reached(&v.parent) {
+            Some(p) if p.level + 1 == v.level => {}
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "vertex {id} at level {} has parent {} not one level up",
+                    v.level,
+                    v.parent
+                ))
+            }
+        }
+    }
+
+    let mut tree_edges_seen = std::collections::HashSet::new();
+    let mut component_edges = 0u64;
+    let mut error: Option<String> = None;
+    for_each_edge(edge_file, |src, dst| {
+        if error.is_some() {
+            return;
+        }
+        match (reached(&src), reached(&dst)) {
+            (None, None) => {}
+            (Some(s), Some(d)) => {
+                let ok = if directed {
+                    d.level <= s.level + 1
+                } else {
+                    s.level.abs_diff(d.level) <= 1
+                };
+                if !ok {
+                    error = Some(format!(
+                        "edge {src}-{dst} joins levels {} and {}",
+                        s.level, d.level
+                    ));
+                }
+                component_edges += 1;
+                if dst != root && d.parent == src {
+                    tree_edges_seen.insert(dst);
+                }
+                if !directed && src != root && s.parent == dst {
+                    tree_edges_seen.insert(src);
+                }
+            }
+            (None, Some(_)) if directed => {}
+            _ => error = Some(format!("edge {src}-{dst} leaves the tree's component")),
+        }
+    })?;
+    if let Some(e) = error {
+        return Err(anyhow::anyhow!(e));
+    }
+    let reached_count = tree.values().filter(|v| v.is_reached()).count();
+    if tree_edges_seen.len() + 1 < reached_count {
+        return Err(anyhow::anyhow!(
+            "{} tree edges are not input edges",
+            reached_count - 1 - tree_edges_seen.len()
+        ));
+    }
+    Ok(component_edges)
+}
+
+/// Kernel 3's weight for the edge `u`-`v`: uniform in [0, 1) and derived from the seed and the
+/// unordered endpoints, so the loader and the validator agree without storing weights.
+fn graph500_weight(seed: u64, u: u64, v: u64) -> f64 {
+    let mut key = [0u8; 16];
+    key[..8].copy_from_slice(&u.min(v).to_le_bytes());
+    key[8..].copy_from_slice(&u.max(v).to_le_bytes());
+    (xxhash_rust::xxh3::xxh3_64_with_seed(&key, seed) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Slack allowed when comparing distances summed in a different order.
+const SSSP_TOLERANCE: f64 = 1e-9;
+
+/// Validates a shortest-path tree from `root` the way the Graph500 spec validates kernel 3: the
+/// root is its own parent at distance 0, parent pointers lead to the root without cycles, every
+/// tree edge is an input edge whose weight makes up the distance difference, no input edge
+/// offers a shorter path, and the tree spans exactly the root's component. Returns the input
+/// edges inside the component.
+fn validate_sssp_tree(
+    root: u64,
+    tree: &std::collections::HashMap<u64, SsspTreeValue>,
+    edge_file: &Path,
+    directed: bool,
+    seed: u64,
+) -> anyhow::Result<u64> {
+    let reached = |id: &u64| tree.get(id).filter(|v| v.distance.is_finite());
+    match reached(&root) {
+        Some(v) if v.distance == 0.0 && v.parent == root => {}
+        other => return Err(anyhow::anyhow!("root {root} has bad tree entry {other:?}")),
+    }
+    let reached_count = tree.values().filter(|v| v.distance.is_finite()).count();
+    let mut rooted = std::collections::HashSet::from([root]);
+    for (id, _) in tree.iter().filter(|(_, v)| v.distance.is_finite()) {
+        let mut path = Vec::new();
+        let mut current = *id;
+        while !rooted.contains(&current) {
+            let v = reached(&current)
+                .ok_or_else(|| anyhow::anyhow!("vertex {id} descends from unreached {current}"))?;
+            if path.len() >= reached_count {
+                return Err(anyhow::anyhow!("vertex {id} has a parent cycle"));
+            }
+            path.push(current);
+            current = v.parent;
+        }
+        rooted.extend(path);
+    }
+
+    let tight = |parent: &SsspTreeValue, child: &SsspTreeValue, weight: f64| {
+        (child.distance - (parent.distance + weight)).abs() <= SSSP_TOLERANCE
+    };
+    let mut tree_edges_seen = std::collections::HashSet::new();
+    let mut component_edges = 0u64;
+    let mut error: Option<String> = None;
+    for_each_edge(edge_file, |src, dst| {
+        if error.is_some() {
+            return;
+        }
+        let weight = graph500_weight(seed, src, dst);
+        match (reached(&src), reached(&dst)) {
+            (None, None) => {}
+            (Some(s), Some(d)) => {
+                let mut ok = d.distance <= s.distance + weight + SSSP_TOLERANCE;
+                if !directed {
+                    ok &= s.distance <= d.distance + weight + SSSP_TOLERANCE;
+                }
+                if !ok {
+                    error = Some(format!(
+                        "edge {src}-{dst} of weight {weight} joins distances {} and {}",
+                        s.distance, d.distance
+                    ));
+                }
+                component_edges += 1;
+                if dst != root && d.parent == src && tight(s, d, weight) {
+                    tree_edges_seen.insert(dst);
+                }
+                if !directed && src != root && s.parent == dst && tight(d, s, weight) {
+                    tree_edges_seen.insert(src);
+                }
+            }
+            (None, Some(_)) if directed => {}
+            _ => error = Some(format!("edge {src}-{dst} leaves the tree's component")),
+        }
+    })?;
+    if let Some(e) = error {
+        return Err(anyhow::anyhow!(e));
+    }
+    if tree_edges_seen.len() + 1 < reached_count {
+        return Err(anyhow::anyhow!(
+            "{} tree edges are not input edges of the right weight",
+            reached_count - 1 - tree_edges_seen.len()
+        ));
+    }
+    Ok(component_edges)
+}
+
+fn harmonic_mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.len() as f64 / values.iter().map(|v| 1.0 / v).sum::<f64>()
+}
+
+/// How a search kernel samples its keys and bounds each search.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(default)]
+struct SearchOptions {
+    keys: usize,
+    seed: u64,
+    iterations: u64,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            keys: 64,
+            seed: 1,
+            iterations: 1000,
+        }
+    }
+}
+
+/// TEPS of a search kernel over its keys; searches that fail validation count in `failed` only.
+#[derive(Debug, Clone, serde::Serialize)]
+struct SearchReport {
+    keys: u64,
+    failed: u64,
+    harmonic_mean_teps: f64,
+    min_teps: f64,
+    max_teps: f64,
+    mean_seconds: f64,
+}
+
+impl SearchReport {
+    fn passed(&self) -> bool {
+        self.keys > 0 && self.failed == 0
+    }
+}
+
+fn decode_tree<T: serde::de::DeserializeOwned>(
+    vertices: Vec<(Vec<u8>, Vec<u8>)>,
+) -> anyhow::Result<std::collections::HashMap<u64, T>> {
+    let mut tree = std::collections::HashMap::with_capacity(vertices.len());
+    for (id, value) in vertices {
+        tree.insert(bincode::deserialize(&id)?, bincode::deserialize(&value)?);
+    }
+    Ok(tree)
+}
+
+/// Runs `algorithm` from every root on the loaded graph, timing only the supersteps, and checks
+/// each result with `validate`, which returns the input edges in the root's component.
+async fn run_search_kernel<P, V>(
+    driver: &mut DistributedDriver,
+    kernel: &str,
+    algorithm: &str,
+    roots: &[u64],
+    iterations: u64,
+    params: impl Fn(u64) -> P,
+    mut validate: V,
+) -> anyhow::Result<SearchReport>
+where
+    P: serde::Serialize,
+    V: FnMut(u64, Vec<(Vec<u8>, Vec<u8>)>) -> anyhow::Result<u64>,
+{
+    let mut teps = Vec::with_capacity(roots.len());
+    let mut seconds = Vec::with_capacity(roots.len());
+    let mut failed = 0u64;
+    for (i, root) in roots.iter().enumerate() {
+        gf(driver
+            .set_algorithm(
+                algorithm.to_string(),
+                iterations,
+                bincode::serialize(&params(*root))?,
+            )
+            .await)?;
+        let t = std::time::Instant::now();
+        let supersteps = gf(driver.execute(iterations).await)?;
+        let elapsed = t.elapsed().as_secs_f64();
+
+        let vertices = gf(driver.fetch_vertices().await)?;
+        match validate(*root, vertices) {
+            Ok(edges) => {
+                let rate = edges as f64 / elapsed.max(f64::MIN_POSITIVE);
+                println!(
+                    "{kernel} key {i} root={root}: {supersteps} supersteps in {elapsed:.3}s, {edges} edges, {rate:.3e} TEPS"
+                );
+                teps.push(rate);
+                seconds.push(elapsed);
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("{kernel} key {i} root={root}: validation failed: {e}");
+            }
+        }
+    }
+
+    let report = SearchReport {
+        keys: roots.len() as u64,
+        failed,
+        harmonic_mean_teps: harmonic_mean(&teps),
+        min_teps: teps.iter().copied().fold(f64::INFINITY, f64::min),
+        max_teps: teps.iter().copied().fold(0.0, f64::max),
+        mean_seconds: seconds.iter().sum::<f64>() / seconds.len().max(1) as f64,
+    };
+    println!(
+        "{kernel}: keys={} failed={} harmonic_mean_teps={:.3e} min_teps={:.3e} max_teps={:.3e} mean_time={:.3}s",
+        report.keys,
+        report.failed,
+        report.harmonic_mean_teps,
+        report.min_teps,
+        report.max_teps,
+        report.mean_seconds
+    );
+    Ok(report)
+}
+
+async fn run_bfs_meta(
+    meta: &Graph500Meta,
+    workers: usize,
+    out_dir: &Path,
+    search: SearchOptions,
+) -> anyhow::Result<SearchReport> {
+    println!(
+        "Graph500 meta: scale={} meta.vertices={} edges={} directed={}",
+        meta.scale, meta.vertices, meta.edges, meta.directed
+    );
+    let roots = sample_search_keys(&meta.edge_file, search.keys, search.seed)?;
+    println!("BFS search keys: {}", roots.len());
+
+    let db_dir = out_dir.join("db_bfs");
+    let _ = std::fs::remove_dir_all(&db_dir);
+    let (worker_addrs, handles) = start_workers(workers, &db_dir).await?;
+    let mut driver = gf(DistributedDriver::connect(&worker_addrs).await)?;
+    let t0 = std::time::Instant::now();
+    let unreached = bincode::serialize(&BfsValue::unreached())?;
+    gf(driver
+        .load_graph500_streaming(
+            &meta.vertex_file,
+            &meta.edge_file,
+            |_| unreached.clone(),
+            50_000,
+            50_000,
+            !meta.directed,
+        )
+        .await)?;
+    println!("BFS load done in {:.2}s", t0.elapsed().as_secs_f64());
+
+    let report = run_search_kernel(
+        &mut driver,
+        "BFS",
+        "bfs",
+        &roots,
+        search.iterations,
+        |source| BfsParams { source },
+        |root, vertices| {
+            validate_bfs_tree(
+                root,
+                &decode_tree(vertices)?,
+                &meta.edge_file,
+                meta.directed,
+            )
+        },
+    )
+    .await;
+
+    let _ = driver.shutdown().await;
+    for h in handles {
+        h.abort();
+    }
+    report
+}
+
+async fn run_sssp_meta(
+    meta: &Graph500Meta,
+    workers: usize,
+    out_dir: &Path,
+    search: SearchOptions,
+) -> anyhow::Result<SearchReport> {
+    println!(
+        "Graph500 meta: scale={} meta.vertices={} edges={} directed={}",
+        meta.scale, meta.vertices, meta.edges, meta.directed
+    );
+    let roots = sample_search_keys(&meta.edge_file, search.keys, search.seed)?;
+    println!("SSSP search keys: {}", roots.len());
+
+    let db_dir = out_dir.join("db_sssp");
+    let _ = std::fs::remove_dir_all(&db_dir);
+    let (worker_addrs, handles) = start_workers(workers, &db_dir).await?;
+    let mut driver = gf(DistributedDriver::connect(&worker_addrs).await)?;
+    let t0 = std::time::Instant::now();
+    let unreached = bincode::serialize(&SsspTreeValue::unreached())?;
+    gf(driver
+        .load_graph500_streaming_weighted(
+            &meta.vertex_file,
+            &meta.edge_file,
+            |_| unreached.clone(),
+            |src, dst| bincode::serialize(&graph500_weight(search.seed, src, dst)).unwrap(),
+            50_000,
+            !meta.directed,
+        )
+        .await)?;
+    println!("SSSP load done in {:.2}s", t0.elapsed().as_secs_f64());
+
+    let report = run_search_kernel(
+        &mut driver,
+        "SSSP",
+        "sssp_tree",
+        &roots,
+        search.iterations,
+        |source| SsspTreeParams { source },
+        |root, vertices| {
+            validate_sssp_tree(
+                root,
+                &decode_tree(vertices)?,
+                &meta.edge_file,
+                meta.directed,
+                search.seed,
+            )
+        },
+    )
+    .await;
+
+    let _ = driver.shutdown().await;
+    for h in handles {
+        h.abort();
+    }
+    report
+}
+
+async fn run_bfs(
+    dir: &Path,
+    workers: usize,
+    out_dir: &Path,
+    search: SearchOptions,
+) -> anyhow::Result<()> {
+    let meta = read_properties(dir)?;
+    let report = run_bfs_meta(&meta, workers, out_dir, search).await?;
+    if !report.passed() {
+        return Err(anyhow::anyhow!("BFS verification failed"));
+    }
+    Ok(())
+}
+
+async fn run_sssp(
+    dir: &Path,
+    workers: usize,
+    out_dir: &Path,
+    search: SearchOptions,
+) -> anyhow::Result<()> {
+    let meta = read_properties(dir)?;
+    let report = run_sssp_meta(&meta, workers, out_dir, search).await?;
+    if !report.passed() {
+        return Err(anyhow::anyhow!("SSSP verification failed"));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 struct DatasetEntry {
     meta: Graph500Meta,
@@ -693,6 +1229,17 @@ struct JobRecord {
     out_dir: String,
     wcc: Option<WccReport>,
     pagerank: Option<PageRankReport>,
+    bfs: Option<SearchReport>,
+    sssp: Option<SearchReport>,
+}
+
+/// What the kernels in a job's `run` list reported.
+#[derive(Debug, Default)]
+struct JobReports {
+    wcc: Option<WccReport>,
+    pagerank: Option<PageRankReport>,
+    bfs: Option<SearchReport>,
+    sssp: Option<SearchReport>,
 }
 
 #[derive(Debug)]
@@ -765,6 +1312,9 @@ struct SubmitJobRequest {
     alpha: f64,
     run: Vec<String>,
     out_dir: Option<String>,
+    /// Options of the `bfs` and `sssp` kernels.
+    #[serde(default)]
+    search: SearchOptions,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -885,6 +1435,8 @@ async fn handle_submit_job(state: Arc<AppState>, req: &HttpRequest) -> HttpRespo
         out_dir: job_out_dir_s.clone(),
         wcc: None,
         pagerank: None,
+        bfs: None,
+        sssp: None,
     };
     state.jobs.insert(job_id.clone(), record);
 
@@ -900,12 +1452,11 @@ async fn handle_submit_job(state: Arc<AppState>, req: &HttpRequest) -> HttpRespo
 
         let result = async {
             std::fs::create_dir_all(&job_out_dir)?;
-            let mut wcc_report = None;
-            let mut pr_report = None;
+            let mut reports = JobReports::default();
 
             for item in &run {
                 if item == "wcc" {
-                    wcc_report = Some(
+                    reports.wcc = Some(
                         run_wcc_meta(
                             &dataset.meta,
                             payload.workers,
@@ -915,7 +1466,7 @@ async fn handle_submit_job(state: Arc<AppState>, req: &HttpRequest) -> HttpRespo
                         .await?,
                     );
                 } else if item == "pr" || item == "pagerank" {
-                    pr_report = Some(
+                    reports.pagerank = Some(
                         run_pagerank_meta(
                             &dataset.meta,
                             payload.workers,
@@ -925,15 +1476,25 @@ async fn handle_submit_job(state: Arc<AppState>, req: &HttpRequest) -> HttpRespo
                         )
                         .await?,
                     );
+                } else if item == "bfs" {
+                    reports.bfs = Some(
+                        run_bfs_meta(&dataset.meta, payload.workers, &job_out_dir, payload.search)
+                            .await?,
+                    );
+                } else if item == "sssp" {
+                    reports.sssp = Some(
+                        run_sssp_meta(&dataset.meta, payload.workers, &job_out_dir, payload.search)
+                            .await?,
+                    );
                 }
             }
 
-            if let Some(w) = wcc_report.as_ref() {
+            if let Some(w) = reports.wcc.as_ref() {
                 if w.mismatches > 0 || w.unexpected > 0 || w.missing > 0 {
                     return Err(anyhow::anyhow!("WCC verification failed"));
                 }
             }
-            if let Some(p) = pr_report.as_ref() {
+            if let Some(p) = reports.pagerank.as_ref() {
                 if p.unexpected > 0
                     || p.missing > 0
                     || !p.max_abs_diff.is_finite()
@@ -942,20 +1503,26 @@ async fn handle_submit_job(state: Arc<AppState>, req: &HttpRequest) -> HttpRespo
                     return Err(anyhow::anyhow!("PageRank verification failed"));
                 }
             }
+            if reports.bfs.as_ref().is_some_and(|r| !r.passed()) {
+                return Err(anyhow::anyhow!("BFS verification failed"));
+            }
+            if reports.sssp.as_ref().is_some_and(|r| !r.passed()) {
+                return Err(anyhow::anyhow!("SSSP verification failed"));
+            }
 
-            Ok::<(Option<WccReport>, Option<PageRankReport>), anyhow::Error>((
-                wcc_report, pr_report,
-            ))
+            Ok::<JobReports, anyhow::Error>(reports)
         }
         .await;
 
         match result {
-            Ok((wcc_report, pr_report)) => {
+            Ok(reports) => {
                 if let Some(mut j) = state_cloned.jobs.get_mut(&job_id) {
                     j.status = JobStatus::Succeeded;
                     j.finished_ms = Some(now_ms());
-                    j.wcc = wcc_report;
-                    j.pagerank = pr_report;
+                    j.wcc = reports.wcc;
+                    j.pagerank = reports.pagerank;
+                    j.bfs = reports.bfs;
+                    j.sssp = reports.sssp;
                 }
             }
             Err(e) => {
@@ -1021,7 +1588,7 @@ async fn main() -> anyhow::Result<()> {
                 })
                 .filter_map(|p| std::fs::metadata(p).ok().map(|m| (m.len(), p.clone())))
                 .collect();
-            candidates.sort_by(|a, b| b.0.cmp(&a.0));
+            candidates.sort_by_key(|c| std::cmp::Reverse(c.0));
 
             if let Some((_, p)) = candidates.first() {
                 let fmt = sniff_text_format(p)?;
@@ -1057,6 +1624,38 @@ async fn main() -> anyhow::Result<()> {
             std::fs::create_dir_all(&out_dir)?;
             run_pagerank(&dir, workers, &out_dir, iterations, alpha).await?;
         }
+        Command::RunBfs {
+            dir,
+            workers,
+            out_dir,
+            keys,
+            seed,
+            iterations,
+        } => {
+            std::fs::create_dir_all(&out_dir)?;
+            let search = SearchOptions {
+                keys,
+                seed,
+                iterations,
+            };
+            run_bfs(&dir, workers, &out_dir, search).await?;
+        }
+        Command::RunSssp {
+            dir,
+            workers,
+            out_dir,
+            keys,
+            seed,
+            iterations,
+        } => {
+            std::fs::create_dir_all(&out_dir)?;
+            let search = SearchOptions {
+                keys,
+                seed,
+                iterations,
+            };
+            run_sssp(&dir, workers, &out_dir, search).await?;
+        }
         Command::RunAll {
             dir,
             workers,
@@ -1,18 +1,21 @@
 use clap::{Parser, ValueEnum};
 use geaflow_api::graph::PGraphWindow;
-use geaflow_runtime::algorithms::pagerank::PageRankAlgorithm;
+use geaflow_runtime::algorithms::pagerank::{PageRankAlgorithm, PAGERANK_DELTA};
+use geaflow_runtime::algorithms::sssp::{DeltaSteppingAlgorithm, SsspAlgorithm, SsspWeights};
 use geaflow_runtime::algorithms::wcc::WccAlgorithm;
 use geaflow_runtime::distributed::protocol::{
     framed, recv_msg, send_msg, ClientToDriver, DriverToClient,
 };
 use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
 use geaflow_runtime::io::file::{
-    read_edges_u64_u8, read_vertices_u64_f64, read_vertices_u64_u64_id_default,
+    read_edges_dict_u8, read_edges_u64_u8, read_vertices_dict, read_vertices_u64_f64,
+    read_vertices_u64_u64_id_default,
 };
+use geaflow_runtime::io::id_dictionary::IdDictionary;
 use geaflow_runtime::observability::init_tracing;
 use geaflow_runtime::plan::execution_plan::ExecutionPlan;
 use geaflow_runtime::plan::job_spec::{
-    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, JobMode, JobSpec,
+    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, IdSpec, JobMode, JobSpec, PartitionerSpec,
 };
 use std::net::SocketAddr;
 use tokio::net::TcpStream;
@@ -28,6 +31,20 @@ enum Mode {
 enum Algorithm {
     Wcc,
     Pagerank,
+    Sssp,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Weights {
+    Unit,
+    EdgeValue,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Partitioner {
+    Hash,
+    Range,
+    Mapping,
 }
 
 #[derive(Debug, Parser)]
@@ -50,6 +67,22 @@ struct Args {
     #[arg(long, default_value_t = 0.85)]
     alpha: f64,
 
+    /// Stops `--algorithm pagerank` once a superstep changes the ranks by less than this in
+    /// total.
+    #[arg(long)]
+    tolerance: Option<f64>,
+
+    /// Start vertex for `--algorithm sssp`.
+    #[arg(long, required_if_eq("algorithm", "sssp"))]
+    source: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = Weights::EdgeValue)]
+    weights: Weights,
+
+    /// Bucket width; runs `--algorithm sssp` by delta-stepping.
+    #[arg(long)]
+    delta: Option<f64>,
+
     #[arg(long, default_value_t = 4)]
     parallelism: usize,
 
@@ -70,12 +103,47 @@ struct Args {
 
     #[arg(long, default_value = "/tmp/geaflow-checkpoints")]
     checkpoint_dir: String,
+
+    #[arg(long, value_enum, default_value_t = Partitioner::Hash)]
+    partitioner: Partitioner,
+
+    /// Upper id bound of every partition but the last, for `--partitioner range`.
+    #[arg(long, value_delimiter = ',')]
+    partition_bounds: Vec<u64>,
+
+    /// `id,partition` lines, for `--partitioner mapping`.
+    #[arg(long, required_if_eq("partitioner", "mapping"))]
+    partition_map: Option<String>,
+
+    /// Read vertex ids as arbitrary strings and print results under them.
+    #[arg(long, default_value_t = false)]
+    id_dictionary: bool,
 }
 
 fn dec<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> T {
     bincode::deserialize(bytes).unwrap()
 }
 
+/// The external id of `id`, or `id` itself when it was not read through the dictionary.
+fn external(dict: &IdDictionary, id: u64) -> String {
+    dict.external(id)
+        .map_or_else(|| id.to_string(), str::to_string)
+}
+
+fn sssp_weights(weights: Weights) -> SsspWeights {
+    match weights {
+        Weights::Unit => SsspWeights::Unit,
+        Weights::EdgeValue => SsspWeights::EdgeValue,
+    }
+}
+
+fn dec_id(bytes: &[u8], ids: IdSpec) -> String {
+    match ids {
+        IdSpec::U64 => dec::<u64>(bytes).to_string(),
+        IdSpec::Dictionary => dec(bytes),
+    }
+}
+
 fn new_job_id() -> String {
     let nanos = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -92,6 +160,23 @@ fn build_job_spec(args: &Args) -> JobSpec {
         Algorithm::Pagerank => AlgorithmSpec::PageRank {
             iterations: args.iterations,
             alpha: args.alpha,
+            tolerance: args.tolerance,
+        },
+        Algorithm::Sssp => AlgorithmSpec::Sssp {
+            iterations: args.iterations,
+            source: args.source.clone().unwrap_or_default(),
+            weights: sssp_weights(args.weights),
+            delta: args.delta,
+        },
+    };
+
+    let partitioner = match args.partitioner {
+        Partitioner::Hash => PartitionerSpec::Hash,
+        Partitioner::Range => PartitionerSpec::Range {
+            upper_bounds: args.partition_bounds.clone(),
+        },
+        Partitioner::Mapping => PartitionerSpec::Mapping {
+            path: args.partition_map.clone().unwrap_or_default(),
         },
     };
 
@@ -111,6 +196,11 @@ fn build_job_spec(args: &Args) -> JobSpec {
             edges: FileSource::Csv {
                 path: args.edges.clone(),
             },
+            ids: if args.id_dictionary {
+                IdSpec::Dictionary
+            } else {
+                IdSpec::U64
+            },
         },
         algorithm,
         checkpoint: CheckpointSpec {
@@ -118,6 +208,7 @@ fn build_job_spec(args: &Args) -> JobSpec {
             interval_iters: args.checkpoint_interval_iters,
             base_dir: args.checkpoint_dir.clone(),
         },
+        partitioner,
     }
 }
 
@@ -187,12 +278,12 @@ async fn run_via_driver(
     for (id, value) in vertices {
         match algorithm {
             Algorithm::Wcc => {
-                let id: u64 = dec(&id);
-                let v: u64 = dec(&value);
+                let id = dec_id(&id, job.graph.ids);
+                let v = dec_id(&value, job.graph.ids);
                 println!("{id},{v}");
             }
-            Algorithm::Pagerank => {
-                let id: u64 = dec(&id);
+            Algorithm::Pagerank | Algorithm::Sssp => {
+                let id = dec_id(&id, job.graph.ids);
                 let v: f64 = dec(&value);
                 println!("{id},{v}");
             }
@@ -228,32 +319,99 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     match args.mode {
-        Mode::Local => match args.algorithm {
-            Algorithm::Wcc => {
-                let vertices = read_vertices_u64_u64_id_default(&args.vertices)?;
-                let edges = read_edges_u64_u8(&args.edges, 0)?;
-                let graph = PartitionedGraph::new(vertices, edges, args.parallelism);
-                let algo = WccAlgorithm::new(args.iterations);
-                let result_graph = graph.compute_algorithm(&algo, args.parallelism);
-                let mut vertices = result_graph.vertices();
-                vertices.sort_by_key(|v| v.id);
-                for v in vertices {
-                    println!("{},{}", v.id, v.value);
+        Mode::Local => {
+            let mut dict = IdDictionary::new();
+            match args.algorithm {
+                Algorithm::Wcc => {
+                    let (vertices, edges) = if args.id_dictionary {
+                        let edges = read_edges_dict_u8(&args.edges, &mut dict, 0)?;
+                        (
+                            read_vertices_dict(&args.vertices, &mut dict, |id| id)?,
+                            edges,
+                        )
+                    } else {
+                        (
+                            read_vertices_u64_u64_id_default(&args.vertices)?,
+                            read_edges_u64_u8(&args.edges, 0)?,
+                        )
+                    };
+                    let graph = PartitionedGraph::new(vertices, edges, args.parallelism);
+                    let algo = WccAlgorithm::new(args.iterations);
+                    let result_graph = graph.compute_algorithm(&algo, args.parallelism);
+                    let mut vertices = result_graph.vertices();
+                    vertices.sort_by_key(|v| v.id);
+                    for v in vertices {
+                        println!("{},{}", external(&dict, v.id), external(&dict, v.value));
+                    }
                 }
-            }
-            Algorithm::Pagerank => {
-                let vertices = read_vertices_u64_f64(&args.vertices, 1.0)?;
-                let edges = read_edges_u64_u8(&args.edges, 0)?;
-                let graph = PartitionedGraph::new(vertices, edges, args.parallelism);
-                let algo = PageRankAlgorithm::new(args.iterations, args.alpha);
-                let result_graph = graph.compute_algorithm(&algo, args.parallelism);
-                let mut vertices = result_graph.vertices();
-                vertices.sort_by_key(|v| v.id);
-                for v in vertices {
-                    println!("{},{}", v.id, v.value);
+                Algorithm::Pagerank => {
+                    let (vertices, edges) = if args.id_dictionary {
+                        let edges = read_edges_dict_u8(&args.edges, &mut dict, 0)?;
+                        (
+                            read_vertices_dict(&args.vertices, &mut dict, |_| 1.0)?,
+                            edges,
+                        )
+                    } else {
+                        (
+                            read_vertices_u64_f64(&args.vertices, 1.0)?,
+                            read_edges_u64_u8(&args.edges, 0)?,
+                        )
+                    };
+                    let graph = PartitionedGraph::new(vertices, edges, args.parallelism);
+                    let mut algo = PageRankAlgorithm::new(args.iterations, args.alpha);
+                    if let Some(tolerance) = args.tolerance {
+                        algo = algo.with_tolerance(tolerance);
+                    }
+                    let result_graph = graph.compute_algorithm(&algo, args.parallelism);
+                    if let Some(residual) = result_graph.aggregated().get(PAGERANK_DELTA) {
+                        eprintln!("pagerank residual: {:e}", residual.as_f64());
+                    }
+                    let mut vertices = result_graph.vertices();
+                    vertices.sort_by_key(|v| v.id);
+                    for v in vertices {
+                        println!("{},{}", external(&dict, v.id), v.value);
+                    }
+                }
+                Algorithm::Sssp => {
+                    let (vertices, edges) = if args.id_dictionary {
+                        let edges = read_edges_dict_u8(&args.edges, &mut dict, 0)?;
+                        (
+                            read_vertices_dict(&args.vertices, &mut dict, |_| f64::INFINITY)?,
+                            edges,
+                        )
+                    } else {
+                        (
+                            read_vertices_u64_f64(&args.vertices, f64::INFINITY)?,
+                            read_edges_u64_u8(&args.edges, 0)?,
+                        )
+                    };
+                    let source = args.source.as_deref().unwrap_or_default();
+                    let source = if args.id_dictionary {
+                        dict.get(source)
+                    } else {
+                        source.parse().ok()
+                    }
+                    .ok_or_else(|| format!("unknown sssp source: {source}"))?;
+                    let graph = PartitionedGraph::new(vertices, edges, args.parallelism);
+                    let weights = sssp_weights(args.weights);
+                    let result_graph = match args.delta {
+                        Some(delta) => graph.compute_algorithm(
+                            &DeltaSteppingAlgorithm::new(source, args.iterations, weights, delta),
+                            args.parallelism,
+                        ),
+                        None => graph.compute_algorithm(
+                            &SsspAlgorithm::new(source, args.iterations, weights),
+                            args.parallelism,
+                        ),
+                    };
+                    let mut vertices = result_graph.vertices();
+                    vertices.sort_by_key(|v| v.id);
+                    for v in vertices {
+                        println!("{},{}", external(&dict, v.id), v.value);
+                    }
                 }
             }
-        },
+        }
         Mode::Distributed => {
             if let Some(driver_addr) = args.driver {
                 run_via_driver(driver_addr, &job, args.algorithm).await?;
@@ -276,12 +434,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             for (id, value) in vertices {
                 match args.algorithm {
                     Algorithm::Wcc => {
-                        let id: u64 = dec(&id);
-                        let v: u64 = dec(&value);
+                        let id = dec_id(&id, job.graph.ids);
+                        let v = dec_id(&value, job.graph.ids);
                         println!("{id},{v}");
                     }
-                    Algorithm::Pagerank => {
-                        let id: u64 = dec(&id);
+                    Algorithm::Pagerank | Algorithm::Sssp => {
+                        let id = dec_id(&id, job.graph.ids);
                         let v: f64 = dec(&value);
                         println!("{id},{v}");
                     }
@@ -1,23 +1,175 @@
+use crate::algorithms::bfs::{bfs_aggregators, BfsMaster, BfsMessage, BfsMode, BfsSend, BfsValue};
+use crate::algorithms::pagerank::{
+    dangling_share, pagerank_aggregators, PageRankMaster, PAGERANK_DANGLING, PAGERANK_DELTA,
+    PAGERANK_VERTICES,
+};
+use crate::algorithms::sssp::{
+    DeltaPhase, DeltaSteppingMaster, SsspTreeValue, SsspWeights, DELTA_NEXT, DELTA_PHASE_START,
+};
+use crate::shuffle::BytesCombiner;
+use geaflow_api::aggregator::{AggregateOp, AggregateValue, AggregatorSpec, Aggregators};
+use geaflow_api::graph::EdgeDirection;
+use geaflow_api::master::{MasterCompute, MasterState};
+use geaflow_api::mutation::GraphMutation;
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use geaflow_common::types::{Edge, Vertex};
 use serde::{Deserialize, Serialize};
 
 pub type OutMessage = (Vec<u8>, Vec<u8>);
 pub type Outbox = Vec<OutMessage>;
 pub type ComputeOutput = (Option<Vec<u8>>, Outbox);
 pub type ComputeResult = GeaFlowResult<ComputeOutput>;
+pub type BytesMutation = GraphMutation<Vec<u8>, Vec<u8>, Vec<u8>>;
+
+pub struct ComputeContext<'a> {
+    pub iteration: u64,
+    pub aggregators: &'a mut Aggregators,
+    pub halted: bool,
+    pub master: &'a MasterState,
+    pub mutations: Vec<BytesMutation>,
+    /// Out-degree across all workers of a vertex whose edges a vertex-cut split; the edges
+    /// passed to compute are only this worker's.
+    pub out_degree: Option<u64>,
+}
+
+impl<'a> ComputeContext<'a> {
+    pub fn new(iteration: u64, aggregators: &'a mut Aggregators, master: &'a MasterState) -> Self {
+        Self {
+            iteration,
+            aggregators,
+            halted: false,
+            master,
+            mutations: Vec::new(),
+            out_degree: None,
+        }
+    }
+
+    pub fn phase(&self) -> u64 {
+        self.master.phase
+    }
+
+    pub fn get_broadcast(&self, name: &str) -> Option<AggregateValue> {
+        self.master.get_broadcast(name)
+    }
+
+    pub fn add_vertex(&mut self, id: Vec<u8>, value: Vec<u8>) {
+        self.mutations
+            .push(GraphMutation::AddVertex(Vertex::new(id, value)));
+    }
+
+    pub fn remove_vertex(&mut self, id: Vec<u8>) {
+        self.mutations.push(GraphMutation::RemoveVertex(id));
+    }
+
+    pub fn add_edge(&mut self, src_id: Vec<u8>, target_id: Vec<u8>, value: Vec<u8>) {
+        self.mutations
+            .push(GraphMutation::AddEdge(Edge::new(src_id, target_id, value)));
+    }
+
+    pub fn remove_edge(&mut self, src_id: Vec<u8>, target_id: Vec<u8>) {
+        self.mutations
+            .push(GraphMutation::RemoveEdge { src_id, target_id });
+    }
+
+    pub fn vote_to_halt(&mut self) {
+        self.halted = true;
+    }
+
+    pub fn aggregate(&mut self, name: &str, value: AggregateValue) {
+        self.aggregators.aggregate(name, value);
+    }
+
+    pub fn get_aggregated(&self, name: &str) -> Option<AggregateValue> {
+        self.aggregators.get_aggregated(name)
+    }
+}
 
 pub trait DistributedAlgorithm: Send {
     fn name(&self) -> &str;
     fn iterations(&self) -> u64;
 
+    /// `edges` are (neighbour id, edge value) pairs in `superstep_edge_direction()`.
     fn compute_vertex(
         &mut self,
         vertex_id: &[u8],
         vertex_value: Option<&[u8]>,
-        out_edges: &[(Vec<u8>, Vec<u8>)],
+        edges: &[(Vec<u8>, Vec<u8>)],
         messages: &[Vec<u8>],
-        iteration: u64,
+        ctx: &mut ComputeContext,
     ) -> ComputeResult;
+
+    /// Runs instead of `compute_vertex` in the first superstep after a graph delta.
+    fn evolve_vertex(
+        &mut self,
+        vertex_id: &[u8],
+        vertex_value: Option<&[u8]>,
+        edges: &[(Vec<u8>, Vec<u8>)],
+        ctx: &mut ComputeContext,
+    ) -> ComputeResult {
+        self.compute_vertex(vertex_id, vertex_value, edges, &[], ctx)
+    }
+
+    fn edge_direction(&self) -> EdgeDirection {
+        EdgeDirection::Out
+    }
+
+    /// The direction of the edges passed to superstep `iteration`, for algorithms that change
+    /// direction between phases.
+    fn superstep_edge_direction(&self, _iteration: u64, _master: &MasterState) -> EdgeDirection {
+        self.edge_direction()
+    }
+
+    fn combiner(&self) -> Option<Box<dyn BytesCombiner>> {
+        None
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        Vec::new()
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        None
+    }
+
+    /// Whether removing part of a vertex holding `touched` makes a vertex holding `value` stale.
+    fn invalidates(&self, _touched: &[u8], _value: &[u8]) -> bool {
+        true
+    }
+
+    /// The value a stale vertex restarts from; `None` keeps the stored value.
+    fn reset_value(&self, _vertex_id: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Whether vertex values are ids of vertices that hold their own id, as WCC component
+    /// labels are; results under an `IdDictionary` translate them like the ids.
+    fn values_are_labels(&self) -> bool {
+        false
+    }
+}
+
+pub fn create_algorithm(
+    name: &str,
+    iterations: u64,
+    params: &[u8],
+) -> GeaFlowResult<Box<dyn DistributedAlgorithm>> {
+    match name {
+        "wcc" => Ok(Box::new(WccAlgorithm::new(iterations))),
+        "pagerank" => Ok(Box::new(PageRankAlgorithm::from_params(
+            iterations, params,
+        )?)),
+        "sssp" => Ok(Box::new(SsspAlgorithm::from_params(iterations, params)?)),
+        "delta_stepping" => Ok(Box::new(DeltaSteppingAlgorithm::from_params(
+            iterations, params,
+        )?)),
+        "sssp_tree" => Ok(Box::new(SsspTreeAlgorithm::from_params(
+            iterations, params,
+        )?)),
+        "bfs" => Ok(Box::new(BfsAlgorithm::from_params(iterations, params)?)),
+        other => Err(GeaFlowError::InvalidArgument(format!(
+            "unknown algorithm: {other}"
+        ))),
+    }
 }
 
 fn encode<T: serde::Serialize>(v: &T) -> GeaFlowResult<Vec<u8>> {
@@ -28,6 +180,53 @@ fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> GeaFlowResult<T> {
     bincode::deserialize(bytes).map_err(|e| GeaFlowError::Internal(format!("bincode decode: {e}")))
 }
 
+pub struct MinU64Combiner;
+
+impl BytesCombiner for MinU64Combiner {
+    fn combine(&self, old_message: &[u8], new_message: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        let a: u64 = decode(old_message)?;
+        let b: u64 = decode(new_message)?;
+        encode(&a.min(b))
+    }
+}
+
+pub struct MinF64Combiner;
+
+impl BytesCombiner for MinF64Combiner {
+    fn combine(&self, old_message: &[u8], new_message: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        let a: f64 = decode(old_message)?;
+        let b: f64 = decode(new_message)?;
+        encode(&a.min(b))
+    }
+}
+
+/// Keeps the shorter of two `(distance, parent)` offers, the smaller parent on a tie.
+pub struct MinDistanceCombiner;
+
+fn shorter(a: (f64, u64), b: (f64, u64)) -> bool {
+    a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)).is_lt()
+}
+
+impl BytesCombiner for MinDistanceCombiner {
+    fn combine(&self, old_message: &[u8], new_message: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        let a: (f64, u64) = decode(old_message)?;
+        let b: (f64, u64) = decode(new_message)?;
+        encode(&if shorter(b, a) { b } else { a })
+    }
+}
+
+pub struct SumF64Combiner;
+
+impl BytesCombiner for SumF64Combiner {
+    fn combine(&self, old_message: &[u8], new_message: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        let a: f64 = decode(old_message)?;
+        let b: f64 = decode(new_message)?;
+        encode(&(a + b))
+    }
+}
+
+pub const WCC_UPDATED_VERTICES: &str = "updated_vertices";
+
 #[derive(Debug, Clone)]
 pub struct WccAlgorithm {
     iterations: u64,
@@ -44,6 +243,10 @@ impl DistributedAlgorithm for WccAlgorithm {
         "wcc"
     }
 
+    fn values_are_labels(&self) -> bool {
+        true
+    }
+
     fn iterations(&self) -> u64 {
         self.iterations
     }
@@ -52,17 +255,19 @@ impl DistributedAlgorithm for WccAlgorithm {
         &mut self,
         vertex_id: &[u8],
         vertex_value: Option<&[u8]>,
-        out_edges: &[(Vec<u8>, Vec<u8>)],
+        edges: &[(Vec<u8>, Vec<u8>)],
         messages: &[Vec<u8>],
-        iteration: u64,
+        ctx: &mut ComputeContext,
     ) -> ComputeResult {
         let vid: u64 = decode(vertex_id)?;
         let mut current: u64 = vertex_value.map(decode).transpose()?.unwrap_or(vid);
 
-        if iteration == 1 {
+        if ctx.iteration == 1 {
             current = vid;
+            ctx.aggregate(WCC_UPDATED_VERTICES, 1u64.into());
+            ctx.vote_to_halt();
             let msg = encode(&current)?;
-            let out = out_edges
+            let out = edges
                 .iter()
                 .map(|(t, _)| (t.clone(), msg.clone()))
                 .collect();
@@ -77,9 +282,11 @@ impl DistributedAlgorithm for WccAlgorithm {
             }
         }
 
+        ctx.vote_to_halt();
         if min_comp < current {
+            ctx.aggregate(WCC_UPDATED_VERTICES, 1u64.into());
             let msg = encode(&min_comp)?;
-            let out = out_edges
+            let out = edges
                 .iter()
                 .map(|(t, _)| (t.clone(), msg.clone()))
                 .collect();
@@ -88,17 +295,62 @@ impl DistributedAlgorithm for WccAlgorithm {
             Ok((None, Vec::new()))
         }
     }
+
+    fn evolve_vertex(
+        &mut self,
+        vertex_id: &[u8],
+        vertex_value: Option<&[u8]>,
+        edges: &[(Vec<u8>, Vec<u8>)],
+        ctx: &mut ComputeContext,
+    ) -> ComputeResult {
+        let msg = match vertex_value {
+            Some(v) => v.to_vec(),
+            None => vertex_id.to_vec(),
+        };
+        ctx.vote_to_halt();
+        let out = edges
+            .iter()
+            .map(|(t, _)| (t.clone(), msg.clone()))
+            .collect();
+        Ok((None, out))
+    }
+
+    fn edge_direction(&self) -> EdgeDirection {
+        EdgeDirection::Both
+    }
+
+    fn combiner(&self) -> Option<Box<dyn BytesCombiner>> {
+        Some(Box::new(MinU64Combiner))
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        vec![AggregatorSpec::new(
+            WCC_UPDATED_VERTICES,
+            AggregateOp::Count,
+        )]
+    }
+
+    fn invalidates(&self, touched: &[u8], value: &[u8]) -> bool {
+        touched == value
+    }
+
+    fn reset_value(&self, vertex_id: &[u8]) -> Option<Vec<u8>> {
+        Some(vertex_id.to_vec())
+    }
 }
 
+/// `tolerance` stops the job once a superstep changes the ranks by less than that in total.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PageRankParams {
     pub alpha: f64,
+    pub tolerance: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PageRankAlgorithm {
     iterations: u64,
     alpha: f64,
+    tolerance: Option<f64>,
     teleport: Option<f64>,
 }
 
@@ -107,13 +359,22 @@ impl PageRankAlgorithm {
         Self {
             iterations,
             alpha,
+            tolerance: None,
             teleport: None,
         }
     }
 
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
     pub fn from_params(iterations: u64, params: &[u8]) -> GeaFlowResult<Self> {
         let p: PageRankParams = decode(params)?;
-        Ok(Self::new(iterations, p.alpha))
+        Ok(Self {
+            tolerance: p.tolerance,
+            ..Self::new(iterations, p.alpha)
+        })
     }
 }
 
@@ -132,32 +393,31 @@ impl DistributedAlgorithm for PageRankAlgorithm {
         vertex_value: Option<&[u8]>,
         out_edges: &[(Vec<u8>, Vec<u8>)],
         messages: &[Vec<u8>],
-        iteration: u64,
+        ctx: &mut ComputeContext,
     ) -> ComputeResult {
-        let out_degree = out_edges.len() as f64;
+        let out_degree = ctx.out_degree.unwrap_or(out_edges.len() as u64) as f64;
         let vertex_value: f64 = vertex_value.map(decode).transpose()?.unwrap_or(0.0);
         if self.teleport.is_none() && vertex_value > 0.0 {
             self.teleport = Some((1.0 - self.alpha) * vertex_value);
         }
+        ctx.aggregate(PAGERANK_VERTICES, 1u64.into());
 
-        if iteration == 1 {
-            if out_degree > 0.0 {
-                let msg = encode(&(vertex_value / out_degree))?;
-                let out = out_edges
-                    .iter()
-                    .map(|(t, _)| (t.clone(), msg.clone()))
-                    .collect();
-                return Ok((None, out));
+        let (pr, new_value) = if ctx.iteration == 1 {
+            (vertex_value, None)
+        } else {
+            let dangling = dangling_share(
+                ctx.get_aggregated(PAGERANK_DANGLING),
+                ctx.get_aggregated(PAGERANK_VERTICES),
+            );
+            let mut sum = 0.0;
+            for m in messages {
+                let v: f64 = decode(m)?;
+                sum += v;
             }
-            return Ok((None, Vec::new()));
-        }
-
-        let mut sum = 0.0;
-        for m in messages {
-            let v: f64 = decode(m)?;
-            sum += v;
-        }
-        let pr = sum * self.alpha + self.teleport.unwrap_or(1.0 - self.alpha);
+            let pr = (sum + dangling) * self.alpha + self.teleport.unwrap_or(1.0 - self.alpha);
+            ctx.aggregate(PAGERANK_DELTA, (pr - vertex_value).abs().into());
+            (pr, Some(encode(&pr)?))
+        };
 
         let mut out = Vec::new();
         if out_degree > 0.0 {
@@ -166,8 +426,467 @@ impl DistributedAlgorithm for PageRankAlgorithm {
                 .iter()
                 .map(|(t, _)| (t.clone(), msg.clone()))
                 .collect();
+        } else {
+            ctx.aggregate(PAGERANK_DANGLING, pr.into());
+        }
+
+        Ok((new_value, out))
+    }
+
+    fn combiner(&self) -> Option<Box<dyn BytesCombiner>> {
+        Some(Box::new(SumF64Combiner))
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        pagerank_aggregators()
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(PageRankMaster {
+            tolerance: self.tolerance,
+        }))
+    }
+}
+
+fn edge_weight(weights: SsspWeights, value: &[u8]) -> GeaFlowResult<f64> {
+    match weights {
+        SsspWeights::Unit => Ok(1.0),
+        SsspWeights::EdgeValue => Ok(weights.weight(decode(value)?)),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsspParams {
+    pub source: u64,
+    pub weights: SsspWeights,
+}
+
+/// Single-source shortest paths over `u64` ids and `u8` edge values; vertex values become `f64`
+/// distances, `f64::INFINITY` where unreachable.
+#[derive(Debug, Clone)]
+pub struct SsspAlgorithm {
+    iterations: u64,
+    source: Vec<u8>,
+    weights: SsspWeights,
+}
+
+impl SsspAlgorithm {
+    pub fn new(iterations: u64, source: u64, weights: SsspWeights) -> GeaFlowResult<Self> {
+        Ok(Self {
+            iterations,
+            source: encode(&source)?,
+            weights,
+        })
+    }
+
+    pub fn from_params(iterations: u64, params: &[u8]) -> GeaFlowResult<Self> {
+        let p: SsspParams = decode(params)?;
+        Self::new(iterations, p.source, p.weights)
+    }
+
+    fn relax(&self, distance: f64, out_edges: &[(Vec<u8>, Vec<u8>)]) -> GeaFlowResult<Outbox> {
+        out_edges
+            .iter()
+            .map(|(t, value)| {
+                let weight = edge_weight(self.weights, value)?;
+                Ok((t.clone(), encode(&(distance + weight))?))
+            })
+            .collect()
+    }
+}
+
+impl DistributedAlgorithm for SsspAlgorithm {
+    fn name(&self) -> &str {
+        "sssp"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    fn compute_vertex(
+        &mut self,
+        vertex_id: &[u8],
+        vertex_value: Option<&[u8]>,
+        out_edges: &[(Vec<u8>, Vec<u8>)],
+        messages: &[Vec<u8>],
+        ctx: &mut ComputeContext,
+    ) -> ComputeResult {
+        ctx.vote_to_halt();
+        let first = ctx.iteration == 1;
+        let current: f64 = if first {
+            f64::INFINITY
+        } else {
+            vertex_value
+                .map(decode)
+                .transpose()?
+                .unwrap_or(f64::INFINITY)
+        };
+        let mut best = if first && vertex_id == self.source.as_slice() {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+        for m in messages {
+            let d: f64 = decode(m)?;
+            best = best.min(d);
+        }
+
+        if best < current {
+            Ok((Some(encode(&best)?), self.relax(best, out_edges)?))
+        } else if first {
+            Ok((Some(encode(&f64::INFINITY)?), Vec::new()))
+        } else {
+            Ok((None, Vec::new()))
         }
+    }
+
+    fn evolve_vertex(
+        &mut self,
+        _vertex_id: &[u8],
+        vertex_value: Option<&[u8]>,
+        out_edges: &[(Vec<u8>, Vec<u8>)],
+        ctx: &mut ComputeContext,
+    ) -> ComputeResult {
+        ctx.vote_to_halt();
+        let current: f64 = vertex_value
+            .map(decode)
+            .transpose()?
+            .unwrap_or(f64::INFINITY);
+        if current.is_finite() {
+            Ok((None, self.relax(current, out_edges)?))
+        } else {
+            Ok((None, Vec::new()))
+        }
+    }
+
+    fn combiner(&self) -> Option<Box<dyn BytesCombiner>> {
+        Some(Box::new(MinF64Combiner))
+    }
+
+    /// A removal can lengthen any path, so every distance is recomputed from the source.
+    fn reset_value(&self, vertex_id: &[u8]) -> Option<Vec<u8>> {
+        let distance = if vertex_id == self.source.as_slice() {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+        encode(&distance).ok()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsspTreeParams {
+    pub source: u64,
+}
+
+/// `SsspAlgorithm` that also records each vertex's parent in the shortest-path tree. Edge values
+/// are bincode `f64` weights and vertex values bincode `SsspTreeValue`s.
+#[derive(Debug, Clone)]
+pub struct SsspTreeAlgorithm {
+    iterations: u64,
+    source: u64,
+}
+
+impl SsspTreeAlgorithm {
+    pub fn new(iterations: u64, source: u64) -> Self {
+        Self { iterations, source }
+    }
+
+    pub fn from_params(iterations: u64, params: &[u8]) -> GeaFlowResult<Self> {
+        let p: SsspTreeParams = decode(params)?;
+        Ok(Self::new(iterations, p.source))
+    }
+}
+
+impl DistributedAlgorithm for SsspTreeAlgorithm {
+    fn name(&self) -> &str {
+        "sssp_tree"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    fn compute_vertex(
+        &mut self,
+        vertex_id: &[u8],
+        vertex_value: Option<&[u8]>,
+        out_edges: &[(Vec<u8>, Vec<u8>)],
+        messages: &[Vec<u8>],
+        ctx: &mut ComputeContext,
+    ) -> ComputeResult {
+        ctx.vote_to_halt();
+        let id: u64 = decode(vertex_id)?;
+        let first = ctx.iteration == 1;
+        let current = if first {
+            SsspTreeValue::unreached()
+        } else {
+            vertex_value
+                .map(decode)
+                .transpose()?
+                .unwrap_or_else(SsspTreeValue::unreached)
+        };
+        let mut best = (first && id == self.source).then_some((0.0, id));
+        for m in messages {
+            let offer: (f64, u64) = decode(m)?;
+            if best.is_none_or(|b| shorter(offer, b)) {
+                best = Some(offer);
+            }
+        }
+
+        match best {
+            Some((distance, parent)) if distance < current.distance => {
+                let mut out = Vec::with_capacity(out_edges.len());
+                for (t, value) in out_edges {
+                    let weight: f64 = decode(value)?;
+                    out.push((t.clone(), encode(&(distance + weight, id))?));
+                }
+                Ok((Some(encode(&SsspTreeValue { distance, parent })?), out))
+            }
+            _ if first => Ok((Some(encode(&current)?), Vec::new())),
+            _ => Ok((None, Vec::new())),
+        }
+    }
+
+    fn combiner(&self) -> Option<Box<dyn BytesCombiner>> {
+        Some(Box::new(MinDistanceCombiner))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaSteppingParams {
+    pub source: u64,
+    pub weights: SsspWeights,
+    pub delta: f64,
+}
+
+/// `SsspAlgorithm` by delta-stepping; the driver's master moves the job through the distance
+/// buckets between supersteps.
+#[derive(Debug, Clone)]
+pub struct DeltaSteppingAlgorithm {
+    iterations: u64,
+    source: Vec<u8>,
+    weights: SsspWeights,
+    delta: f64,
+}
+
+impl DeltaSteppingAlgorithm {
+    pub fn new(
+        iterations: u64,
+        source: u64,
+        weights: SsspWeights,
+        delta: f64,
+    ) -> GeaFlowResult<Self> {
+        if !(delta > 0.0 && delta.is_finite()) {
+            return Err(GeaFlowError::InvalidArgument(format!(
+                "delta must be positive, got {delta}"
+            )));
+        }
+        Ok(Self {
+            iterations,
+            source: encode(&source)?,
+            weights,
+            delta,
+        })
+    }
+
+    pub fn from_params(iterations: u64, params: &[u8]) -> GeaFlowResult<Self> {
+        let p: DeltaSteppingParams = decode(params)?;
+        Self::new(iterations, p.source, p.weights, p.delta)
+    }
+}
+
+impl DistributedAlgorithm for DeltaSteppingAlgorithm {
+    fn name(&self) -> &str {
+        "delta_stepping"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    fn compute_vertex(
+        &mut self,
+        vertex_id: &[u8],
+        vertex_value: Option<&[u8]>,
+        out_edges: &[(Vec<u8>, Vec<u8>)],
+        messages: &[Vec<u8>],
+        ctx: &mut ComputeContext,
+    ) -> ComputeResult {
+        ctx.vote_to_halt();
+        let first = ctx.iteration == 1;
+        let phase = DeltaPhase::new(
+            self.delta,
+            ctx.phase(),
+            ctx.iteration,
+            ctx.get_broadcast(DELTA_PHASE_START),
+        );
+        let current: f64 = if first {
+            f64::INFINITY
+        } else {
+            vertex_value
+                .map(decode)
+                .transpose()?
+                .unwrap_or(f64::INFINITY)
+        };
+        let mut best = if first && vertex_id == self.source.as_slice() {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+        for m in messages {
+            let d: f64 = decode(m)?;
+            best = best.min(d);
+        }
+        let improved = best < current;
+        let distance = best.min(current);
+
+        if let Some(pending) = phase.pending(distance, improved) {
+            ctx.aggregate(DELTA_NEXT, pending.into());
+        }
+        let mut out = Vec::new();
+        if phase.relaxes(distance, improved) {
+            for (t, value) in out_edges {
+                let weight = edge_weight(self.weights, value)?;
+                if phase.takes(weight) {
+                    out.push((t.clone(), encode(&(distance + weight))?));
+                }
+            }
+        }
+        let value = if improved || first {
+            Some(encode(&distance)?)
+        } else {
+            None
+        };
+        Ok((value, out))
+    }
+
+    fn combiner(&self) -> Option<Box<dyn BytesCombiner>> {
+        Some(Box::new(MinF64Combiner))
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        vec![AggregatorSpec::new(DELTA_NEXT, AggregateOp::Min)]
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(DeltaSteppingMaster { delta: self.delta }))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BfsParams {
+    pub source: u64,
+}
+
+/// Direction-optimizing BFS over `u64` ids; values are bincode `BfsValue`s. Bottom-up
+/// supersteps read in-edges, which every loader stores.
+#[derive(Debug, Clone)]
+pub struct BfsAlgorithm {
+    iterations: u64,
+    source: u64,
+}
+
+impl BfsAlgorithm {
+    pub fn new(iterations: u64, source: u64) -> Self {
+        Self { iterations, source }
+    }
+
+    pub fn from_params(iterations: u64, params: &[u8]) -> GeaFlowResult<Self> {
+        let p: BfsParams = decode(params)?;
+        Ok(Self::new(iterations, p.source))
+    }
+
+    fn mode(iteration: u64, master: &MasterState) -> BfsMode {
+        BfsMode::new(master.phase, iteration, |name| master.get_broadcast(name))
+    }
+}
+
+impl DistributedAlgorithm for BfsAlgorithm {
+    fn name(&self) -> &str {
+        "bfs"
+    }
+
+    fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    fn compute_vertex(
+        &mut self,
+        vertex_id: &[u8],
+        vertex_value: Option<&[u8]>,
+        edges: &[(Vec<u8>, Vec<u8>)],
+        messages: &[Vec<u8>],
+        ctx: &mut ComputeContext,
+    ) -> ComputeResult {
+        let mode = Self::mode(ctx.iteration, ctx.master);
+        let id: u64 = decode(vertex_id)?;
+        let value: Option<BfsValue> = if ctx.iteration == 1 {
+            None
+        } else {
+            vertex_value.map(decode).transpose()?
+        };
+        let messages = messages
+            .iter()
+            .map(|m| decode(m))
+            .collect::<GeaFlowResult<Vec<BfsMessage>>>()?;
+        let step = mode.step(self.source, id, ctx.iteration, value, messages);
+
+        let degree = match mode.edge_direction() {
+            EdgeDirection::In => edges.len() as u64,
+            _ => ctx.out_degree.unwrap_or(edges.len() as u64),
+        };
+        for (name, value) in step.aggregates(ctx.iteration, degree) {
+            ctx.aggregate(name, value);
+        }
+        if step.halt {
+            ctx.vote_to_halt();
+        }
+        let out = match step.send {
+            BfsSend::None => Vec::new(),
+            BfsSend::OutNeighbours(m) => {
+                let m = encode(&m)?;
+                edges.iter().map(|(t, _)| (t.clone(), m.clone())).collect()
+            }
+            BfsSend::InNeighbours {
+                message,
+                skip,
+                take,
+            } => {
+                let m = encode(&message)?;
+                edges
+                    .iter()
+                    .skip(skip)
+                    .take(take)
+                    .map(|(t, _)| (t.clone(), m.clone()))
+                    .collect()
+            }
+            BfsSend::Vertices(targets, m) => {
+                let m = encode(&m)?;
+                targets
+                    .iter()
+                    .map(|t| Ok((encode(t)?, m.clone())))
+                    .collect::<GeaFlowResult<Outbox>>()?
+            }
+        };
+        let value = if step.changed {
+            Some(encode(&step.value)?)
+        } else {
+            None
+        };
+        Ok((value, out))
+    }
+
+    fn superstep_edge_direction(&self, iteration: u64, master: &MasterState) -> EdgeDirection {
+        Self::mode(iteration, master).edge_direction()
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        bfs_aggregators()
+    }
 
-        Ok((Some(encode(&pr)?), out))
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(BfsMaster::default()))
     }
 }
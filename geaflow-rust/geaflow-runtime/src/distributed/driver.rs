@@ -1,12 +1,24 @@
+use crate::distributed::algorithm::{create_algorithm, BytesMutation};
 use crate::distributed::protocol::{
-    framed, recv_msg, send_msg, DriverFramed, DriverToWorker, WorkerToDriver,
+    framed, recv_msg, send_msg, DriverFramed, DriverToWorker, MirrorUpdate, WorkerToDriver,
 };
-use crate::shuffle::MessageShuffle;
+use crate::distributed::stream::{StreamRecord, WindowResults, WindowSpec};
+use crate::io::id_dictionary::IdDictionary;
+use crate::shuffle::partitioner::{create_partitioner, HashPartitioner, Partitioner};
+use crate::shuffle::vertex_cut::{EdgePartitioning, EdgePlacer};
+use crate::shuffle::{BytesCombiner, DriverShuffle, MessageShuffle};
+use geaflow_api::aggregator::{AggregateValues, Aggregators};
+use geaflow_api::master::{MasterCompute, MasterState, MasterStep};
+use geaflow_api::mutation::{resolve_mutations, GraphMutation};
+use geaflow_api::window::WatermarkStrategy;
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
-use std::collections::HashMap;
+use geaflow_common::types::{Edge, Vertex};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::io::BufRead;
 use std::net::SocketAddr;
 use std::path::Path;
+use std::sync::Arc;
 use tokio::net::TcpStream;
 use tokio::time::{sleep, Duration};
 
@@ -14,102 +26,235 @@ pub type Inbox = HashMap<Vec<u8>, Vec<Vec<u8>>>;
 pub type Inboxes = Vec<Inbox>;
 type EdgeBytes = (Vec<u8>, Vec<u8>, Vec<u8>);
 
+fn encode<T: Serialize>(v: &T) -> GeaFlowResult<Vec<u8>> {
+    bincode::serialize(v).map_err(|e| GeaFlowError::Internal(format!("bincode encode: {e}")))
+}
+
+/// Where a Graph500 load takes its vertex ids from.
+#[derive(Clone, Copy)]
+enum Graph500Vertices<'a> {
+    /// One id per line; blank and `#` lines are skipped.
+    File(&'a Path),
+    /// The ids `0..count`.
+    Generated(u64),
+}
+
+impl Graph500Vertices<'_> {
+    fn ids(self) -> GeaFlowResult<Box<dyn Iterator<Item = GeaFlowResult<u64>> + Send>> {
+        let path = match self {
+            Graph500Vertices::File(path) => path,
+            Graph500Vertices::Generated(count) => return Ok(Box::new((0..count).map(Ok))),
+        };
+        let file = std::fs::File::open(path)
+            .map_err(|e| GeaFlowError::Internal(format!("open vertices: {e}")))?;
+        let ids = std::io::BufReader::new(file)
+            .lines()
+            .map(parse_vertex_id)
+            .filter_map(Result::transpose);
+        Ok(Box::new(ids))
+    }
+}
+
+/// The id on a Graph500 vertex line, `None` for blank and `#` lines.
+fn parse_vertex_id(line: std::io::Result<String>) -> GeaFlowResult<Option<u64>> {
+    let line = line.map_err(|e| GeaFlowError::Internal(format!("read vertices: {e}")))?;
+    let s = line.trim();
+    if s.is_empty() || s.starts_with('#') {
+        return Ok(None);
+    }
+    let id = s
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| GeaFlowError::InvalidArgument("vertex id missing".to_string()))?
+        .parse()
+        .map_err(|e| GeaFlowError::InvalidArgument(format!("vertex id parse: {e}")))?;
+    Ok(Some(id))
+}
+
+/// Edges waiting to be loaded, per worker: each edge is sent out under its source's worker and
+/// in under its target's, or both under the worker a vertex-cut placed it on.
+struct EdgeBuffers {
+    out: Vec<Vec<EdgeBytes>>,
+    inbound: Vec<Vec<EdgeBytes>>,
+    batch_size: usize,
+    placer: EdgePlacer<Vec<u8>>,
+}
+
+impl EdgeBuffers {
+    fn new(workers: usize, batch_size: usize, edge_partitioning: EdgePartitioning) -> Self {
+        Self {
+            out: (0..workers).map(|_| Vec::new()).collect(),
+            inbound: (0..workers).map(|_| Vec::new()).collect(),
+            batch_size: batch_size.max(1),
+            placer: EdgePlacer::new(edge_partitioning, workers),
+        }
+    }
+}
+
 pub struct DistributedDriver {
     workers: Vec<DriverFramed>,
+    combiner: Option<Box<dyn BytesCombiner>>,
+    aggregators: Aggregators,
+    master: Option<Box<dyn MasterCompute>>,
+    master_state: MasterState,
+    evolving: bool,
+    watermarks: WatermarkStrategy,
+    max_timestamp: Option<u64>,
+    partitioner: Arc<dyn Partitioner>,
+    edge_partitioning: EdgePartitioning,
+    /// Under a vertex-cut, the workers holding a mirror of each split vertex.
+    mirrors: HashMap<Vec<u8>, Vec<usize>>,
+    /// Vertices that had not voted to halt after the last superstep.
+    active_vertices: u64,
 }
 
 impl DistributedDriver {
+    /// Loads and runs `job`, returning bincode `(id, value)` pairs; ids, and WCC labels, are
+    /// `String`s under `IdSpec::Dictionary`, `u64`s otherwise.
     pub async fn run_job(
         worker_addrs: &[SocketAddr],
         job: &crate::plan::job_spec::JobSpec,
     ) -> GeaFlowResult<Vec<(Vec<u8>, Vec<u8>)>> {
         let mut driver = Self::connect(worker_addrs).await?;
+        driver.set_partitioner(create_partitioner(&job.partitioner)?);
 
+        let mut dict = match job.graph.ids {
+            crate::plan::job_spec::IdSpec::U64 => None,
+            crate::plan::job_spec::IdSpec::Dictionary => Some(IdDictionary::new()),
+        };
         let edges = match &job.graph.edges {
-            crate::plan::job_spec::FileSource::Csv { path } => {
-                crate::io::file::read_edges_u64_u8(path, 0)?
-            }
+            crate::plan::job_spec::FileSource::Csv { path } => match dict.as_mut() {
+                Some(dict) => crate::io::file::read_edges_dict_u8(path, dict, 0)?,
+                None => crate::io::file::read_edges_u64_u8(path, 0)?,
+            },
         };
 
         match &job.algorithm {
             crate::plan::job_spec::AlgorithmSpec::Wcc { iterations } => {
                 let vertices = match &job.graph.vertices {
-                    crate::plan::job_spec::FileSource::Csv { path } => {
-                        crate::io::file::read_vertices_u64_u64_id_default(path)?
-                    }
+                    crate::plan::job_spec::FileSource::Csv { path } => match dict.as_mut() {
+                        Some(dict) => crate::io::file::read_vertices_dict(path, dict, |id| id)?,
+                        None => crate::io::file::read_vertices_u64_u64_id_default(path)?,
+                    },
                 };
 
-                let vertices: Vec<(Vec<u8>, Vec<u8>)> = vertices
-                    .into_iter()
-                    .map(|v| {
-                        (
-                            bincode::serialize(&v.id).unwrap(),
-                            bincode::serialize(&v.value).unwrap(),
-                        )
-                    })
-                    .collect();
-                let edges: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> = edges
-                    .into_iter()
-                    .map(|e| {
-                        (
-                            bincode::serialize(&e.src_id).unwrap(),
-                            bincode::serialize(&e.target_id).unwrap(),
-                            bincode::serialize(&e.value).unwrap(),
-                        )
-                    })
-                    .collect();
-
-                driver.load_graph(vertices, edges).await?;
+                driver
+                    .load_parsed_graph(vertices, edges, dict.as_ref())
+                    .await?;
                 driver
                     .set_algorithm("wcc".to_string(), *iterations, Vec::new())
                     .await?;
                 crate::scheduler::cycle_scheduler::CycleScheduler::run(&mut driver, job).await?;
             }
-            crate::plan::job_spec::AlgorithmSpec::PageRank { iterations, alpha } => {
+            crate::plan::job_spec::AlgorithmSpec::PageRank {
+                iterations,
+                alpha,
+                tolerance,
+            } => {
                 let vertices = match &job.graph.vertices {
-                    crate::plan::job_spec::FileSource::Csv { path } => {
-                        crate::io::file::read_vertices_u64_f64(path, 1.0)?
-                    }
+                    crate::plan::job_spec::FileSource::Csv { path } => match dict.as_mut() {
+                        Some(dict) => crate::io::file::read_vertices_dict(path, dict, |_| 1.0)?,
+                        None => crate::io::file::read_vertices_u64_f64(path, 1.0)?,
+                    },
                 };
 
-                let vertices: Vec<(Vec<u8>, Vec<u8>)> = vertices
-                    .into_iter()
-                    .map(|v| {
-                        (
-                            bincode::serialize(&v.id).unwrap(),
-                            bincode::serialize(&v.value).unwrap(),
-                        )
-                    })
-                    .collect();
-                let edges: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> = edges
-                    .into_iter()
-                    .map(|e| {
-                        (
-                            bincode::serialize(&e.src_id).unwrap(),
-                            bincode::serialize(&e.target_id).unwrap(),
-                            bincode::serialize(&e.value).unwrap(),
-                        )
-                    })
-                    .collect();
-
-                driver.load_graph(vertices, edges).await?;
-                let params = crate::distributed::algorithm::PageRankParams { alpha: *alpha };
                 driver
-                    .set_algorithm(
-                        "pagerank".to_string(),
-                        *iterations,
-                        bincode::serialize(&params).unwrap(),
-                    )
+                    .load_parsed_graph(vertices, edges, dict.as_ref())
+                    .await?;
+                let params = crate::distributed::algorithm::PageRankParams {
+                    alpha: *alpha,
+                    tolerance: *tolerance,
+                };
+                driver
+                    .set_algorithm("pagerank".to_string(), *iterations, encode(&params)?)
+                    .await?;
+                crate::scheduler::cycle_scheduler::CycleScheduler::run(&mut driver, job).await?;
+            }
+            crate::plan::job_spec::AlgorithmSpec::Sssp {
+                iterations,
+                source,
+                weights,
+                delta,
+            } => {
+                let vertices = match &job.graph.vertices {
+                    crate::plan::job_spec::FileSource::Csv { path } => match dict.as_mut() {
+                        Some(dict) => {
+                            crate::io::file::read_vertices_dict(path, dict, |_| f64::INFINITY)?
+                        }
+                        None => crate::io::file::read_vertices_u64_f64(path, f64::INFINITY)?,
+                    },
+                };
+                let source = match &dict {
+                    Some(dict) => dict.get(source),
+                    None => source.parse().ok(),
+                }
+                .ok_or_else(|| {
+                    GeaFlowError::InvalidArgument(format!("unknown sssp source: {source}"))
+                })?;
+
+                driver
+                    .load_parsed_graph(vertices, edges, dict.as_ref())
+                    .await?;
+                let (name, params) = match delta {
+                    Some(delta) => (
+                        "delta_stepping",
+                        encode(&crate::distributed::algorithm::DeltaSteppingParams {
+                            source,
+                            weights: *weights,
+                            delta: *delta,
+                        })?,
+                    ),
+                    None => (
+                        "sssp",
+                        encode(&crate::distributed::algorithm::SsspParams {
+                            source,
+                            weights: *weights,
+                        })?,
+                    ),
+                };
+                driver
+                    .set_algorithm(name.to_string(), *iterations, params)
                     .await?;
                 crate::scheduler::cycle_scheduler::CycleScheduler::run(&mut driver, job).await?;
             }
         }
 
-        let vertices = driver.fetch_vertices().await?;
+        let vertices = match dict {
+            Some(_) => driver
+                .fetch_vertices_by_external_id()
+                .await?
+                .into_iter()
+                .map(|(id, value)| Ok((encode(&id)?, value)))
+                .collect::<GeaFlowResult<_>>()?,
+            None => driver.fetch_vertices().await?,
+        };
         driver.shutdown().await?;
         Ok(vertices)
     }
 
+    /// Loads vertices and edges parsed from a job's files, bincode-encoded, along with the
+    /// dictionary their ids were interned in.
+    async fn load_parsed_graph<VV: Serialize>(
+        &mut self,
+        vertices: Vec<Vertex<u64, VV>>,
+        edges: Vec<Edge<u64, u8>>,
+        dict: Option<&IdDictionary>,
+    ) -> GeaFlowResult<()> {
+        let vertices = vertices
+            .iter()
+            .map(|v| Ok((encode(&v.id)?, encode(&v.value)?)))
+            .collect::<GeaFlowResult<_>>()?;
+        let edges = edges
+            .iter()
+            .map(|e| Ok((encode(&e.src_id)?, encode(&e.target_id)?, encode(&e.value)?)))
+            .collect::<GeaFlowResult<_>>()?;
+        self.load_graph(vertices, edges).await?;
+        if let Some(dict) = dict {
+            self.load_id_dictionary(dict).await?;
+        }
+        Ok(())
+    }
+
     pub async fn connect(worker_addrs: &[SocketAddr]) -> GeaFlowResult<Self> {
         let mut workers = Vec::with_capacity(worker_addrs.len());
         for addr in worker_addrs {
@@ -141,13 +286,84 @@ impl DistributedDriver {
             }
             workers.push(framed);
         }
-        Ok(Self { workers })
+        Ok(Self {
+            workers,
+            combiner: None,
+            aggregators: Aggregators::default(),
+            master: None,
+            master_state: MasterState::default(),
+            evolving: false,
+            watermarks: WatermarkStrategy::default(),
+            max_timestamp: None,
+            partitioner: Arc::new(HashPartitioner),
+            edge_partitioning: EdgePartitioning::EdgeCut,
+            mirrors: HashMap::new(),
+            active_vertices: 0,
+        })
+    }
+
+    /// Decides which worker owns each vertex and stream key. Set it before loading anything.
+    pub fn set_partitioner(&mut self, partitioner: Arc<dyn Partitioner>) {
+        self.partitioner = partitioner;
+    }
+
+    pub fn partitioner(&self) -> &dyn Partitioner {
+        self.partitioner.as_ref()
+    }
+
+    /// How the next load spreads edges over workers. Graphs loaded with a vertex-cut do not
+    /// support topology mutations or deltas.
+    pub fn set_edge_partitioning(&mut self, edge_partitioning: EdgePartitioning) {
+        self.edge_partitioning = edge_partitioning;
+    }
+
+    pub fn edge_partitioning(&self) -> EdgePartitioning {
+        self.edge_partitioning
+    }
+
+    /// Workers mirroring each split vertex of the loaded graph.
+    pub fn mirrors(&self) -> &HashMap<Vec<u8>, Vec<usize>> {
+        &self.mirrors
     }
 
     pub fn worker_count(&self) -> usize {
         self.workers.len()
     }
 
+    pub fn aggregated(&self) -> &AggregateValues {
+        self.aggregators.previous()
+    }
+
+    pub fn restore_aggregated(&mut self, values: AggregateValues) {
+        self.aggregators.set_previous(values);
+    }
+
+    pub fn master_state(&self) -> &MasterState {
+        &self.master_state
+    }
+
+    pub fn restore_master_state(&mut self, state: MasterState) {
+        self.master_state = state;
+    }
+
+    pub fn active_vertices(&self) -> u64 {
+        self.active_vertices
+    }
+
+    pub fn restore_active_vertices(&mut self, active_vertices: u64) {
+        self.active_vertices = active_vertices;
+    }
+
+    pub fn before_superstep(&mut self, iteration: u64, messages_pending: bool) -> MasterStep {
+        self.master_state.before_superstep(
+            &mut self.master,
+            iteration,
+            messages_pending,
+            self.active_vertices,
+            self.aggregators.previous(),
+        )
+    }
+
     pub fn new_inboxes(worker_count: usize) -> Inboxes {
         (0..worker_count).map(|_| HashMap::new()).collect()
     }
@@ -168,6 +384,9 @@ impl DistributedDriver {
                         iteration,
                         inbox: Vec::new(),
                         last: true,
+                        aggregates: self.aggregators.previous().clone(),
+                        master: self.master_state.clone(),
+                        evolve: self.evolving,
                     },
                 )
                 .await?;
@@ -182,6 +401,17 @@ impl DistributedDriver {
                             iteration,
                             inbox: inbox_vec[offset..end].to_vec(),
                             last,
+                            aggregates: if last {
+                                self.aggregators.previous().clone()
+                            } else {
+                                AggregateValues::new()
+                            },
+                            master: if last {
+                                self.master_state.clone()
+                            } else {
+                                MasterState::default()
+                            },
+                            evolve: self.evolving,
                         },
                     )
                     .await?;
@@ -190,27 +420,37 @@ impl DistributedDriver {
             }
         }
 
+        self.evolving = false;
+
         let mut next_inboxes: Inboxes = (0..n).map(|_| HashMap::new()).collect();
         let mut any_msg = false;
+        let mut mutations: Vec<BytesMutation> = Vec::new();
+        let mut mirror_updates: Vec<MirrorUpdate> = Vec::new();
+        let mut active_vertices: u64 = 0;
+        let shuffler = DriverShuffle::new(self.combiner.as_deref())
+            .with_partitioner(self.partitioner.as_ref());
 
         for worker in &mut self.workers {
             loop {
                 let resp: WorkerToDriver = recv_msg(worker).await?;
                 match resp {
-                    WorkerToDriver::SuperstepResult { outbox, .. } => {
+                    WorkerToDriver::SuperstepResultBatch {
+                        outbox,
+                        last,
+                        aggregates,
+                        mutations: mut worker_mutations,
+                        mirror_updates: mut worker_mirror_updates,
+                        active,
+                        ..
+                    } => {
+                        active_vertices += active;
                         if !outbox.is_empty() {
                             any_msg = true;
                         }
-                        let shuffler = crate::shuffle::DriverShuffle;
-                        shuffler.route_outbox(outbox, n, &mut next_inboxes);
-                        break;
-                    }
-                    WorkerToDriver::SuperstepResultBatch { outbox, last, .. } => {
-                        if !outbox.is_empty() {
-                            any_msg = true;
-                        }
-                        let shuffler = crate::shuffle::DriverShuffle;
-                        shuffler.route_outbox(outbox, n, &mut next_inboxes);
+                        shuffler.route_outbox(outbox, n, &mut next_inboxes)?;
+                        self.aggregators.merge(&aggregates);
+                        mutations.append(&mut worker_mutations);
+                        mirror_updates.append(&mut worker_mirror_updates);
                         if last {
                             break;
                         }
@@ -227,9 +467,217 @@ impl DistributedDriver {
             }
         }
 
+        if !mutations.is_empty() {
+            active_vertices += self.apply_mutations(mutations).await?;
+        }
+        self.active_vertices = active_vertices;
+        if !self.mirrors.is_empty() {
+            self.copy_to_mirrors(&mut next_inboxes);
+            self.sync_mirrors(mirror_updates).await?;
+        }
+
+        self.aggregators.advance();
         Ok((next_inboxes, any_msg))
     }
 
+    /// Delivers each split vertex's messages to its mirrors as well as its master.
+    fn copy_to_mirrors(&self, inboxes: &mut Inboxes) {
+        let mut copies = Vec::new();
+        for inbox in inboxes.iter() {
+            for (id, msgs) in inbox {
+                for &p in self.mirrors.get(id).into_iter().flatten() {
+                    copies.push((p, id.clone(), msgs.clone()));
+                }
+            }
+        }
+        for (p, id, mut msgs) in copies {
+            inboxes[p].entry(id).or_default().append(&mut msgs);
+        }
+    }
+
+    /// The vertex-cut barrier: hands each master's new state to its mirrors before the next
+    /// superstep.
+    async fn sync_mirrors(&mut self, updates: Vec<MirrorUpdate>) -> GeaFlowResult<()> {
+        let mut parts: Vec<Vec<MirrorUpdate>> =
+            (0..self.worker_count()).map(|_| Vec::new()).collect();
+        for update in updates {
+            for &p in self.mirrors.get(&update.id).into_iter().flatten() {
+                parts[p].push(update.clone());
+            }
+        }
+        for (worker, updates) in self.workers.iter_mut().zip(parts) {
+            if !updates.is_empty() {
+                send_msg(worker, &DriverToWorker::SyncMirrors { updates }).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn require_edge_cut(&self) -> GeaFlowResult<()> {
+        if self.mirrors.is_empty() {
+            Ok(())
+        } else {
+            Err(GeaFlowError::InvalidArgument(
+                "graph mutations are not supported on a vertex-cut graph".to_string(),
+            ))
+        }
+    }
+
+    /// Returns how many vertices the mutations added.
+    async fn apply_mutations(&mut self, mut mutations: Vec<BytesMutation>) -> GeaFlowResult<u64> {
+        self.require_edge_cut()?;
+        let n = self.worker_count().max(1);
+        resolve_mutations(&mut mutations);
+        let mut in_edges = edge_mutations(&mutations);
+        let mut added = 0;
+        let mut parts: Vec<Vec<BytesMutation>> = (0..n).map(|_| Vec::new()).collect();
+        for m in mutations {
+            let p = self.partitioner.partition(m.owner(), n);
+            parts[p].push(m);
+        }
+
+        for (worker, part) in self.workers.iter_mut().zip(parts) {
+            if part.is_empty() {
+                continue;
+            }
+            send_msg(worker, &DriverToWorker::ApplyMutations { mutations: part }).await?;
+            let ack: WorkerToDriver = recv_msg(worker).await?;
+            match ack {
+                WorkerToDriver::MutationsApplied { dropped, activated } => {
+                    in_edges.extend(dropped_edges(dropped));
+                    added += activated;
+                }
+                WorkerToDriver::Error { message } => {
+                    return Err(GeaFlowError::Internal(format!("worker error: {message}")))
+                }
+                other => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "unexpected mutation ack: {other:?}"
+                    )))
+                }
+            }
+        }
+        self.update_in_edges(in_edges, false).await?;
+        Ok(added)
+    }
+
+    /// Mirrors edge mutations into the in-edge index on the workers owning their targets.
+    async fn update_in_edges(
+        &mut self,
+        mut mutations: Vec<BytesMutation>,
+        activate: bool,
+    ) -> GeaFlowResult<()> {
+        let n = self.worker_count().max(1);
+        resolve_mutations(&mut mutations);
+        let mut parts: Vec<Vec<BytesMutation>> = (0..n).map(|_| Vec::new()).collect();
+        for m in mutations {
+            let target = match &m {
+                GraphMutation::AddEdge(e) => &e.target_id,
+                GraphMutation::RemoveEdge { target_id, .. } => target_id,
+                GraphMutation::AddVertex(_) | GraphMutation::RemoveVertex(_) => continue,
+            };
+            parts[self.partitioner.partition(target, n)].push(m);
+        }
+
+        for (worker, part) in self.workers.iter_mut().zip(parts) {
+            if part.is_empty() {
+                continue;
+            }
+            send_msg(
+                worker,
+                &DriverToWorker::UpdateInEdges {
+                    mutations: part,
+                    activate,
+                },
+            )
+            .await?;
+            let ack: WorkerToDriver = recv_msg(worker).await?;
+            match ack {
+                WorkerToDriver::InEdgesUpdated => {}
+                WorkerToDriver::Error { message } => {
+                    return Err(GeaFlowError::Internal(format!("worker error: {message}")))
+                }
+                other => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "unexpected in-edge ack: {other:?}"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies a delta to the stored graph so the next `execute` only evolves the vertices it
+    /// touched. Call after `set_algorithm`.
+    pub async fn apply_delta(&mut self, mut delta: Vec<BytesMutation>) -> GeaFlowResult<()> {
+        self.require_edge_cut()?;
+        let n = self.worker_count().max(1);
+        resolve_mutations(&mut delta);
+        let mut in_edges = edge_mutations(&delta);
+        let mut parts: Vec<Vec<BytesMutation>> = (0..n).map(|_| Vec::new()).collect();
+        for m in delta {
+            let p = self.partitioner.partition(m.owner(), n);
+            parts[p].push(m);
+        }
+
+        for (worker, part) in self.workers.iter_mut().zip(parts) {
+            send_msg(worker, &DriverToWorker::ApplyDelta { mutations: part }).await?;
+        }
+        let mut touched: Vec<Vec<u8>> = Vec::new();
+        for worker in &mut self.workers {
+            let resp: WorkerToDriver = recv_msg(worker).await?;
+            match resp {
+                WorkerToDriver::DeltaApplied {
+                    touched: mut worker_touched,
+                    dropped,
+                } => {
+                    touched.append(&mut worker_touched);
+                    in_edges.extend(dropped_edges(dropped));
+                }
+                WorkerToDriver::Error { message } => {
+                    return Err(GeaFlowError::Internal(format!("worker error: {message}")))
+                }
+                other => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "unexpected delta ack: {other:?}"
+                    )))
+                }
+            }
+        }
+        self.update_in_edges(in_edges, true).await?;
+
+        if !touched.is_empty() {
+            touched.sort();
+            touched.dedup();
+            for worker in &mut self.workers {
+                send_msg(
+                    worker,
+                    &DriverToWorker::Invalidate {
+                        touched: touched.clone(),
+                    },
+                )
+                .await?;
+            }
+            for worker in &mut self.workers {
+                let resp: WorkerToDriver = recv_msg(worker).await?;
+                match resp {
+                    WorkerToDriver::Invalidated => {}
+                    WorkerToDriver::Error { message } => {
+                        return Err(GeaFlowError::Internal(format!("worker error: {message}")))
+                    }
+                    other => {
+                        return Err(GeaFlowError::Internal(format!(
+                            "unexpected invalidate ack: {other:?}"
+                        )))
+                    }
+                }
+            }
+        }
+
+        self.evolving = true;
+        Ok(())
+    }
+
     pub async fn load_graph(
         &mut self,
         vertices: Vec<(Vec<u8>, Vec<u8>)>,
@@ -238,14 +686,19 @@ impl DistributedDriver {
         let n = self.worker_count().max(1);
         let mut v_parts: Vec<Vec<(Vec<u8>, Vec<u8>)>> = (0..n).map(|_| Vec::new()).collect();
         for (id, value) in vertices {
-            let p = partition_of(&id, n);
+            let p = self.partitioner.partition(&id, n);
             v_parts[p].push((id, value));
         }
 
-        let mut e_parts: Vec<Vec<EdgeBytes>> = (0..n).map(|_| Vec::new()).collect();
-        for (src, target, value) in edges {
-            let p = partition_of(&src, n);
-            e_parts[p].push((src, target, value));
+        let values: HashMap<Vec<u8>, Vec<u8>> = if self.edge_partitioning.is_vertex_cut() {
+            v_parts.iter().flatten().cloned().collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut e_parts = EdgeBuffers::new(n, 1, self.edge_partitioning);
+        for edge in edges {
+            self.place_edge(&mut e_parts, edge);
         }
 
         for (i, worker) in self.workers.iter_mut().enumerate() {
@@ -253,7 +706,55 @@ impl DistributedDriver {
                 worker,
                 &DriverToWorker::LoadGraph {
                     vertices: std::mem::take(&mut v_parts[i]),
-                    edges: std::mem::take(&mut e_parts[i]),
+                    edges: std::mem::take(&mut e_parts.out[i]),
+                    in_edges: std::mem::take(&mut e_parts.inbound[i]),
+                },
+            )
+            .await?;
+        }
+        self.load_replicas(&e_parts.placer, |id| values.get(id).cloned())
+            .await
+    }
+
+    /// Sends the vertex-cut replicas `placer` recorded; `value_of` gives a vertex's loaded
+    /// value, `None` when it has none.
+    async fn load_replicas(
+        &mut self,
+        placer: &EdgePlacer<Vec<u8>>,
+        mut value_of: impl FnMut(&[u8]) -> Option<Vec<u8>>,
+    ) -> GeaFlowResult<()> {
+        self.mirrors.clear();
+        if !placer.partitioning().is_vertex_cut() {
+            return Ok(());
+        }
+        let n = self.worker_count().max(1);
+        let mut out_degrees: Vec<Vec<(Vec<u8>, u64)>> = (0..n).map(|_| Vec::new()).collect();
+        let mut mirrors: Vec<Vec<(Vec<u8>, Vec<u8>)>> = (0..n).map(|_| Vec::new()).collect();
+        for (id, replicas) in placer.replicas() {
+            let master = self.partitioner.partition(id, n);
+            let mirrored: Vec<usize> = replicas.iter().copied().filter(|&p| p != master).collect();
+            if mirrored.is_empty() {
+                continue;
+            }
+            let Some(value) = value_of(id) else {
+                continue;
+            };
+            let degree = placer.out_degree(id);
+            out_degrees[master].push((id.clone(), degree));
+            for &p in &mirrored {
+                out_degrees[p].push((id.clone(), degree));
+                mirrors[p].push((id.clone(), value.clone()));
+            }
+            self.mirrors.insert(id.clone(), mirrored);
+        }
+        for ((worker, out_degrees), mirrors) in
+            self.workers.iter_mut().zip(out_degrees).zip(mirrors)
+        {
+            send_msg(
+                worker,
+                &DriverToWorker::LoadReplicas {
+                    out_degrees,
+                    mirrors,
                 },
             )
             .await?;
@@ -265,7 +766,8 @@ impl DistributedDriver {
         &mut self,
         worker_index: usize,
         vertices: Vec<(Vec<u8>, Vec<u8>)>,
-        edges: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>,
+        edges: Vec<EdgeBytes>,
+        in_edges: Vec<EdgeBytes>,
         last: bool,
     ) -> GeaFlowResult<()> {
         let worker = self
@@ -277,6 +779,7 @@ impl DistributedDriver {
             &DriverToWorker::LoadGraphBatch {
                 vertices,
                 edges,
+                in_edges,
                 last,
             },
         )
@@ -293,61 +796,145 @@ impl DistributedDriver {
         }
     }
 
+    /// Buffers `edge` on the workers storing it as an out-edge and an in-edge, returned in
+    /// that order.
+    fn place_edge(&self, bufs: &mut EdgeBuffers, edge: EdgeBytes) -> (usize, usize) {
+        let n = bufs.out.len();
+        let src_p = self.partitioner.partition(&edge.0, n);
+        let target_p = self.partitioner.partition(&edge.1, n);
+        let out_p = bufs.placer.place(&edge.0, &edge.1, src_p, target_p);
+        let in_p = if bufs.placer.partitioning().is_vertex_cut() {
+            out_p
+        } else {
+            target_p
+        };
+        bufs.inbound[in_p].push(edge.clone());
+        bufs.out[out_p].push(edge);
+        (out_p, in_p)
+    }
+
+    async fn buffer_edge(&mut self, bufs: &mut EdgeBuffers, edge: EdgeBytes) -> GeaFlowResult<()> {
+        let (out_p, in_p) = self.place_edge(bufs, edge);
+        for p in [out_p, in_p] {
+            if bufs.out[p].len() >= bufs.batch_size || bufs.inbound[p].len() >= bufs.batch_size {
+                self.flush_edges(bufs, p).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn flush_edges(&mut self, bufs: &mut EdgeBuffers, p: usize) -> GeaFlowResult<()> {
+        if bufs.out[p].is_empty() && bufs.inbound[p].is_empty() {
+            return Ok(());
+        }
+        let edges = std::mem::take(&mut bufs.out[p]);
+        let in_edges = std::mem::take(&mut bufs.inbound[p]);
+        self.load_graph_batch(p, Vec::new(), edges, in_edges, false)
+            .await
+    }
+
     pub async fn load_graph500_streaming<F>(
         &mut self,
         vertices_path: impl AsRef<Path>,
         edges_path: impl AsRef<Path>,
-        mut vertex_value: F,
+        vertex_value: F,
         vertex_batch_size: usize,
         edge_batch_size: usize,
         undirected: bool,
     ) -> GeaFlowResult<()>
     where
         F: FnMut(u64) -> Vec<u8>,
+    {
+        let edge_value = encode(&0u8)?;
+        self.stream_graph500(
+            Graph500Vertices::File(vertices_path.as_ref()),
+            edges_path.as_ref(),
+            vertex_value,
+            |_, _| edge_value.clone(),
+            (vertex_batch_size, edge_batch_size),
+            undirected,
+        )
+        .await
+    }
+
+    /// `load_graph500_streaming` with each edge's value from `edge_value(src, dst)`; both
+    /// directions of an undirected edge get the value computed for the line in the file.
+    pub async fn load_graph500_streaming_weighted<F, G>(
+        &mut self,
+        vertices_path: impl AsRef<Path>,
+        edges_path: impl AsRef<Path>,
+        vertex_value: F,
+        edge_value: G,
+        batch_size: usize,
+        undirected: bool,
+    ) -> GeaFlowResult<()>
+    where
+        F: FnMut(u64) -> Vec<u8>,
+        G: FnMut(u64, u64) -> Vec<u8>,
+    {
+        self.stream_graph500(
+            Graph500Vertices::File(vertices_path.as_ref()),
+            edges_path.as_ref(),
+            vertex_value,
+            edge_value,
+            (batch_size, batch_size),
+            undirected,
+        )
+        .await
+    }
+
+    async fn stream_graph500<F, G>(
+        &mut self,
+        vertices: Graph500Vertices<'_>,
+        edges_path: &Path,
+        mut vertex_value: F,
+        mut edge_value: G,
+        (vertex_batch_size, edge_batch_size): (usize, usize),
+        undirected: bool,
+    ) -> GeaFlowResult<()>
+    where
+        F: FnMut(u64) -> Vec<u8>,
+        G: FnMut(u64, u64) -> Vec<u8>,
     {
         let n = self.worker_count().max(1);
         let mut v_bufs: Vec<Vec<(Vec<u8>, Vec<u8>)>> = (0..n).map(|_| Vec::new()).collect();
-        let mut e_bufs: Vec<Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>> =
-            (0..n).map(|_| Vec::new()).collect();
+        let mut e_bufs = EdgeBuffers::new(n, edge_batch_size, self.edge_partitioning);
+        // Ids read from a file, so a vertex-cut only mirrors vertices that exist.
+        let mut loaded: HashSet<u64> = HashSet::new();
 
-        let vertices_f = std::fs::File::open(vertices_path.as_ref())
-            .map_err(|e| GeaFlowError::Internal(format!("open vertices: {e}")))?;
-        let vertices_r = std::io::BufReader::new(vertices_f);
-        for line in vertices_r.lines() {
-            let line = line.map_err(|e| GeaFlowError::Internal(format!("read vertices: {e}")))?;
-            let s = line.trim();
-            if s.is_empty() || s.starts_with('#') {
-                continue;
-            }
-            let mut it = s.split_whitespace();
-            let id: u64 = it
-                .next()
-                .ok_or_else(|| GeaFlowError::InvalidArgument("vertex id missing".to_string()))?
-                .parse()
-                .map_err(|e| GeaFlowError::InvalidArgument(format!("vertex id parse: {e}")))?;
-            let id_bytes = bincode::serialize(&id)
-                .map_err(|e| GeaFlowError::Internal(format!("encode vertex id: {e}")))?;
+        for id in vertices.ids()? {
+            let id = id?;
+            let id_bytes = encode(&id)?;
             let value_bytes = vertex_value(id);
-            let p = partition_of(&id_bytes, n);
+            let p = self.partitioner.partition(&id_bytes, n);
             v_bufs[p].push((id_bytes, value_bytes));
+            if self.edge_partitioning.is_vertex_cut()
+                && matches!(vertices, Graph500Vertices::File(_))
+            {
+                loaded.insert(id);
+            }
             if v_bufs[p].len() >= vertex_batch_size.max(1) {
-                self.load_graph_batch(p, std::mem::take(&mut v_bufs[p]), Vec::new(), false)
-                    .await?;
+                self.load_graph_batch(
+                    p,
+                    std::mem::take(&mut v_bufs[p]),
+                    Vec::new(),
+                    Vec::new(),
+                    false,
+                )
+                .await?;
             }
         }
 
-        for p in 0..n {
-            if !v_bufs[p].is_empty() {
-                self.load_graph_batch(p, std::mem::take(&mut v_bufs[p]), Vec::new(), false)
+        for (p, buf) in v_bufs.iter_mut().enumerate() {
+            if !buf.is_empty() {
+                self.load_graph_batch(p, std::mem::take(buf), Vec::new(), Vec::new(), false)
                     .await?;
             }
         }
 
-        let edges_f = std::fs::File::open(edges_path.as_ref())
+        let edges_f = std::fs::File::open(edges_path)
             .map_err(|e| GeaFlowError::Internal(format!("open edges: {e}")))?;
         let edges_r = std::io::BufReader::new(edges_f);
-        let edge_value_bytes = bincode::serialize(&0u8)
-            .map_err(|e| GeaFlowError::Internal(format!("encode edge value: {e}")))?;
         for line in edges_r.lines() {
             let line = line.map_err(|e| GeaFlowError::Internal(format!("read edges: {e}")))?;
             let s = line.trim();
@@ -365,51 +952,45 @@ impl DistributedDriver {
                 .ok_or_else(|| GeaFlowError::InvalidArgument("edge dst missing".to_string()))?
                 .parse()
                 .map_err(|e| GeaFlowError::InvalidArgument(format!("edge dst parse: {e}")))?;
-            let src_bytes = bincode::serialize(&src)
-                .map_err(|e| GeaFlowError::Internal(format!("encode edge src: {e}")))?;
-            let dst_bytes = bincode::serialize(&dst)
-                .map_err(|e| GeaFlowError::Internal(format!("encode edge dst: {e}")))?;
-            {
-                let p = partition_of(&src_bytes, n);
-                e_bufs[p].push((
-                    src_bytes.clone(),
-                    dst_bytes.clone(),
-                    edge_value_bytes.clone(),
-                ));
-                if e_bufs[p].len() >= edge_batch_size.max(1) {
-                    self.load_graph_batch(p, Vec::new(), std::mem::take(&mut e_bufs[p]), false)
-                        .await?;
-                }
-            }
+            let src_bytes = encode(&src)?;
+            let dst_bytes = encode(&dst)?;
+            let value_bytes = edge_value(src, dst);
+            self.buffer_edge(
+                &mut e_bufs,
+                (src_bytes.clone(), dst_bytes.clone(), value_bytes.clone()),
+            )
+            .await?;
             if undirected {
-                let p = partition_of(&dst_bytes, n);
-                e_bufs[p].push((dst_bytes, src_bytes, edge_value_bytes.clone()));
-                if e_bufs[p].len() >= edge_batch_size.max(1) {
-                    self.load_graph_batch(p, Vec::new(), std::mem::take(&mut e_bufs[p]), false)
-                        .await?;
-                }
+                self.buffer_edge(&mut e_bufs, (dst_bytes, src_bytes, value_bytes))
+                    .await?;
             }
         }
 
         for p in 0..n {
-            if !e_bufs[p].is_empty() {
-                self.load_graph_batch(p, Vec::new(), std::mem::take(&mut e_bufs[p]), false)
-                    .await?;
-            }
+            self.flush_edges(&mut e_bufs, p).await?;
         }
 
         for p in 0..n {
-            self.load_graph_batch(p, Vec::new(), Vec::new(), true)
+            self.load_graph_batch(p, Vec::new(), Vec::new(), Vec::new(), true)
                 .await?;
         }
-        Ok(())
+        self.load_replicas(&e_bufs.placer, |id| {
+            let id: u64 = bincode::deserialize(id).ok()?;
+            let exists = match vertices {
+                Graph500Vertices::File(_) => loaded.contains(&id),
+                Graph500Vertices::Generated(count) => id < count,
+            };
+            exists.then(|| vertex_value(id))
+        })
+        .await
     }
 
+    /// `load_graph500_streaming` over the vertices `0..vertex_count` instead of a vertex file.
     pub async fn load_graph500_streaming_generated_vertices<F>(
         &mut self,
         vertex_count: u64,
         edges_path: impl AsRef<Path>,
-        mut vertex_value: F,
+        vertex_value: F,
         vertex_batch_size: usize,
         edge_batch_size: usize,
         undirected: bool,
@@ -417,92 +998,16 @@ impl DistributedDriver {
     where
         F: FnMut(u64) -> Vec<u8>,
     {
-        let n = self.worker_count().max(1);
-        let mut v_bufs: Vec<Vec<(Vec<u8>, Vec<u8>)>> = (0..n).map(|_| Vec::new()).collect();
-        let mut e_bufs: Vec<Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>> =
-            (0..n).map(|_| Vec::new()).collect();
-
-        for id in 0..vertex_count {
-            let id_bytes = bincode::serialize(&id)
-                .map_err(|e| GeaFlowError::Internal(format!("encode vertex id: {e}")))?;
-            let value_bytes = vertex_value(id);
-            let p = partition_of(&id_bytes, n);
-            v_bufs[p].push((id_bytes, value_bytes));
-            if v_bufs[p].len() >= vertex_batch_size.max(1) {
-                self.load_graph_batch(p, std::mem::take(&mut v_bufs[p]), Vec::new(), false)
-                    .await?;
-            }
-        }
-
-        for p in 0..n {
-            if !v_bufs[p].is_empty() {
-                self.load_graph_batch(p, std::mem::take(&mut v_bufs[p]), Vec::new(), false)
-                    .await?;
-            }
-        }
-
-        let edges_f = std::fs::File::open(edges_path.as_ref())
-            .map_err(|e| GeaFlowError::Internal(format!("open edges: {e}")))?;
-        let edges_r = std::io::BufReader::new(edges_f);
-        let edge_value_bytes = bincode::serialize(&0u8)
-            .map_err(|e| GeaFlowError::Internal(format!("encode edge value: {e}")))?;
-        for line in edges_r.lines() {
-            let line = line.map_err(|e| GeaFlowError::Internal(format!("read edges: {e}")))?;
-            let s = line.trim();
-            if s.is_empty() || s.starts_with('#') {
-                continue;
-            }
-            let mut it = s.split_whitespace();
-            let src: u64 = it
-                .next()
-                .ok_or_else(|| GeaFlowError::InvalidArgument("edge src missing".to_string()))?
-                .parse()
-                .map_err(|e| GeaFlowError::InvalidArgument(format!("edge src parse: {e}")))?;
-            let dst: u64 = it
-                .next()
-                .ok_or_else(|| GeaFlowError::InvalidArgument("edge dst missing".to_string()))?
-                .parse()
-                .map_err(|e| GeaFlowError::InvalidArgument(format!("edge dst parse: {e}")))?;
-            let src_bytes = bincode::serialize(&src)
-                .map_err(|e| GeaFlowError::Internal(format!("encode edge src: {e}")))?;
-            let dst_bytes = bincode::serialize(&dst)
-                .map_err(|e| GeaFlowError::Internal(format!("encode edge dst: {e}")))?;
-
-            {
-                let p = partition_of(&src_bytes, n);
-                e_bufs[p].push((
-                    src_bytes.clone(),
-                    dst_bytes.clone(),
-                    edge_value_bytes.clone(),
-                ));
-                if e_bufs[p].len() >= edge_batch_size.max(1) {
-                    self.load_graph_batch(p, Vec::new(), std::mem::take(&mut e_bufs[p]), false)
-                        .await?;
-                }
-            }
-            if undirected {
-                let p = partition_of(&dst_bytes, n);
-                e_bufs[p].push((dst_bytes, src_bytes, edge_value_bytes.clone()));
-                if e_bufs[p].len() >= edge_batch_size.max(1) {
-                    self.load_graph_batch(p, Vec::new(), std::mem::take(&mut e_bufs[p]), false)
-                        .await?;
-                }
-            }
-        }
-
-        for p in 0..n {
-            if !e_bufs[p].is_empty() {
-                self.load_graph_batch(p, Vec::new(), std::mem::take(&mut e_bufs[p]), false)
-                    .await?;
-            }
-        }
-
-        for p in 0..n {
-            self.load_graph_batch(p, Vec::new(), Vec::new(), true)
-                .await?;
-        }
-
-        Ok(())
+        let edge_value = encode(&0u8)?;
+        self.stream_graph500(
+            Graph500Vertices::Generated(vertex_count),
+            edges_path.as_ref(),
+            vertex_value,
+            |_, _| edge_value.clone(),
+            (vertex_batch_size, edge_batch_size),
+            undirected,
+        )
+        .await
     }
 
     pub async fn dump_vertices_csv(
@@ -512,6 +1017,8 @@ impl DistributedDriver {
     ) -> GeaFlowResult<Vec<std::path::PathBuf>> {
         let output_dir = output_dir.as_ref();
         std::fs::create_dir_all(output_dir).map_err(GeaFlowError::Io)?;
+        let labels: Vec<(Vec<u8>, String)> =
+            self.fetch_label_external_ids().await?.into_iter().collect();
         let mut out = Vec::with_capacity(self.workers.len());
 
         for (i, worker) in self.workers.iter_mut().enumerate() {
@@ -521,6 +1028,7 @@ impl DistributedDriver {
                 worker,
                 &DriverToWorker::DumpVerticesCsv {
                     output_path: path_s.clone(),
+                    labels: labels.clone(),
                 },
             )
             .await?;
@@ -548,6 +1056,11 @@ impl DistributedDriver {
         iterations: u64,
         params: Vec<u8>,
     ) -> GeaFlowResult<()> {
+        let algorithm = create_algorithm(&name, iterations, &params)?;
+        self.combiner = algorithm.combiner();
+        self.aggregators = Aggregators::new(&algorithm.aggregators());
+        self.master = algorithm.master_compute();
+        self.master_state = MasterState::default();
         for w in &mut self.workers {
             send_msg(
                 w,
@@ -562,19 +1075,109 @@ impl DistributedDriver {
         Ok(())
     }
 
+    /// Starts a keyed window aggregate on every worker; each worker keeps the windows of the
+    /// keys routed to it in its state store.
+    pub async fn set_window_aggregate(
+        &mut self,
+        name: String,
+        params: Vec<u8>,
+        window: WindowSpec,
+        watermarks: WatermarkStrategy,
+        allowed_lateness: u64,
+    ) -> GeaFlowResult<()> {
+        crate::distributed::stream::create_window_aggregate(&name, &params)?;
+        self.watermarks = watermarks;
+        self.max_timestamp = None;
+        for w in &mut self.workers {
+            send_msg(
+                w,
+                &DriverToWorker::SetWindowAggregate {
+                    name: name.clone(),
+                    params: params.clone(),
+                    window,
+                    allowed_lateness,
+                },
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Routes `records` to workers by key, then advances every worker to the watermark the
+    /// records imply.
+    pub async fn push_records(
+        &mut self,
+        records: Vec<StreamRecord>,
+    ) -> GeaFlowResult<WindowResults> {
+        let n = self.worker_count().max(1);
+        let mut batches: Vec<Vec<StreamRecord>> = (0..n).map(|_| Vec::new()).collect();
+        for record in records {
+            self.max_timestamp = Some(
+                self.max_timestamp
+                    .map_or(record.timestamp, |ts| ts.max(record.timestamp)),
+            );
+            batches[self.partitioner.partition(&record.key, n)].push(record);
+        }
+        let watermark = self
+            .max_timestamp
+            .map_or(0, |ts| self.watermarks.watermark(ts));
+        self.advance_stream(batches, watermark).await
+    }
+
+    /// Fires every window still open, as at the end of a bounded stream.
+    pub async fn finish_stream(&mut self) -> GeaFlowResult<WindowResults> {
+        let batches = (0..self.worker_count()).map(|_| Vec::new()).collect();
+        self.advance_stream(batches, u64::MAX).await
+    }
+
+    async fn advance_stream(
+        &mut self,
+        batches: Vec<Vec<StreamRecord>>,
+        watermark: u64,
+    ) -> GeaFlowResult<WindowResults> {
+        for (w, records) in self.workers.iter_mut().zip(batches) {
+            send_msg(w, &DriverToWorker::StreamRecords { records, watermark }).await?;
+        }
+        let mut out = WindowResults::default();
+        for w in &mut self.workers {
+            let resp: WorkerToDriver = recv_msg(w).await?;
+            match resp {
+                WorkerToDriver::WindowsFired { mut results } => {
+                    out.fired.append(&mut results.fired);
+                    out.late.append(&mut results.late);
+                }
+                WorkerToDriver::Error { message } => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "stream records failed: {message}"
+                    )))
+                }
+                other => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "unexpected stream response: {other:?}"
+                    )))
+                }
+            }
+        }
+        out.fired.sort_by(|a, b| {
+            (a.window.end, a.window.start, &a.key).cmp(&(b.window.end, b.window.start, &b.key))
+        });
+        Ok(out)
+    }
+
     pub async fn execute(&mut self, iterations: u64) -> GeaFlowResult<u64> {
         let n = self.worker_count().max(1);
         let mut inboxes: Inboxes = (0..n).map(|_| HashMap::new()).collect();
 
         let mut iteration: u64 = 1;
+        let mut messages_pending = true;
         while iteration <= iterations {
+            if self.before_superstep(iteration, messages_pending) == MasterStep::Halt {
+                break;
+            }
             let (next_inboxes, any_msg) = self.superstep_round(iteration, &mut inboxes).await?;
             iteration += 1;
             inboxes = next_inboxes;
-
-            if !any_msg {
-                break;
-            }
+            messages_pending = any_msg;
         }
 
         Ok(iteration - 1)
@@ -612,6 +1215,56 @@ impl DistributedDriver {
         Ok(())
     }
 
+    /// Sends a checkpoint barrier after the last pushed records. It travels in band, on the
+    /// same connection as the records, and each worker has no other input, so it is aligned
+    /// without buffering; workers only receive records in rounds they all acknowledge, so no
+    /// worker runs ahead of it either. Each snapshots its window state to
+    /// `base_dir/worker_<i>`, the layout `load_checkpoint_all` reads.
+    pub async fn stream_barrier(
+        &mut self,
+        checkpoint_id: u64,
+        base_dir: impl AsRef<Path>,
+    ) -> GeaFlowResult<()> {
+        let base_dir = base_dir.as_ref();
+        for (i, w) in self.workers.iter_mut().enumerate() {
+            let checkpoint_dir = base_dir.join(format!("worker_{i}"));
+            send_msg(
+                w,
+                &DriverToWorker::StreamBarrier {
+                    checkpoint_id,
+                    checkpoint_dir: checkpoint_dir.to_string_lossy().to_string(),
+                },
+            )
+            .await?;
+        }
+        for w in &mut self.workers {
+            let resp: WorkerToDriver = recv_msg(w).await?;
+            match resp {
+                WorkerToDriver::BarrierAcked { checkpoint_id: id } if id == checkpoint_id => {}
+                WorkerToDriver::Error { message } => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "stream barrier failed: {message}"
+                    )))
+                }
+                other => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "unexpected barrier response: {other:?}"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The latest event time pushed so far, which the watermark derives from.
+    pub fn stream_max_timestamp(&self) -> Option<u64> {
+        self.max_timestamp
+    }
+
+    pub fn restore_stream_max_timestamp(&mut self, max_timestamp: Option<u64>) {
+        self.max_timestamp = max_timestamp;
+    }
+
     pub async fn load_checkpoint_all(&mut self, base_dir: impl AsRef<Path>) -> GeaFlowResult<()> {
         let base_dir = base_dir.as_ref();
         for (i, w) in self.workers.iter_mut().enumerate() {
@@ -644,6 +1297,92 @@ impl DistributedDriver {
         Ok(())
     }
 
+    /// Stores each vertex's external id on the worker that holds it, so dumps write external ids
+    /// and `fetch_external_ids` can translate results back, also after a restore.
+    pub async fn load_id_dictionary(&mut self, dict: &IdDictionary) -> GeaFlowResult<()> {
+        let n = self.workers.len();
+        let mut per_worker: Vec<Vec<(Vec<u8>, String)>> = vec![Vec::new(); n];
+        for (id, external) in dict.iter() {
+            let id_bytes = bincode::serialize(&id)
+                .map_err(|e| GeaFlowError::Internal(format!("encode vertex id: {e}")))?;
+            let w = self.partitioner.partition(&id_bytes, n);
+            per_worker[w].push((id_bytes, external.to_string()));
+        }
+        for (w, ids) in self.workers.iter_mut().zip(per_worker) {
+            send_msg(w, &DriverToWorker::LoadExternalIds { ids }).await?;
+        }
+        Ok(())
+    }
+
+    /// Internal id bytes to external id, for every vertex loaded through an `IdDictionary`.
+    pub async fn fetch_external_ids(&mut self) -> GeaFlowResult<HashMap<Vec<u8>, String>> {
+        self.collect_external_ids(DriverToWorker::FetchExternalIds)
+            .await
+    }
+
+    /// Like `fetch_external_ids`, for the vertices whose id labels others, such as WCC
+    /// component roots; empty when the algorithm's values are not labels.
+    pub async fn fetch_label_external_ids(&mut self) -> GeaFlowResult<HashMap<Vec<u8>, String>> {
+        self.collect_external_ids(DriverToWorker::FetchLabelExternalIds)
+            .await
+    }
+
+    async fn collect_external_ids(
+        &mut self,
+        request: DriverToWorker,
+    ) -> GeaFlowResult<HashMap<Vec<u8>, String>> {
+        for w in &mut self.workers {
+            send_msg(w, &request).await?;
+        }
+
+        let mut out = HashMap::new();
+        for w in &mut self.workers {
+            let resp: WorkerToDriver = recv_msg(w).await?;
+            match resp {
+                WorkerToDriver::ExternalIds { ids } => out.extend(ids),
+                WorkerToDriver::Error { message } => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "fetch external ids failed: {message}"
+                    )))
+                }
+                other => {
+                    return Err(GeaFlowError::Internal(format!(
+                        "unexpected fetch response: {other:?}"
+                    )))
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Like `fetch_vertices`, keyed by external id instead of internal id bytes. Values that
+    /// are labels, such as WCC components, come back as the label's external id, a bincode
+    /// `String`.
+    pub async fn fetch_vertices_by_external_id(&mut self) -> GeaFlowResult<Vec<(String, Vec<u8>)>> {
+        let ids = self.fetch_external_ids().await?;
+        let labels = self.fetch_label_external_ids().await?;
+        self.fetch_vertices()
+            .await?
+            .into_iter()
+            .map(|(id, value)| {
+                let Some(external) = ids.get(&id) else {
+                    return Err(GeaFlowError::Internal(format!(
+                        "vertex {id:?} has no external id"
+                    )));
+                };
+                if labels.is_empty() {
+                    return Ok((external.clone(), value));
+                }
+                let label = labels.get(&value).ok_or_else(|| {
+                    GeaFlowError::Internal(format!("label {value:?} has no external id"))
+                })?;
+                let label = bincode::serialize(label)
+                    .map_err(|e| GeaFlowError::Internal(format!("encode label: {e}")))?;
+                Ok((external.clone(), label))
+            })
+            .collect()
+    }
+
     pub async fn fetch_vertices(&mut self) -> GeaFlowResult<Vec<(Vec<u8>, Vec<u8>)>> {
         for w in &mut self.workers {
             send_msg(w, &DriverToWorker::FetchVertices).await?;
@@ -677,10 +1416,22 @@ impl DistributedDriver {
     }
 }
 
-fn partition_of(id: &[u8], partitions: usize) -> usize {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::Hasher;
-    let mut h = DefaultHasher::new();
-    h.write(id);
-    (h.finish() as usize) % partitions
+/// The edge mutations of an already resolved batch, to mirror into the in-edge index.
+fn edge_mutations(mutations: &[BytesMutation]) -> Vec<BytesMutation> {
+    mutations
+        .iter()
+        .filter(|m| {
+            matches!(
+                m,
+                GraphMutation::AddEdge(_) | GraphMutation::RemoveEdge { .. }
+            )
+        })
+        .cloned()
+        .collect()
+}
+
+fn dropped_edges(dropped: Vec<(Vec<u8>, Vec<u8>)>) -> impl Iterator<Item = BytesMutation> {
+    dropped
+        .into_iter()
+        .map(|(src_id, target_id)| GraphMutation::RemoveEdge { src_id, target_id })
 }
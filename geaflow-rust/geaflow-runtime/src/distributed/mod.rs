@@ -3,4 +3,5 @@ pub mod driver;
 pub mod driver_service;
 pub mod master;
 pub mod protocol;
+pub mod stream;
 pub mod worker;
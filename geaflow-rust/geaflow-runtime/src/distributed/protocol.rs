@@ -1,4 +1,8 @@
+use crate::distributed::stream::{StreamRecord, WindowResults, WindowSpec};
 use bytes::Bytes;
+use geaflow_api::aggregator::AggregateValues;
+use geaflow_api::master::MasterState;
+use geaflow_api::mutation::GraphMutation;
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
 use serde::{Deserialize, Serialize};
 use tokio::net::TcpStream;
@@ -56,17 +60,42 @@ pub enum MasterResponse {
     Error { message: String },
 }
 
+/// A split vertex's state after its master computed it, for its mirrors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorUpdate {
+    pub id: Vec<u8>,
+    pub value: Vec<u8>,
+    pub halted: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum DriverToWorker {
+    /// `edges` leave this worker's vertices; `in_edges` point at them.
     LoadGraph {
         vertices: Vec<(Vec<u8>, Vec<u8>)>,
         edges: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>,
+        in_edges: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>,
     },
     LoadGraphBatch {
         vertices: Vec<(Vec<u8>, Vec<u8>)>,
         edges: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>,
+        in_edges: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>,
         last: bool,
     },
+    /// Vertex-cut replication, after the graph: the global out-degree of each split vertex this
+    /// worker holds, and a mirror of each one it holds edges of but does not master.
+    LoadReplicas {
+        out_degrees: Vec<(Vec<u8>, u64)>,
+        mirrors: Vec<(Vec<u8>, Vec<u8>)>,
+    },
+    /// External ids of this worker's vertices, for graphs loaded through an `IdDictionary`.
+    LoadExternalIds {
+        ids: Vec<(Vec<u8>, String)>,
+    },
+    /// Masters' states for this worker's mirrors, applied at the barrier.
+    SyncMirrors {
+        updates: Vec<MirrorUpdate>,
+    },
     SetAlgorithm {
         name: String,
         iterations: u64,
@@ -75,11 +104,48 @@ pub enum DriverToWorker {
     Superstep {
         iteration: u64,
         inbox: Vec<(Vec<u8>, Vec<Vec<u8>>)>,
+        aggregates: AggregateValues,
+        master: MasterState,
+        evolve: bool,
     },
     SuperstepBatch {
         iteration: u64,
         inbox: Vec<(Vec<u8>, Vec<Vec<u8>>)>,
         last: bool,
+        aggregates: AggregateValues,
+        master: MasterState,
+        evolve: bool,
+    },
+    ApplyMutations {
+        mutations: Vec<GraphMutation<Vec<u8>, Vec<u8>, Vec<u8>>>,
+    },
+    ApplyDelta {
+        mutations: Vec<GraphMutation<Vec<u8>, Vec<u8>, Vec<u8>>>,
+    },
+    Invalidate {
+        touched: Vec<Vec<u8>>,
+    },
+    /// Edge mutations mirrored onto the worker owning their targets; `activate` wakes those
+    /// targets for an incremental run.
+    UpdateInEdges {
+        mutations: Vec<GraphMutation<Vec<u8>, Vec<u8>, Vec<u8>>>,
+        activate: bool,
+    },
+    SetWindowAggregate {
+        name: String,
+        params: Vec<u8>,
+        window: WindowSpec,
+        allowed_lateness: u64,
+    },
+    /// Records keyed to this worker, then the watermark to advance to.
+    StreamRecords {
+        records: Vec<StreamRecord>,
+        watermark: u64,
+    },
+    /// Snapshots window state once every record sent before it has been processed.
+    StreamBarrier {
+        checkpoint_id: u64,
+        checkpoint_dir: String,
     },
     CreateCheckpoint {
         checkpoint_dir: String,
@@ -88,8 +154,14 @@ pub enum DriverToWorker {
         checkpoint_dir: String,
     },
     FetchVertices,
+    FetchExternalIds,
+    /// External ids of this worker's vertices that label others, when the algorithm's values
+    /// are labels.
+    FetchLabelExternalIds,
+    /// `labels` translates id-valued results to external ids.
     DumpVerticesCsv {
         output_path: String,
+        labels: Vec<(Vec<u8>, String)>,
     },
     Shutdown,
 }
@@ -100,20 +172,43 @@ pub enum WorkerToDriver {
     GraphLoaded {
         last: bool,
     },
-    SuperstepResult {
-        iteration: u64,
-        outbox: Vec<(Vec<u8>, Vec<u8>)>,
-    },
     SuperstepResultBatch {
         iteration: u64,
         outbox: Vec<(Vec<u8>, Vec<u8>)>,
         last: bool,
+        aggregates: AggregateValues,
+        mutations: Vec<GraphMutation<Vec<u8>, Vec<u8>, Vec<u8>>>,
+        /// States of the split vertices this worker masters and computed.
+        mirror_updates: Vec<MirrorUpdate>,
+        /// Vertices this worker masters that did not vote to halt; set on the last batch.
+        active: u64,
+    },
+    /// `dropped` are the (src, target) pairs of out-edges removed with their vertices;
+    /// `activated` counts the vertices added.
+    MutationsApplied {
+        dropped: Vec<(Vec<u8>, Vec<u8>)>,
+        activated: u64,
+    },
+    DeltaApplied {
+        touched: Vec<Vec<u8>>,
+        dropped: Vec<(Vec<u8>, Vec<u8>)>,
+    },
+    Invalidated,
+    InEdgesUpdated,
+    WindowsFired {
+        results: WindowResults,
+    },
+    BarrierAcked {
+        checkpoint_id: u64,
     },
     CheckpointCreated,
     CheckpointLoaded,
     Vertices {
         vertices: Vec<(Vec<u8>, Vec<u8>)>,
     },
+    ExternalIds {
+        ids: Vec<(Vec<u8>, String)>,
+    },
     VerticesDumped {
         output_path: String,
     },
@@ -0,0 +1,377 @@
+use crate::state::{WindowEntry, WindowState};
+use crate::stream::LocalKeyedStream;
+use geaflow_api::window::{EventTimeWindow, SlidingWindow, TimeTumblingWindow, TimeWindow};
+use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamRecord {
+    pub key: Vec<u8>,
+    pub timestamp: u64,
+    pub value: Vec<u8>,
+}
+
+impl StreamRecord {
+    pub fn new(key: Vec<u8>, timestamp: u64, value: Vec<u8>) -> Self {
+        Self {
+            key,
+            timestamp,
+            value,
+        }
+    }
+}
+
+/// Encodes a keyed stream for `DistributedDriver::push_records`.
+pub fn to_records<K, T>(
+    stream: LocalKeyedStream<K, T>,
+    timestamp: impl Fn(&T) -> u64,
+) -> GeaFlowResult<Vec<StreamRecord>>
+where
+    K: Serialize,
+    T: Serialize,
+{
+    stream
+        .collect()
+        .into_iter()
+        .map(|(key, value)| {
+            Ok(StreamRecord::new(
+                encode(&key)?,
+                timestamp(&value),
+                encode(&value)?,
+            ))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiredWindow {
+    pub key: Vec<u8>,
+    pub window: TimeWindow,
+    pub result: Vec<u8>,
+}
+
+/// What a watermark advance produced across the workers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowResults {
+    pub fired: Vec<FiredWindow>,
+    pub late: Vec<StreamRecord>,
+}
+
+/// Window assigners that keep state per key without merging, so they can live in RocksDB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowSpec {
+    Tumbling { size: u64 },
+    Sliding { size: u64, slide: u64 },
+}
+
+impl WindowSpec {
+    pub fn assign_windows(&self, timestamp: u64) -> Vec<TimeWindow> {
+        match *self {
+            WindowSpec::Tumbling { size } => TimeTumblingWindow { size }.assign_windows(timestamp),
+            WindowSpec::Sliding { size, slide } => {
+                SlidingWindow { size, slide }.assign_windows(timestamp)
+            }
+        }
+    }
+}
+
+/// A keyed window aggregate over encoded values, the streaming counterpart of
+/// `DistributedAlgorithm`.
+pub trait WindowAggregate: Send {
+    fn name(&self) -> &str;
+    fn create_accumulator(&self) -> GeaFlowResult<Vec<u8>>;
+    fn add(&self, acc: &[u8], value: &[u8]) -> GeaFlowResult<Vec<u8>>;
+
+    fn get_result(&self, acc: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        Ok(acc.to_vec())
+    }
+}
+
+pub fn create_window_aggregate(
+    name: &str,
+    _params: &[u8],
+) -> GeaFlowResult<Box<dyn WindowAggregate>> {
+    match name {
+        "count" => Ok(Box::new(CountAggregate)),
+        "sum_u64" => Ok(Box::new(SumU64Aggregate)),
+        "sum_f64" => Ok(Box::new(SumF64Aggregate)),
+        other => Err(GeaFlowError::InvalidArgument(format!(
+            "unknown window aggregate: {other}"
+        ))),
+    }
+}
+
+fn encode<T: Serialize>(v: &T) -> GeaFlowResult<Vec<u8>> {
+    bincode::serialize(v).map_err(|e| GeaFlowError::Internal(format!("bincode encode: {e}")))
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> GeaFlowResult<T> {
+    bincode::deserialize(bytes).map_err(|e| GeaFlowError::Internal(format!("bincode decode: {e}")))
+}
+
+/// Counts records; values are ignored.
+pub struct CountAggregate;
+
+impl WindowAggregate for CountAggregate {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn create_accumulator(&self) -> GeaFlowResult<Vec<u8>> {
+        encode(&0u64)
+    }
+
+    fn add(&self, acc: &[u8], _value: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        encode(&(decode::<u64>(acc)? + 1))
+    }
+}
+
+pub struct SumU64Aggregate;
+
+impl WindowAggregate for SumU64Aggregate {
+    fn name(&self) -> &str {
+        "sum_u64"
+    }
+
+    fn create_accumulator(&self) -> GeaFlowResult<Vec<u8>> {
+        encode(&0u64)
+    }
+
+    fn add(&self, acc: &[u8], value: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        encode(&(decode::<u64>(acc)? + decode::<u64>(value)?))
+    }
+}
+
+pub struct SumF64Aggregate;
+
+impl WindowAggregate for SumF64Aggregate {
+    fn name(&self) -> &str {
+        "sum_f64"
+    }
+
+    fn create_accumulator(&self) -> GeaFlowResult<Vec<u8>> {
+        encode(&0f64)
+    }
+
+    fn add(&self, acc: &[u8], value: &[u8]) -> GeaFlowResult<Vec<u8>> {
+        encode(&(decode::<f64>(acc)? + decode::<f64>(value)?))
+    }
+}
+
+/// A worker's share of a keyed window aggregate; accumulators live in `WindowState`.
+pub struct WindowOperator {
+    pub aggregate: Box<dyn WindowAggregate>,
+    pub window: WindowSpec,
+    pub allowed_lateness: u64,
+    pub watermark: Option<u64>,
+}
+
+impl WindowOperator {
+    pub fn new(
+        aggregate: Box<dyn WindowAggregate>,
+        window: WindowSpec,
+        allowed_lateness: u64,
+    ) -> Self {
+        Self {
+            aggregate,
+            window,
+            allowed_lateness,
+            watermark: None,
+        }
+    }
+
+    fn expired(&self, window: &TimeWindow, watermark: Option<u64>) -> bool {
+        watermark.is_some_and(|wm| window.end.saturating_add(self.allowed_lateness) <= wm)
+    }
+
+    /// Folds `records` into their windows, then advances to `watermark`: windows it closes
+    /// fire, and windows past the allowed lateness are dropped.
+    pub fn process(
+        &mut self,
+        state: &dyn WindowState,
+        records: Vec<StreamRecord>,
+        watermark: u64,
+    ) -> GeaFlowResult<WindowResults> {
+        let mut results = WindowResults::default();
+        for record in records {
+            let windows: Vec<TimeWindow> = self
+                .window
+                .assign_windows(record.timestamp)
+                .into_iter()
+                .filter(|w| !self.expired(w, self.watermark))
+                .collect();
+            if windows.is_empty() {
+                results.late.push(record);
+                continue;
+            }
+            for window in windows {
+                let mut entry = match state.get_window(&record.key, &window)? {
+                    Some(entry) => entry,
+                    None => WindowEntry {
+                        key: record.key.clone(),
+                        window,
+                        fired: false,
+                        acc: self.aggregate.create_accumulator()?,
+                    },
+                };
+                entry.acc = self.aggregate.add(&entry.acc, &record.value)?;
+                if entry.fired {
+                    results.fired.push(self.fire(&entry)?);
+                }
+                state.put_window(&entry)?;
+            }
+        }
+
+        let watermark = self.watermark.map_or(watermark, |wm| wm.max(watermark));
+        self.watermark = Some(watermark);
+        for mut entry in state.windows_ending_by(watermark)? {
+            let newly_closed = !entry.fired;
+            if newly_closed {
+                results.fired.push(self.fire(&entry)?);
+                entry.fired = true;
+            }
+            if self.expired(&entry.window, Some(watermark)) {
+                state.remove_window(&entry.key, &entry.window)?;
+            } else if newly_closed {
+                state.put_window(&entry)?;
+            }
+        }
+        Ok(results)
+    }
+
+    /// Saves the operator's own state next to its windows, ahead of a checkpoint barrier.
+    pub fn snapshot(&self, state: &dyn WindowState) -> GeaFlowResult<()> {
+        match self.watermark {
+            Some(watermark) => state.put_window_watermark(watermark),
+            None => Ok(()),
+        }
+    }
+
+    pub fn restore(&mut self, state: &dyn WindowState) -> GeaFlowResult<()> {
+        self.watermark = state.get_window_watermark()?;
+        Ok(())
+    }
+
+    fn fire(&self, entry: &WindowEntry) -> GeaFlowResult<FiredWindow> {
+        Ok(FiredWindow {
+            key: entry.key.clone(),
+            window: entry.window,
+            result: self.aggregate.get_result(&entry.acc)?,
+        })
+    }
+}
+
+/// Where fired windows go. A sink that upserts by key and window is exactly-once as it is; a
+/// transactional one stages output per checkpoint and publishes it in `commit`.
+pub trait WindowSink {
+    fn write(&mut self, results: &WindowResults) -> GeaFlowResult<()>;
+
+    /// Seals what was written since the previous checkpoint under `checkpoint_id`.
+    fn pre_commit(&mut self, _checkpoint_id: u64) -> GeaFlowResult<()> {
+        Ok(())
+    }
+
+    /// `checkpoint_id` is durable, so its sealed output may be published.
+    fn commit(&mut self, _checkpoint_id: u64) -> GeaFlowResult<()> {
+        Ok(())
+    }
+
+    /// After a restart from `checkpoint_id` (`None`: from scratch), publishes what was sealed up
+    /// to it and discards the rest.
+    fn recover(&mut self, _checkpoint_id: Option<u64>) -> GeaFlowResult<()> {
+        Ok(())
+    }
+}
+
+/// A transactional sink writing one file per checkpoint: `part-<id>.staged` on pre-commit,
+/// renamed to `part-<id>` on commit.
+pub struct FileWindowSink {
+    dir: PathBuf,
+    pending: Vec<FiredWindow>,
+}
+
+impl FileWindowSink {
+    pub fn new(dir: impl Into<PathBuf>) -> GeaFlowResult<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(GeaFlowError::Io)?;
+        Ok(Self {
+            dir,
+            pending: Vec::new(),
+        })
+    }
+
+    fn part_path(&self, checkpoint_id: u64, staged: bool) -> PathBuf {
+        let suffix = if staged { ".staged" } else { "" };
+        self.dir.join(format!("part-{checkpoint_id:010}{suffix}"))
+    }
+
+    /// Committed windows in checkpoint order.
+    pub fn read_committed(dir: impl AsRef<Path>) -> GeaFlowResult<Vec<FiredWindow>> {
+        let mut out = Vec::new();
+        for (_, path, staged) in parts(dir.as_ref())? {
+            if !staged {
+                let bytes = std::fs::read(path).map_err(GeaFlowError::Io)?;
+                out.extend(decode::<Vec<FiredWindow>>(&bytes)?);
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// `(checkpoint id, path, staged)` of every part file in `dir`, by id.
+fn parts(dir: &Path) -> GeaFlowResult<Vec<(u64, PathBuf, bool)>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(GeaFlowError::Io)? {
+        let path = entry.map_err(GeaFlowError::Io)?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(rest) = name.strip_prefix("part-") else {
+            continue;
+        };
+        let (id, staged) = match rest.strip_suffix(".staged") {
+            Some(id) => (id, true),
+            None => (rest, false),
+        };
+        if let Ok(id) = id.parse::<u64>() {
+            out.push((id, path, staged));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+impl WindowSink for FileWindowSink {
+    fn write(&mut self, results: &WindowResults) -> GeaFlowResult<()> {
+        self.pending.extend(results.fired.iter().cloned());
+        Ok(())
+    }
+
+    fn pre_commit(&mut self, checkpoint_id: u64) -> GeaFlowResult<()> {
+        let bytes = encode(&std::mem::take(&mut self.pending))?;
+        std::fs::write(self.part_path(checkpoint_id, true), bytes).map_err(GeaFlowError::Io)
+    }
+
+    fn commit(&mut self, checkpoint_id: u64) -> GeaFlowResult<()> {
+        std::fs::rename(
+            self.part_path(checkpoint_id, true),
+            self.part_path(checkpoint_id, false),
+        )
+        .map_err(GeaFlowError::Io)
+    }
+
+    fn recover(&mut self, checkpoint_id: Option<u64>) -> GeaFlowResult<()> {
+        self.pending.clear();
+        for (id, path, staged) in parts(&self.dir)? {
+            if checkpoint_id.is_some_and(|cp| id <= cp) {
+                if staged {
+                    std::fs::rename(&path, self.part_path(id, false)).map_err(GeaFlowError::Io)?;
+                }
+            } else {
+                std::fs::remove_file(&path).map_err(GeaFlowError::Io)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,21 @@
-use crate::distributed::algorithm::{DistributedAlgorithm, PageRankAlgorithm, WccAlgorithm};
+use crate::distributed::algorithm::{
+    create_algorithm, BytesMutation, ComputeContext, DistributedAlgorithm,
+};
 use crate::distributed::protocol::{
-    framed, recv_msg, send_msg, DriverToWorker, MasterRequest, WorkerToDriver, WorkerToMaster,
+    framed, recv_msg, send_msg, DriverToWorker, MasterRequest, MirrorUpdate, WorkerToDriver,
+    WorkerToMaster,
 };
+use crate::distributed::stream::{create_window_aggregate, WindowOperator};
+use crate::shuffle::combine_outbox;
 use crate::state::rocksdb_graph_state::RocksDbGraphState;
-use crate::state::GraphState;
+use crate::state::{ExternalIdState, GraphState, ReplicaState};
+use geaflow_api::aggregator::{AggregateValues, Aggregators};
+use geaflow_api::graph::EdgeDirection;
+use geaflow_api::master::MasterState;
+use geaflow_api::mutation::GraphMutation;
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
 use geaflow_common::types::{Edge, Vertex};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use tokio::net::TcpListener;
@@ -40,13 +49,22 @@ pub async fn run_worker(config: WorkerConfig) -> GeaFlowResult<()> {
     let mut state = RocksDbGraphState::open(&config.state_dir)?;
 
     let mut algorithm: Option<Box<dyn DistributedAlgorithm>> = None;
+    let mut aggregators = Aggregators::default();
+    let mut master_state = MasterState::default();
     let mut pending_iteration: Option<u64> = None;
     let mut pending_inbox: HashMap<Vec<u8>, Vec<Vec<u8>>> = HashMap::new();
+    let mut window_operator: Option<WindowOperator> = None;
+    // Whether the graph was loaded with a vertex-cut.
+    let mut replicated = false;
 
     loop {
         let msg: DriverToWorker = recv_msg(&mut framed).await?;
         match msg {
-            DriverToWorker::LoadGraph { vertices, edges } => {
+            DriverToWorker::LoadGraph {
+                vertices,
+                edges,
+                in_edges,
+            } => {
                 let v: Vec<Vertex<Vec<u8>, Vec<u8>>> = vertices
                     .into_iter()
                     .map(|(id, value)| Vertex { id, value })
@@ -54,22 +72,13 @@ pub async fn run_worker(config: WorkerConfig) -> GeaFlowResult<()> {
                 <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_vertex_batch(
                     &state, &v,
                 )?;
-
-                let e: Vec<Edge<Vec<u8>, Vec<u8>>> = edges
-                    .into_iter()
-                    .map(|(src, target, value)| Edge {
-                        src_id: src,
-                        target_id: target,
-                        value,
-                    })
-                    .collect();
-                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_edge_batch(
-                    &state, &e,
-                )?;
+                activate_vertices(&state, &v)?;
+                put_edges(&state, edges, in_edges)?;
             }
             DriverToWorker::LoadGraphBatch {
                 vertices,
                 edges,
+                in_edges,
                 last,
             } => {
                 if !vertices.is_empty() {
@@ -80,55 +89,80 @@ pub async fn run_worker(config: WorkerConfig) -> GeaFlowResult<()> {
                     <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_vertex_batch(
                         &state, &v,
                     )?;
+                    activate_vertices(&state, &v)?;
                 }
 
-                if !edges.is_empty() {
-                    let e: Vec<Edge<Vec<u8>, Vec<u8>>> = edges
-                        .into_iter()
-                        .map(|(src, target, value)| Edge {
-                            src_id: src,
-                            target_id: target,
-                            value,
-                        })
-                        .collect();
-                    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_edge_batch(
-                        &state, &e,
-                    )?;
-                }
+                put_edges(&state, edges, in_edges)?;
 
                 send_msg(&mut framed, &WorkerToDriver::GraphLoaded { last }).await?;
             }
+            DriverToWorker::LoadReplicas {
+                out_degrees,
+                mirrors,
+            } => {
+                state.put_out_degree_batch(&out_degrees)?;
+                state.put_mirror_batch(&mirrors)?;
+                let ids: Vec<Vec<u8>> = mirrors.into_iter().map(|(id, _)| id).collect();
+                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::update_active_batch(
+                    &state,
+                    &ids,
+                    &[],
+                )?;
+                replicated = true;
+            }
+            DriverToWorker::LoadExternalIds { ids } => state.put_external_id_batch(&ids)?,
+            DriverToWorker::SyncMirrors { updates } => sync_mirrors(&state, updates)?,
             DriverToWorker::SetAlgorithm {
                 name,
                 iterations,
                 params,
             } => {
-                let algo: Box<dyn DistributedAlgorithm> = match name.as_str() {
-                    "wcc" => Box::new(WccAlgorithm::new(iterations)),
-                    "pagerank" => Box::new(PageRankAlgorithm::from_params(iterations, &params)?),
-                    other => {
-                        return Err(GeaFlowError::InvalidArgument(format!(
-                            "unknown algorithm: {other}"
-                        )))
-                    }
-                };
+                let algo = create_algorithm(&name, iterations, &params)?;
+                aggregators = Aggregators::new(&algo.aggregators());
+                master_state = MasterState::default();
                 algorithm = Some(algo);
+                // A new run starts with every vertex, also those an earlier run halted.
+                activate_all(&state)?;
             }
-            DriverToWorker::Superstep { iteration, inbox } => {
+            DriverToWorker::Superstep {
+                iteration,
+                inbox,
+                aggregates,
+                master,
+                evolve,
+            } => {
                 pending_inbox.clear();
                 for (k, msgs) in inbox {
                     pending_inbox.insert(k, msgs);
                 }
-                let mut inbox_map = std::mem::take(&mut pending_inbox);
+                let inbox_map = std::mem::take(&mut pending_inbox);
                 let algo = algorithm.as_mut().ok_or_else(|| {
                     GeaFlowError::InvalidArgument("algorithm not set".to_string())
                 })?;
-                process_superstep(iteration, &mut inbox_map, &state, algo, &mut framed).await?;
+                aggregators.set_previous(aggregates);
+                apply_master_state(&state, &mut master_state, master)?;
+                process_superstep(
+                    SuperstepInput {
+                        iteration,
+                        inbox_map,
+                        evolve,
+                        replicated,
+                    },
+                    &state,
+                    algo,
+                    &mut aggregators,
+                    &master_state,
+                    &mut framed,
+                )
+                .await?;
             }
             DriverToWorker::SuperstepBatch {
                 iteration,
                 inbox,
                 last,
+                aggregates,
+                master,
+                evolve,
             } => {
                 if pending_iteration != Some(iteration) {
                     pending_iteration = Some(iteration);
@@ -138,14 +172,159 @@ pub async fn run_worker(config: WorkerConfig) -> GeaFlowResult<()> {
                     pending_inbox.entry(k).or_default().append(&mut msgs);
                 }
                 if last {
-                    let mut inbox_map = std::mem::take(&mut pending_inbox);
+                    let inbox_map = std::mem::take(&mut pending_inbox);
                     let algo = algorithm.as_mut().ok_or_else(|| {
                         GeaFlowError::InvalidArgument("algorithm not set".to_string())
                     })?;
-                    process_superstep(iteration, &mut inbox_map, &state, algo, &mut framed).await?;
+                    aggregators.set_previous(aggregates);
+                    apply_master_state(&state, &mut master_state, master)?;
+                    process_superstep(
+                        SuperstepInput {
+                            iteration,
+                            inbox_map,
+                            evolve,
+                            replicated,
+                        },
+                        &state,
+                        algo,
+                        &mut aggregators,
+                        &master_state,
+                        &mut framed,
+                    )
+                    .await?;
                     pending_iteration = None;
                 }
             }
+            DriverToWorker::ApplyMutations { mutations } => {
+                match apply_mutations(&state, mutations) {
+                    Ok((dropped, activated)) => {
+                        send_msg(
+                            &mut framed,
+                            &WorkerToDriver::MutationsApplied { dropped, activated },
+                        )
+                        .await?
+                    }
+                    Err(e) => {
+                        send_msg(
+                            &mut framed,
+                            &WorkerToDriver::Error {
+                                message: format!("{e}"),
+                            },
+                        )
+                        .await?
+                    }
+                }
+            }
+            DriverToWorker::ApplyDelta { mutations } => match apply_delta(&state, mutations) {
+                Ok((touched, dropped)) => {
+                    send_msg(
+                        &mut framed,
+                        &WorkerToDriver::DeltaApplied { touched, dropped },
+                    )
+                    .await?
+                }
+                Err(e) => {
+                    send_msg(
+                        &mut framed,
+                        &WorkerToDriver::Error {
+                            message: format!("{e}"),
+                        },
+                    )
+                    .await?
+                }
+            },
+            DriverToWorker::Invalidate { touched } => {
+                let result = match algorithm.as_ref() {
+                    Some(algo) => invalidate(&state, algo.as_ref(), &touched),
+                    None => Err(GeaFlowError::InvalidArgument(
+                        "algorithm not set".to_string(),
+                    )),
+                };
+                match result {
+                    Ok(()) => send_msg(&mut framed, &WorkerToDriver::Invalidated).await?,
+                    Err(e) => {
+                        send_msg(
+                            &mut framed,
+                            &WorkerToDriver::Error {
+                                message: format!("{e}"),
+                            },
+                        )
+                        .await?
+                    }
+                }
+            }
+            DriverToWorker::UpdateInEdges {
+                mutations,
+                activate,
+            } => match update_in_edges(&state, mutations, activate) {
+                Ok(()) => send_msg(&mut framed, &WorkerToDriver::InEdgesUpdated).await?,
+                Err(e) => {
+                    send_msg(
+                        &mut framed,
+                        &WorkerToDriver::Error {
+                            message: format!("{e}"),
+                        },
+                    )
+                    .await?
+                }
+            },
+            DriverToWorker::SetWindowAggregate {
+                name,
+                params,
+                window,
+                allowed_lateness,
+            } => {
+                let aggregate = create_window_aggregate(&name, &params)?;
+                let mut op = WindowOperator::new(aggregate, window, allowed_lateness);
+                op.restore(&state)?;
+                window_operator = Some(op);
+            }
+            DriverToWorker::StreamRecords { records, watermark } => {
+                let result = match window_operator.as_mut() {
+                    Some(op) => op.process(&state, records, watermark),
+                    None => Err(GeaFlowError::InvalidArgument(
+                        "window aggregate not set".to_string(),
+                    )),
+                };
+                match result {
+                    Ok(results) => {
+                        send_msg(&mut framed, &WorkerToDriver::WindowsFired { results }).await?
+                    }
+                    Err(e) => {
+                        send_msg(
+                            &mut framed,
+                            &WorkerToDriver::Error {
+                                message: format!("{e}"),
+                            },
+                        )
+                        .await?
+                    }
+                }
+            }
+            DriverToWorker::StreamBarrier {
+                checkpoint_id,
+                checkpoint_dir,
+            } => {
+                let snapshot = match window_operator.as_ref() {
+                    Some(op) => op.snapshot(&state),
+                    None => Ok(()),
+                };
+                match snapshot.and_then(|_| state.create_checkpoint(Path::new(&checkpoint_dir))) {
+                    Ok(_) => {
+                        send_msg(&mut framed, &WorkerToDriver::BarrierAcked { checkpoint_id })
+                            .await?
+                    }
+                    Err(e) => {
+                        send_msg(
+                            &mut framed,
+                            &WorkerToDriver::Error {
+                                message: format!("{e}"),
+                            },
+                        )
+                        .await?
+                    }
+                }
+            }
             DriverToWorker::CreateCheckpoint { checkpoint_dir } => {
                 let checkpoint_dir = Path::new(&checkpoint_dir);
                 match state.create_checkpoint(checkpoint_dir) {
@@ -164,7 +343,13 @@ pub async fn run_worker(config: WorkerConfig) -> GeaFlowResult<()> {
                 }
             }
             DriverToWorker::LoadCheckpoint { checkpoint_dir } => {
-                match RocksDbGraphState::open(Path::new(&checkpoint_dir)) {
+                let loaded = RocksDbGraphState::open(Path::new(&checkpoint_dir)).and_then(|s| {
+                    if let Some(op) = window_operator.as_mut() {
+                        op.restore(&s)?;
+                    }
+                    Ok(s)
+                });
+                match loaded {
                     Ok(s) => {
                         state = s;
                         send_msg(&mut framed, &WorkerToDriver::CheckpointLoaded).await?;
@@ -189,11 +374,27 @@ pub async fn run_worker(config: WorkerConfig) -> GeaFlowResult<()> {
                     vertices.into_iter().map(|v| (v.id, v.value)).collect();
                 send_msg(&mut framed, &WorkerToDriver::Vertices { vertices }).await?;
             }
-            DriverToWorker::DumpVerticesCsv { output_path } => {
+            DriverToWorker::FetchExternalIds => {
+                let ids = state.list_external_ids()?;
+                send_msg(&mut framed, &WorkerToDriver::ExternalIds { ids }).await?;
+            }
+            DriverToWorker::FetchLabelExternalIds => {
+                let ids = if algorithm.as_ref().is_some_and(|a| a.values_are_labels()) {
+                    state.list_label_external_ids()?
+                } else {
+                    Vec::new()
+                };
+                send_msg(&mut framed, &WorkerToDriver::ExternalIds { ids }).await?;
+            }
+            DriverToWorker::DumpVerticesCsv {
+                output_path,
+                labels,
+            } => {
                 let algo_name = algorithm.as_ref().map(|a| a.name()).unwrap_or("unknown");
+                let labels: HashMap<Vec<u8>, String> = labels.into_iter().collect();
                 let dump_result = match algo_name {
-                    "wcc" => state.dump_vertices_csv_u64_u64(Path::new(&output_path)),
-                    "pagerank" => state.dump_vertices_csv_u64_f64(Path::new(&output_path)),
+                    "wcc" => state.dump_vertices_csv_u64_u64(Path::new(&output_path), &labels),
+                    "pagerank" | "sssp" => state.dump_vertices_csv_u64_f64(Path::new(&output_path)),
                     other => Err(GeaFlowError::InvalidArgument(format!(
                         "unsupported algorithm for dump: {other}"
                     ))),
@@ -220,42 +421,390 @@ pub async fn run_worker(config: WorkerConfig) -> GeaFlowResult<()> {
     Ok(())
 }
 
-async fn process_superstep(
+fn activate_vertices(
+    state: &RocksDbGraphState,
+    vertices: &[Vertex<Vec<u8>, Vec<u8>>],
+) -> GeaFlowResult<()> {
+    let ids: Vec<Vec<u8>> = vertices.iter().map(|v| v.id.clone()).collect();
+    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::update_active_batch(
+        state,
+        &ids,
+        &[],
+    )
+}
+
+type EdgeBytes = (Vec<u8>, Vec<u8>, Vec<u8>);
+/// (src, target) pairs of out-edges removed together with their vertices.
+type DroppedEdges = Vec<(Vec<u8>, Vec<u8>)>;
+
+fn to_edges(edges: Vec<EdgeBytes>) -> Vec<Edge<Vec<u8>, Vec<u8>>> {
+    edges
+        .into_iter()
+        .map(|(src, target, value)| Edge {
+            src_id: src,
+            target_id: target,
+            value,
+        })
+        .collect()
+}
+
+fn put_edges(
+    state: &RocksDbGraphState,
+    edges: Vec<EdgeBytes>,
+    in_edges: Vec<EdgeBytes>,
+) -> GeaFlowResult<()> {
+    if !edges.is_empty() {
+        <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_edge_batch(
+            state,
+            &to_edges(edges),
+        )?;
+    }
+    if !in_edges.is_empty() {
+        <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_in_edge_batch(
+            state,
+            &to_edges(in_edges),
+        )?;
+    }
+    Ok(())
+}
+
+/// Applies mutations to the out-edges this worker owns; returns the dropped edges so their
+/// in-edge entries can be removed too, and how many vertices were added.
+fn apply_mutations(
+    state: &RocksDbGraphState,
+    mutations: Vec<BytesMutation>,
+) -> GeaFlowResult<(DroppedEdges, u64)> {
+    let mut dropped = Vec::new();
+    let mut activated = 0;
+    for m in mutations {
+        match m {
+            GraphMutation::RemoveEdge { src_id, target_id } => {
+                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::remove_edges(
+                    state, &src_id, &target_id,
+                )?;
+            }
+            GraphMutation::RemoveVertex(id) => {
+                let out =
+                    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_out_edges(
+                        state, &id,
+                    )?;
+                dropped.extend(out.into_iter().map(|e| (e.src_id, e.target_id)));
+                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::remove_vertex(
+                    state, &id,
+                )?;
+            }
+            GraphMutation::AddVertex(v) => {
+                let existing =
+                    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_vertex(
+                        state, &v.id,
+                    )?;
+                if existing.is_none() {
+                    let v = [v];
+                    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_vertex_batch(
+                        state, &v,
+                    )?;
+                    activate_vertices(state, &v)?;
+                    activated += 1;
+                }
+            }
+            GraphMutation::AddEdge(e) => {
+                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_edge_batch(
+                    state,
+                    &[e],
+                )?;
+            }
+        }
+    }
+    Ok((dropped, activated))
+}
+
+/// Mirrors edge mutations into the in-edge index of the targets this worker owns.
+fn update_in_edges(
+    state: &RocksDbGraphState,
+    mutations: Vec<BytesMutation>,
+    activate: bool,
+) -> GeaFlowResult<()> {
+    let mut targets = Vec::new();
+    for m in mutations {
+        match m {
+            GraphMutation::AddEdge(e) => {
+                targets.push(e.target_id.clone());
+                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_in_edge_batch(
+                    state,
+                    &[e],
+                )?;
+            }
+            GraphMutation::RemoveEdge { src_id, target_id } => {
+                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::remove_in_edges(
+                    state, &src_id, &target_id,
+                )?;
+                targets.push(target_id);
+            }
+            GraphMutation::AddVertex(_) | GraphMutation::RemoveVertex(_) => {}
+        }
+    }
+    if !activate {
+        return Ok(());
+    }
+    let mut activated = Vec::with_capacity(targets.len());
+    for id in targets {
+        if <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_vertex(state, &id)?
+            .is_some()
+        {
+            activated.push(id);
+        }
+    }
+    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::update_active_batch(
+        state,
+        &activated,
+        &[],
+    )
+}
+
+/// Applies a delta and activates the vertices owning it; returns the values the owners of
+/// removals held beforehand and the out-edges dropped with removed vertices.
+fn apply_delta(
+    state: &RocksDbGraphState,
+    mutations: Vec<BytesMutation>,
+) -> GeaFlowResult<(Vec<Vec<u8>>, DroppedEdges)> {
+    let mut touched = Vec::new();
+    let mut owners = Vec::with_capacity(mutations.len());
+    for m in &mutations {
+        if matches!(
+            m,
+            GraphMutation::RemoveVertex(_) | GraphMutation::RemoveEdge { .. }
+        ) {
+            if let Some(value) =
+                <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_vertex(
+                    state,
+                    m.owner(),
+                )?
+            {
+                touched.push(value);
+            }
+        }
+        owners.push(m.owner().clone());
+    }
+    let (dropped, _) = apply_mutations(state, mutations)?;
+
+    let mut activated = Vec::with_capacity(owners.len());
+    for id in owners {
+        if <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_vertex(state, &id)?
+            .is_some()
+        {
+            activated.push(id);
+        }
+    }
+    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::update_active_batch(
+        state,
+        &activated,
+        &[],
+    )?;
+    Ok((touched, dropped))
+}
+
+fn invalidate(
+    state: &RocksDbGraphState,
+    algo: &dyn DistributedAlgorithm,
+    touched: &[Vec<u8>],
+) -> GeaFlowResult<()> {
+    let vertices =
+        <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::list_vertices(state)?;
+    let mut resets = Vec::new();
+    let mut stale = Vec::new();
+    for v in vertices {
+        if touched.iter().any(|t| algo.invalidates(t, &v.value)) {
+            if let Some(value) = algo.reset_value(&v.id) {
+                resets.push(Vertex::new(v.id.clone(), value));
+            }
+            stale.push(v);
+        }
+    }
+    if !resets.is_empty() {
+        <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::put_vertex_batch(
+            state, &resets,
+        )?;
+    }
+    activate_vertices(state, &stale)
+}
+
+fn sync_mirrors(state: &RocksDbGraphState, updates: Vec<MirrorUpdate>) -> GeaFlowResult<()> {
+    let mut activated = Vec::new();
+    let mut halted = Vec::new();
+    let mut values = Vec::with_capacity(updates.len());
+    for update in updates {
+        if update.halted {
+            halted.push(update.id.clone());
+        } else {
+            activated.push(update.id.clone());
+        }
+        values.push((update.id, update.value));
+    }
+    state.put_mirror_batch(&values)?;
+    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::update_active_batch(
+        state, &activated, &halted,
+    )
+}
+
+fn apply_master_state(
+    state: &RocksDbGraphState,
+    current: &mut MasterState,
+    next: MasterState,
+) -> GeaFlowResult<()> {
+    if next.phase != current.phase {
+        activate_all(state)?;
+    }
+    *current = next;
+    Ok(())
+}
+
+fn activate_all(state: &RocksDbGraphState) -> GeaFlowResult<()> {
+    let vertices =
+        <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::list_vertices(state)?;
+    activate_vertices(state, &vertices)?;
+    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::update_active_batch(
+        state,
+        &state.list_mirror_ids()?,
+        &[],
+    )
+}
+
+/// (neighbour, edge value) pairs of `id` in `direction`.
+fn neighbor_edges(
+    state: &RocksDbGraphState,
+    id: &Vec<u8>,
+    direction: EdgeDirection,
+) -> GeaFlowResult<Vec<(Vec<u8>, Vec<u8>)>> {
+    let mut edges = Vec::new();
+    if direction != EdgeDirection::In {
+        let out =
+            <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_out_edges(state, id)?;
+        edges.extend(out.into_iter().map(|e| (e.target_id, e.value)));
+    }
+    if direction != EdgeDirection::Out {
+        let incoming =
+            <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_in_edges(state, id)?;
+        edges.extend(incoming.into_iter().map(|e| (e.src_id, e.value)));
+    }
+    Ok(edges)
+}
+
+struct SuperstepInput {
     iteration: u64,
-    inbox_map: &mut HashMap<Vec<u8>, Vec<Vec<u8>>>,
+    inbox_map: HashMap<Vec<u8>, Vec<Vec<u8>>>,
+    /// First superstep after a graph delta.
+    evolve: bool,
+    /// Loaded with a vertex-cut, so some vertices here are mirrors.
+    replicated: bool,
+}
+
+async fn process_superstep(
+    input: SuperstepInput,
     state: &RocksDbGraphState,
     algo: &mut Box<dyn DistributedAlgorithm>,
+    aggregators: &mut Aggregators,
+    master: &MasterState,
     framed: &mut crate::distributed::protocol::DriverFramed,
 ) -> GeaFlowResult<()> {
     let start = std::time::Instant::now();
+    let SuperstepInput {
+        iteration,
+        mut inbox_map,
+        evolve,
+        replicated,
+    } = input;
 
-    let vertices =
-        <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::list_vertices(state)?;
+    let active =
+        <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::list_active_vertex_ids(
+            state,
+        )?;
+    let mut frontier: Vec<(Vec<u8>, bool)> = Vec::with_capacity(active.len());
+    {
+        let active_set: HashSet<&Vec<u8>> = active.iter().collect();
+        for id in inbox_map.keys() {
+            if !active_set.contains(id) {
+                frontier.push((id.clone(), false));
+            }
+        }
+    }
+    // Woken vertices compute in key order, like the active ones state lists, so their
+    // messages are combined in the same order on every run.
+    frontier.sort_unstable();
+    frontier.extend(active.into_iter().map(|id| (id, true)));
 
     let mut updates: Vec<Vertex<Vec<u8>, Vec<u8>>> = Vec::new();
     let mut outbox: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    let mut activated: Vec<Vec<u8>> = Vec::new();
+    let mut halted: Vec<Vec<u8>> = Vec::new();
+    let mut mutations: Vec<BytesMutation> = Vec::new();
+    let mut mirror_updates: Vec<MirrorUpdate> = Vec::new();
+    let mut active: u64 = 0;
+    // Mirrors only send along this worker's edges; what they aggregate, mutate or compute as
+    // their value is dropped in favour of the master's.
+    let mut mirror_aggregators = aggregators.clone();
+    let computed = frontier.len();
+    let direction = algo.superstep_edge_direction(iteration, master);
 
-    for v in vertices {
-        let msgs = inbox_map.remove(&v.id).unwrap_or_default();
-        let out_edges =
-            <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_out_edges(
-                state, &v.id,
-            )?;
-        let out_edges: Vec<(Vec<u8>, Vec<u8>)> = out_edges
-            .into_iter()
-            .map(|e| (e.target_id, e.value))
-            .collect();
-
-        let (new_value, mut outgoing) =
-            algo.compute_vertex(&v.id, Some(&v.value), &out_edges, &msgs, iteration)?;
-
-        if let Some(nv) = new_value {
-            updates.push(Vertex {
-                id: v.id.clone(),
-                value: nv,
-            });
-        }
+    for (id, was_active) in frontier {
+        let (value, mirror) =
+            match <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_vertex(
+                state, &id,
+            )? {
+                Some(value) => (value, false),
+                None if replicated => match state.get_mirror(&id)? {
+                    Some(value) => (value, true),
+                    None => continue,
+                },
+                None => continue,
+            };
+        let msgs = inbox_map.remove(&id).unwrap_or_default();
+        let edges = neighbor_edges(state, &id, direction)?;
+        let out_degree = if replicated {
+            state.get_out_degree(&id)?
+        } else {
+            None
+        };
+
+        let vertex_aggregators = if mirror {
+            &mut mirror_aggregators
+        } else {
+            &mut *aggregators
+        };
+        let mut ctx = ComputeContext::new(iteration, vertex_aggregators, master);
+        ctx.out_degree = out_degree;
+        let (new_value, mut outgoing) = if evolve {
+            algo.evolve_vertex(&id, Some(&value), &edges, &mut ctx)?
+        } else {
+            algo.compute_vertex(&id, Some(&value), &edges, &msgs, &mut ctx)?
+        };
+        let voted = ctx.halted;
         outbox.append(&mut outgoing);
+
+        if !mirror {
+            mutations.append(&mut ctx.mutations);
+            if out_degree.is_some() {
+                mirror_updates.push(MirrorUpdate {
+                    id: id.clone(),
+                    value: new_value.clone().unwrap_or(value),
+                    halted: voted,
+                });
+            }
+            if let Some(nv) = new_value {
+                updates.push(Vertex {
+                    id: id.clone(),
+                    value: nv,
+                });
+            }
+        }
+
+        if !voted && !mirror {
+            active += 1;
+        }
+        if voted && was_active {
+            halted.push(id);
+        } else if !voted && !was_active {
+            activated.push(id);
+        }
     }
 
     if !updates.is_empty() {
@@ -263,13 +812,22 @@ async fn process_superstep(
             state, &updates,
         )?;
     }
+    <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::update_active_batch(
+        state, &activated, &halted,
+    )?;
+
+    if let Some(combiner) = algo.combiner() {
+        outbox = combine_outbox(outbox, &*combiner)?;
+    }
 
+    metrics::counter!("geaflow_worker_superstep_computed_total").increment(computed as u64);
     metrics::counter!("geaflow_worker_superstep_updates_total").increment(updates.len() as u64);
     metrics::counter!("geaflow_worker_superstep_outbox_total").increment(outbox.len() as u64);
     metrics::histogram!("geaflow_worker_superstep_duration_ms")
         .record(start.elapsed().as_secs_f64() * 1000.0);
 
     let batch_entries: usize = 256;
+    let mut aggregates = Some(aggregators.take_current());
     if outbox.is_empty() {
         send_msg(
             framed,
@@ -277,6 +835,10 @@ async fn process_superstep(
                 iteration,
                 outbox: Vec::new(),
                 last: true,
+                aggregates: aggregates.take().unwrap_or_default(),
+                mutations,
+                mirror_updates,
+                active,
             },
         )
         .await?;
@@ -293,6 +855,22 @@ async fn process_superstep(
                 iteration,
                 outbox: outbox[offset..end].to_vec(),
                 last,
+                aggregates: if last {
+                    aggregates.take().unwrap_or_default()
+                } else {
+                    AggregateValues::new()
+                },
+                mutations: if last {
+                    std::mem::take(&mut mutations)
+                } else {
+                    Vec::new()
+                },
+                mirror_updates: if last {
+                    std::mem::take(&mut mirror_updates)
+                } else {
+                    Vec::new()
+                },
+                active: if last { active } else { 0 },
             },
         )
         .await?;
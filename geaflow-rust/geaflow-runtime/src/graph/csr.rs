@@ -0,0 +1,410 @@
+use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use geaflow_common::types::Edge;
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::OnceLock;
+
+/// Why the engines, whose graph API has no error path, panic when a `Csr` cannot index a
+/// partition.
+pub(crate) const PARTITION_TOO_LARGE: &str = "an edge partition names more than u32::MAX vertices";
+
+/// Edges grouped by one endpoint in compressed sparse row form. Every endpoint gets a dense
+/// local index `i` in order of first appearance; the edges of row `i` are
+/// `offsets[i]..offsets[i + 1]` of `targets`, which holds far endpoints as indices into `ids`,
+/// and the parallel `values`. Rows keep their edges in insertion order.
+///
+/// Mutations go to an overlay of removed and added edges, which is packed back into the rows
+/// once it grows past a quarter of them.
+#[derive(Clone)]
+pub struct Csr<K, EV> {
+    /// Rows are keyed by `target_id` rather than `src_id`.
+    inbound: bool,
+    index: HashMap<K, u32>,
+    ids: Vec<K>,
+    /// Rows added since the last pack have no entry here.
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+    values: Vec<EV>,
+    /// Packed edges removed since the last pack; empty until the first removal.
+    removed: Vec<bool>,
+    removed_count: usize,
+    /// Edges added since the last pack, by row.
+    added: HashMap<u32, Vec<(u32, EV)>>,
+    added_count: usize,
+}
+
+impl<K, EV> Default for Csr<K, EV> {
+    fn default() -> Self {
+        Self {
+            inbound: false,
+            index: HashMap::new(),
+            ids: Vec::new(),
+            offsets: vec![0],
+            targets: Vec::new(),
+            values: Vec::new(),
+            removed: Vec::new(),
+            removed_count: 0,
+            added: HashMap::new(),
+            added_count: 0,
+        }
+    }
+}
+
+impl<K, EV> Csr<K, EV>
+where
+    K: Clone + Eq + Hash,
+    EV: Clone,
+{
+    /// Rows keyed by `src_id`.
+    pub fn out_edges(edges: Vec<Edge<K, EV>>) -> GeaFlowResult<Self> {
+        Self::build(edges, false)
+    }
+
+    /// Rows keyed by `target_id`.
+    pub fn in_edges(edges: Vec<Edge<K, EV>>) -> GeaFlowResult<Self> {
+        Self::build(edges, true)
+    }
+
+    fn build(edges: Vec<Edge<K, EV>>, inbound: bool) -> GeaFlowResult<Self> {
+        let mut csr = Self {
+            inbound,
+            ..Self::default()
+        };
+        let mut rows: Vec<(u32, u32, EV)> = Vec::with_capacity(edges.len());
+        for e in edges {
+            let src = csr.intern(&e.src_id)?;
+            let target = csr.intern(&e.target_id)?;
+            rows.push(if inbound {
+                (target, src, e.value)
+            } else {
+                (src, target, e.value)
+            });
+        }
+        // Stable, so each row keeps its edges in input order.
+        rows.sort_by_key(|(row, _, _)| *row);
+
+        csr.offsets = vec![0; csr.ids.len() + 1];
+        csr.targets.reserve_exact(rows.len());
+        csr.values.reserve_exact(rows.len());
+        for (row, target, value) in rows {
+            csr.offsets[row as usize + 1] += 1;
+            csr.targets.push(target);
+            csr.values.push(value);
+        }
+        for i in 1..csr.offsets.len() {
+            csr.offsets[i] += csr.offsets[i - 1];
+        }
+        Ok(csr)
+    }
+
+    /// Edges stored.
+    pub fn len(&self) -> usize {
+        self.targets.len() - self.removed_count + self.added_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn degree(&self, id: &K) -> usize {
+        self.row(id).map_or(0, |row| self.row_degree(row))
+    }
+
+    /// The far endpoint of every edge, row by row.
+    pub fn neighbours(&self) -> impl Iterator<Item = &K> + '_ {
+        (0..self.ids.len())
+            .flat_map(move |row| self.row_entries(row))
+            .map(move |(target, _)| &self.ids[target as usize])
+    }
+
+    /// Vertices with edges here and their degree.
+    pub fn rows(&self) -> impl Iterator<Item = (&K, usize)> + '_ {
+        self.ids
+            .iter()
+            .enumerate()
+            .map(move |(row, id)| (id, self.row_degree(row)))
+            .filter(|(_, degree)| *degree > 0)
+    }
+
+    pub fn edges_of(&self, id: &K) -> impl Iterator<Item = Edge<K, EV>> + '_ {
+        self.row(id)
+            .into_iter()
+            .flat_map(move |row| self.row_edges(row))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Edge<K, EV>> + '_ {
+        (0..self.ids.len()).flat_map(move |row| self.row_edges(row))
+    }
+
+    /// Appends `e` to the end of its row.
+    pub fn insert(&mut self, e: Edge<K, EV>) -> GeaFlowResult<()> {
+        let src = self.intern(&e.src_id)?;
+        let target = self.intern(&e.target_id)?;
+        let (row, far) = if self.inbound {
+            (target, src)
+        } else {
+            (src, target)
+        };
+        self.added.entry(row).or_default().push((far, e.value));
+        self.added_count += 1;
+        self.pack_if_large();
+        Ok(())
+    }
+
+    /// Removes every edge from `src_id` to `target_id`; returns whether there was one.
+    pub fn remove(&mut self, src_id: &K, target_id: &K) -> bool {
+        let (Some(src), Some(target)) = (self.index.get(src_id), self.index.get(target_id)) else {
+            return false;
+        };
+        let (row, far) = if self.inbound {
+            (*target, *src)
+        } else {
+            (*src, *target)
+        };
+        self.remove_where(row, |t| t == far)
+    }
+
+    /// Removes every edge in `id`'s row; returns whether there was one.
+    pub fn remove_row(&mut self, id: &K) -> bool {
+        match self.index.get(id) {
+            Some(&row) => self.remove_where(row, |_| true),
+            None => false,
+        }
+    }
+
+    fn remove_where(&mut self, row: u32, matches: impl Fn(u32) -> bool) -> bool {
+        let before = self.len();
+        for i in self.packed_range(row as usize) {
+            if matches(self.targets[i]) && !self.removed.get(i).copied().unwrap_or(false) {
+                self.removed.resize(self.targets.len(), false);
+                self.removed[i] = true;
+                self.removed_count += 1;
+            }
+        }
+        if let Some(added) = self.added.get_mut(&row) {
+            let count = added.len();
+            added.retain(|(t, _)| !matches(*t));
+            self.added_count -= count - added.len();
+        }
+        let changed = self.len() != before;
+        if changed {
+            self.pack_if_large();
+        }
+        changed
+    }
+
+    fn pack_if_large(&mut self) {
+        if 4 * (self.removed_count + self.added_count) > self.targets.len() {
+            self.pack();
+        }
+    }
+
+    /// Folds the overlay into the rows and drops endpoints no edge refers to any more.
+    fn pack(&mut self) {
+        let mut live = vec![false; self.ids.len()];
+        let mut offsets = Vec::with_capacity(self.ids.len() + 1);
+        let mut targets = Vec::with_capacity(self.len());
+        let mut values = Vec::with_capacity(self.len());
+        offsets.push(0);
+        for row in 0..self.ids.len() {
+            for (target, value) in self.row_entries(row) {
+                live[target as usize] = true;
+                targets.push(target);
+                values.push(value.clone());
+            }
+            live[row] |= targets.len() > offsets[row];
+            offsets.push(targets.len());
+        }
+
+        let mut remap = vec![u32::MAX; self.ids.len()];
+        let mut kept = 0u32;
+        let mut packed_offsets = vec![0];
+        for (row, &is_live) in live.iter().enumerate() {
+            if is_live {
+                remap[row] = kept;
+                kept += 1;
+                packed_offsets.push(offsets[row + 1]);
+            }
+        }
+        self.ids = std::mem::take(&mut self.ids)
+            .into_iter()
+            .zip(&live)
+            .filter_map(|(id, &is_live)| is_live.then_some(id))
+            .collect();
+        self.index.retain(|_, i| {
+            *i = remap[*i as usize];
+            *i != u32::MAX
+        });
+        for target in &mut targets {
+            *target = remap[*target as usize];
+        }
+        self.offsets = packed_offsets;
+        self.targets = targets;
+        self.values = values;
+        self.removed = Vec::new();
+        self.removed_count = 0;
+        self.added.clear();
+        self.added_count = 0;
+    }
+
+    fn intern(&mut self, id: &K) -> GeaFlowResult<u32> {
+        if let Some(&i) = self.index.get(id) {
+            return Ok(i);
+        }
+        let i = u32::try_from(self.ids.len()).map_err(|_| {
+            GeaFlowError::InvalidArgument(format!(
+                "more than {} vertices in one edge partition",
+                u32::MAX
+            ))
+        })?;
+        self.index.insert(id.clone(), i);
+        self.ids.push(id.clone());
+        Ok(i)
+    }
+
+    fn row(&self, id: &K) -> Option<usize> {
+        self.index.get(id).map(|&row| row as usize)
+    }
+
+    fn packed_range(&self, row: usize) -> std::ops::Range<usize> {
+        match self.offsets.get(row + 1) {
+            Some(&end) => self.offsets[row]..end,
+            None => 0..0,
+        }
+    }
+
+    fn row_degree(&self, row: usize) -> usize {
+        let range = self.packed_range(row);
+        let packed = if self.removed.is_empty() {
+            range.len()
+        } else {
+            range.filter(|&i| !self.removed[i]).count()
+        };
+        packed + self.added.get(&(row as u32)).map_or(0, Vec::len)
+    }
+
+    fn row_entries(&self, row: usize) -> impl Iterator<Item = (u32, &EV)> + '_ {
+        let range = self.packed_range(row);
+        range
+            .filter(move |&i| !self.removed.get(i).copied().unwrap_or(false))
+            .map(move |i| (self.targets[i], &self.values[i]))
+            .chain(
+                self.added
+                    .get(&(row as u32))
+                    .into_iter()
+                    .flatten()
+                    .map(|(target, value)| (*target, value)),
+            )
+    }
+
+    fn row_edges(&self, row: usize) -> impl Iterator<Item = Edge<K, EV>> + '_ {
+        let id = &self.ids[row];
+        self.row_entries(row).map(move |(target, value)| {
+            let far = self.ids[target as usize].clone();
+            if self.inbound {
+                Edge::new(far, id.clone(), value.clone())
+            } else {
+                Edge::new(id.clone(), far, value.clone())
+            }
+        })
+    }
+}
+
+/// An in-edge `Csr`, built the first time a compute function reads in-edges and dropped
+/// whenever the edges it indexes change.
+#[derive(Clone)]
+pub struct InEdgeIndex<K, EV>(OnceLock<Csr<K, EV>>);
+
+impl<K, EV> Default for InEdgeIndex<K, EV> {
+    fn default() -> Self {
+        Self(OnceLock::new())
+    }
+}
+
+impl<K, EV> InEdgeIndex<K, EV> {
+    pub fn get_or_build(&self, build: impl FnOnce() -> Csr<K, EV>) -> &Csr<K, EV> {
+        self.0.get_or_init(build)
+    }
+
+    pub fn is_built(&self) -> bool {
+        self.0.get().is_some()
+    }
+
+    pub fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
+
+enum Rows<'a, K, EV> {
+    Empty,
+    Built(&'a Csr<K, EV>, Option<usize>),
+    Deferred(&'a InEdgeIndex<K, EV>, &'a dyn Fn() -> Csr<K, EV>, &'a K),
+}
+
+/// One vertex's row of a `Csr`, turned into `Edge`s the first time a compute function asks.
+pub struct LazyEdges<'a, K, EV> {
+    rows: Rows<'a, K, EV>,
+    edges: OnceCell<Vec<Edge<K, EV>>>,
+}
+
+impl<'a, K, EV> LazyEdges<'a, K, EV>
+where
+    K: Clone + Eq + Hash,
+    EV: Clone,
+{
+    pub fn new(csr: &'a Csr<K, EV>, id: &K) -> Self {
+        Self {
+            rows: Rows::Built(csr, csr.row(id)),
+            edges: OnceCell::new(),
+        }
+    }
+
+    /// `id`'s row of `index`, which `build` fills in if no vertex has read it yet.
+    pub fn in_edges(
+        index: &'a InEdgeIndex<K, EV>,
+        build: &'a dyn Fn() -> Csr<K, EV>,
+        id: &'a K,
+    ) -> Self {
+        Self {
+            rows: Rows::Deferred(index, build, id),
+            edges: OnceCell::new(),
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            rows: Rows::Empty,
+            edges: OnceCell::new(),
+        }
+    }
+
+    fn row(&self) -> Option<(&'a Csr<K, EV>, usize)> {
+        match self.rows {
+            Rows::Empty => None,
+            Rows::Built(csr, row) => row.map(|row| (csr, row)),
+            Rows::Deferred(index, build, id) => {
+                let csr = index.get_or_build(build);
+                csr.row(id).map(|row| (csr, row))
+            }
+        }
+    }
+
+    pub fn get(&self) -> &[Edge<K, EV>] {
+        self.edges.get_or_init(|| match self.row() {
+            Some((csr, row)) => csr.row_edges(row).collect(),
+            None => Vec::new(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        match self.row() {
+            Some((csr, row)) => csr.row_degree(row),
+            None => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,46 @@
+use geaflow_api::function::MessageCombiner;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub fn deliver<K, M>(
+    inbox: &mut HashMap<K, Vec<M>>,
+    target: K,
+    message: M,
+    combiner: Option<&dyn MessageCombiner<K, M>>,
+) where
+    K: Hash + Eq + 'static,
+    M: 'static,
+{
+    let Some(combiner) = combiner else {
+        inbox.entry(target).or_default().push(message);
+        return;
+    };
+    match inbox.get_mut(&target) {
+        Some(msgs) => match msgs.pop() {
+            Some(old) => msgs.push(combiner.combine(&target, old, message)),
+            None => msgs.push(message),
+        },
+        None => {
+            inbox.insert(target, vec![message]);
+        }
+    }
+}
+
+pub fn combine_outbox<K, M>(
+    outbox: Vec<(K, M)>,
+    combiner: &dyn MessageCombiner<K, M>,
+) -> Vec<(K, M)>
+where
+    K: Hash + Eq + 'static,
+    M: 'static,
+{
+    let mut combined: HashMap<K, M> = HashMap::with_capacity(outbox.len());
+    for (target, msg) in outbox {
+        let msg = match combined.remove(&target) {
+            Some(old) => combiner.combine(&target, old, msg),
+            None => msg,
+        };
+        combined.insert(target, msg);
+    }
+    combined.into_iter().collect()
+}
@@ -1,14 +1,24 @@
+use crate::graph::csr::{Csr, InEdgeIndex, LazyEdges, PARTITION_TOO_LARGE};
+use crate::graph::inbox::deliver;
+use crate::graph::mutation::{apply_delta, apply_mutations, invalidate};
+use crate::graph::partitioned_graph::PartitionedGraph;
+use crate::graph::traversal::{traversal_superstep, TraversalInbox};
+use crate::graph::StableMap;
+use geaflow_api::aggregator::{AggregateValue, Aggregators};
 use geaflow_api::function::{
-    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+    MessageCombiner, TraversalRequest, TraversalResponse, VertexCentricComputeAlgorithm,
+    VertexCentricComputeFuncContext, VertexCentricComputeFunction, VertexCentricTraversalAlgorithm,
 };
 use geaflow_api::graph::PGraphWindow;
+use geaflow_api::master::{MasterCompute, MasterState, MasterStep};
+use geaflow_api::mutation::{resolve_mutations, GraphMutation};
 use geaflow_common::types::{Edge, Vertex};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub struct InMemoryGraph<K, VV, EV> {
-    vertices: HashMap<K, VV>,
-    edges: Vec<Edge<K, EV>>,
-    adjacency: HashMap<K, Vec<Edge<K, EV>>>,
+    vertices: StableMap<K, VV>,
+    out_edges: Csr<K, EV>,
+    in_edges: InEdgeIndex<K, EV>,
 }
 
 impl<K, VV, EV> InMemoryGraph<K, VV, EV>
@@ -17,36 +27,58 @@ where
     VV: Clone + Send + Sync + 'static,
     EV: Clone + Send + Sync + 'static,
 {
+    /// Panics if the edges name more than `u32::MAX` vertices.
     pub fn new(vertices: Vec<Vertex<K, VV>>, edges: Vec<Edge<K, EV>>) -> Self {
-        let mut v_map = HashMap::new();
+        let mut v_map = StableMap::default();
         for v in vertices {
             v_map.insert(v.id, v.value);
         }
-        let mut adjacency: HashMap<K, Vec<Edge<K, EV>>> = HashMap::new();
-        for e in &edges {
-            adjacency
-                .entry(e.src_id.clone())
-                .or_default()
-                .push(e.clone());
-        }
         Self {
             vertices: v_map,
-            adjacency,
-            edges,
+            out_edges: Csr::out_edges(edges).expect(PARTITION_TOO_LARGE),
+            in_edges: InEdgeIndex::default(),
         }
     }
+
+    /// Whether a compute function has read in-edges since the edges last changed.
+    pub fn in_edges_indexed(&self) -> bool {
+        self.in_edges.is_built()
+    }
+
+    /// Runs `run` on a copy split into `parallelism` partitions, which compute in parallel; each
+    /// partition's function gets its own `init` and `finish`.
+    fn run_partitioned(
+        self,
+        parallelism: usize,
+        run: impl FnOnce(PartitionedGraph<K, VV, EV>) -> PartitionedGraph<K, VV, EV>,
+    ) -> Self {
+        let edges = self.out_edges.iter().collect();
+        let Self { vertices, .. } = self;
+        let vertices = vertices
+            .into_iter()
+            .map(|(id, value)| Vertex::new(id, value))
+            .collect();
+        let graph = run(PartitionedGraph::new(vertices, edges, parallelism));
+        Self::new(graph.vertices(), graph.edges())
+    }
 }
 
-struct InMemoryContext<K, VV, EV, M> {
+struct InMemoryContext<'a, K, VV, EV, M> {
     vertex_value: Option<VV>,
-    edges: Vec<Edge<K, EV>>,
+    edges: LazyEdges<'a, K, EV>,
+    in_edges: LazyEdges<'a, K, EV>,
     out_messages: Vec<(K, M)>,
     iteration: u64,
+    aggregators: &'a mut Aggregators,
+    halted: bool,
+    master: &'a MasterState,
+    mutations: Vec<GraphMutation<K, VV, EV>>,
 }
 
-impl<K, VV, EV, M> VertexCentricComputeFuncContext<K, VV, EV, M> for InMemoryContext<K, VV, EV, M>
+impl<'a, K, VV, EV, M> VertexCentricComputeFuncContext<K, VV, EV, M>
+    for InMemoryContext<'a, K, VV, EV, M>
 where
-    K: Clone,
+    K: Clone + std::hash::Hash + Eq,
     VV: Clone,
     M: Clone,
     EV: Clone,
@@ -64,12 +96,59 @@ where
     }
 
     fn edges(&self) -> Box<dyn Iterator<Item = &geaflow_api::graph::Edge<K, EV>> + '_> {
-        Box::new(self.edges.iter())
+        Box::new(self.edges.get().iter())
+    }
+
+    fn in_edges(&self) -> Box<dyn Iterator<Item = &geaflow_api::graph::Edge<K, EV>> + '_> {
+        Box::new(self.in_edges.get().iter())
+    }
+
+    fn out_degree(&self) -> usize {
+        self.edges.len()
     }
 
     fn iteration(&self) -> u64 {
         self.iteration
     }
+
+    fn aggregate(&mut self, name: &str, value: AggregateValue) {
+        self.aggregators.aggregate(name, value);
+    }
+
+    fn get_aggregated(&self, name: &str) -> Option<AggregateValue> {
+        self.aggregators.get_aggregated(name)
+    }
+
+    fn vote_to_halt(&mut self) {
+        self.halted = true;
+    }
+
+    fn phase(&self) -> u64 {
+        self.master.phase
+    }
+
+    fn get_broadcast(&self, name: &str) -> Option<AggregateValue> {
+        self.master.get_broadcast(name)
+    }
+
+    fn add_vertex(&mut self, id: K, value: VV) {
+        self.mutations
+            .push(GraphMutation::AddVertex(Vertex::new(id, value)));
+    }
+
+    fn remove_vertex(&mut self, id: K) {
+        self.mutations.push(GraphMutation::RemoveVertex(id));
+    }
+
+    fn add_edge(&mut self, src_id: K, target_id: K, value: EV) {
+        self.mutations
+            .push(GraphMutation::AddEdge(Edge::new(src_id, target_id, value)));
+    }
+
+    fn remove_edge(&mut self, src_id: K, target_id: K) {
+        self.mutations
+            .push(GraphMutation::RemoveEdge { src_id, target_id });
+    }
 }
 
 impl<K, VV, EV> PGraphWindow<K, VV, EV> for InMemoryGraph<K, VV, EV>
@@ -78,21 +157,111 @@ where
     VV: Clone + Send + Sync + 'static,
     EV: Clone + Send + Sync + 'static,
 {
-    fn compute<M, F>(mut self, mut compute_function: F, _parallelism: usize) -> Self
+    fn compute<M, F>(mut self, mut compute_function: F, parallelism: usize) -> Self
     where
-        F: VertexCentricComputeFunction<K, VV, EV, M>,
+        F: VertexCentricComputeFunction<K, VV, EV, M> + Clone,
         M: Clone + Send + Sync + 'static,
     {
-        self.run_with_function(&mut compute_function, None)
+        if parallelism > 1 {
+            return self.run_partitioned(parallelism, |g| g.compute(compute_function, parallelism));
+        }
+        self.run_with_function(&mut compute_function, None, None, None, None)
     }
 
-    fn compute_algorithm<M, A>(mut self, algorithm: &A, _parallelism: usize) -> Self
+    fn compute_algorithm<M, A>(mut self, algorithm: &A, parallelism: usize) -> Self
     where
         A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
         M: Send + Sync + 'static + Clone,
     {
+        if parallelism > 1 {
+            return self
+                .run_partitioned(parallelism, |g| g.compute_algorithm(algorithm, parallelism));
+        }
         let mut func = algorithm.create_function();
-        self.run_with_function(&mut *func, Some(algorithm.iterations()))
+        let combiner = algorithm.combiner();
+        self.run_with_function(
+            &mut *func,
+            Some(algorithm.iterations()),
+            combiner.as_deref(),
+            algorithm.master_compute(),
+            None,
+        )
+    }
+
+    fn compute_incremental<M, A>(
+        mut self,
+        algorithm: &A,
+        delta: Vec<GraphMutation<K, VV, EV>>,
+        parallelism: usize,
+    ) -> Self
+    where
+        A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
+        M: Send + Sync + 'static + Clone,
+    {
+        if parallelism > 1 {
+            return self.run_partitioned(parallelism, |g| {
+                g.compute_incremental(algorithm, delta, parallelism)
+            });
+        }
+        let mut applied =
+            apply_delta(&mut self.vertices, &mut self.out_edges, delta).expect(PARTITION_TOO_LARGE);
+        if applied.edges_changed {
+            self.in_edges.invalidate();
+        }
+        invalidate(
+            &mut self.vertices,
+            &applied.touched,
+            &mut applied.affected,
+            |t, v| algorithm.invalidates(t, v),
+            |id| algorithm.reset_value(id),
+        );
+
+        let mut func = algorithm.create_function();
+        let combiner = algorithm.combiner();
+        self.run_with_function(
+            &mut *func,
+            Some(algorithm.iterations()),
+            combiner.as_deref(),
+            algorithm.master_compute(),
+            Some(applied.affected),
+        )
+    }
+
+    fn traverse<M, R, A>(
+        &self,
+        algorithm: &A,
+        requests: Vec<TraversalRequest<K>>,
+        _parallelism: usize,
+    ) -> Vec<TraversalResponse<R>>
+    where
+        A: VertexCentricTraversalAlgorithm<K, VV, EV, M, R>,
+        M: Send + Sync + 'static + Clone,
+        R: Send + 'static,
+    {
+        let mut func = algorithm.create_function();
+        let mut inbox: TraversalInbox<K, M> = HashMap::new();
+        let mut responses = Vec::new();
+        let mut iteration: u64 = 1;
+        while iteration <= algorithm.iterations() {
+            let output = traversal_superstep(
+                &mut *func,
+                &self.vertices,
+                &self.out_edges,
+                &requests,
+                iteration,
+                inbox,
+            );
+            responses.extend(output.responses);
+            inbox = HashMap::new();
+            for (key, msg) in output.outbox {
+                inbox.entry(key).or_default().push(msg);
+            }
+            if inbox.is_empty() {
+                break;
+            }
+            iteration += 1;
+        }
+        responses
     }
 
     fn vertices(&self) -> Vec<Vertex<K, VV>> {
@@ -103,7 +272,7 @@ where
     }
 
     fn edges(&self) -> Vec<Edge<K, EV>> {
-        self.edges.clone()
+        self.out_edges.iter().collect()
     }
 }
 
@@ -113,79 +282,166 @@ where
     VV: Clone + Send + Sync + 'static,
     EV: Clone + Send + Sync + 'static,
 {
+    /// Without `max_iterations` the run stops at the first superstep that sends no messages,
+    /// whether or not every vertex voted to halt.
     fn run_with_function<M>(
         &mut self,
         compute_function: &mut dyn VertexCentricComputeFunction<K, VV, EV, M>,
         max_iterations: Option<u64>,
+        combiner: Option<&dyn MessageCombiner<K, M>>,
+        mut master: Option<Box<dyn MasterCompute>>,
+        evolving: Option<HashSet<K>>,
     ) -> Self
     where
         M: Clone + Send + Sync + 'static,
     {
         let mut messages: HashMap<K, Vec<M>> = HashMap::new();
+        let evolve = evolving.is_some();
+        let mut halted: HashSet<K> = match evolving {
+            Some(affected) => self
+                .vertices
+                .keys()
+                .filter(|id| !affected.contains(*id))
+                .cloned()
+                .collect(),
+            None => HashSet::new(),
+        };
+        let mut aggregators = Aggregators::new(&compute_function.aggregators());
+        let mut master_state = MasterState::default();
 
         let mut init_ctx = InMemoryContext::<K, VV, EV, M> {
             vertex_value: None,
-            edges: Vec::new(),
+            edges: LazyEdges::empty(),
+            in_edges: LazyEdges::empty(),
             out_messages: Vec::new(),
             iteration: 0,
+            aggregators: &mut aggregators,
+            halted: false,
+            master: &master_state,
+            mutations: Vec::new(),
         };
         compute_function.init(&mut init_ctx);
 
         let mut iteration: u64 = 1;
+        let until_quiet = max_iterations.is_none();
         let max_iterations = max_iterations.unwrap_or(u64::MAX);
 
+        let mut messages_pending = true;
+        let mut active_vertices = self.vertices.len() as u64;
+
         while iteration <= max_iterations {
+            match master_state.before_superstep(
+                &mut master,
+                iteration,
+                messages_pending,
+                active_vertices,
+                aggregators.previous(),
+            ) {
+                MasterStep::Halt => break,
+                MasterStep::Continue { wake_all } => {
+                    if wake_all {
+                        halted.clear();
+                    }
+                }
+            }
+
             let mut next_messages: HashMap<K, Vec<M>> = HashMap::new();
-            let mut msg_count = 0usize;
+            let mut mutations: Vec<GraphMutation<K, VV, EV>> = Vec::new();
 
             let all_keys: Vec<K> = self.vertices.keys().cloned().collect();
+            let out_edges = &self.out_edges;
+            let build_in_edges =
+                || Csr::in_edges(out_edges.iter().collect()).expect(PARTITION_TOO_LARGE);
 
             for v_id in all_keys {
+                let msgs = messages.remove(&v_id).unwrap_or_default();
+                if msgs.is_empty() && halted.contains(&v_id) {
+                    continue;
+                }
+
                 let v_val = self.vertices.get(&v_id).cloned();
-                let edges = self.adjacency.get(&v_id).cloned().unwrap_or_default();
 
                 let mut ctx = InMemoryContext::<K, VV, EV, M> {
                     vertex_value: v_val,
-                    edges,
+                    edges: LazyEdges::new(&self.out_edges, &v_id),
+                    in_edges: LazyEdges::in_edges(&self.in_edges, &build_in_edges, &v_id),
                     out_messages: Vec::new(),
                     iteration,
+                    aggregators: &mut aggregators,
+                    halted: false,
+                    master: &master_state,
+                    mutations: Vec::new(),
                 };
 
-                let msgs = messages.remove(&v_id).unwrap_or_default();
                 let mut msg_iter = msgs.into_iter();
 
-                compute_function.compute(&v_id, &mut msg_iter, &mut ctx);
+                if evolve && iteration == 1 {
+                    compute_function.evolve(&v_id, &mut ctx);
+                } else {
+                    compute_function.compute(&v_id, &mut msg_iter, &mut ctx);
+                }
 
                 if let Some(new_val) = ctx.vertex_value {
                     self.vertices.insert(v_id.clone(), new_val);
                 }
-
+                mutations.append(&mut ctx.mutations);
                 for (target, msg) in ctx.out_messages {
-                    next_messages.entry(target).or_default().push(msg);
-                    msg_count += 1;
+                    deliver(&mut next_messages, target, msg, combiner);
+                }
+
+                if ctx.halted {
+                    halted.insert(v_id);
+                } else {
+                    halted.remove(&v_id);
+                }
+            }
+
+            if !mutations.is_empty() {
+                resolve_mutations(&mut mutations);
+                let edges_changed = apply_mutations(
+                    &mut self.vertices,
+                    &mut self.out_edges,
+                    &mut halted,
+                    &mut next_messages,
+                    mutations,
+                )
+                .expect(PARTITION_TOO_LARGE);
+                if edges_changed {
+                    self.in_edges.invalidate();
                 }
             }
 
             messages = next_messages;
+            aggregators.advance();
             iteration += 1;
-
-            if msg_count == 0 {
-                break;
-            }
+            messages_pending = !messages.is_empty();
+            active_vertices = if until_quiet {
+                0
+            } else {
+                self.vertices
+                    .keys()
+                    .filter(|id| !halted.contains(*id))
+                    .count() as u64
+            };
         }
 
         let mut finish_ctx = InMemoryContext::<K, VV, EV, M> {
             vertex_value: None,
-            edges: Vec::new(),
+            edges: LazyEdges::empty(),
+            in_edges: LazyEdges::empty(),
             out_messages: Vec::new(),
             iteration,
+            aggregators: &mut aggregators,
+            halted: false,
+            master: &master_state,
+            mutations: Vec::new(),
         };
         compute_function.finish(&mut finish_ctx);
 
         InMemoryGraph {
             vertices: std::mem::take(&mut self.vertices),
-            edges: std::mem::take(&mut self.edges),
-            adjacency: std::mem::take(&mut self.adjacency),
+            out_edges: std::mem::take(&mut self.out_edges),
+            in_edges: std::mem::take(&mut self.in_edges),
         }
     }
 }
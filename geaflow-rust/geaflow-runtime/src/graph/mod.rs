@@ -1,2 +1,14 @@
+pub mod csr;
+pub mod inbox;
 pub mod mem_graph;
+pub mod mutation;
 pub mod partitioned_graph;
+pub mod traversal;
+
+use std::collections::HashMap;
+use std::hash::BuildHasherDefault;
+use xxhash_rust::xxh3::Xxh3Default;
+
+/// A map with a fixed hasher: the same inserts iterate in the same order on every run, so
+/// vertices compute, and their messages are combined, in a reproducible order.
+pub type StableMap<K, V> = HashMap<K, V, BuildHasherDefault<Xxh3Default>>;
@@ -0,0 +1,126 @@
+use crate::graph::csr::Csr;
+use geaflow_api::mutation::{resolve_mutations, GraphMutation};
+use geaflow_common::error::GeaFlowResult;
+use geaflow_common::types::Vertex;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+
+/// Applies mutations already put in barrier order; returns whether any edge changed.
+pub fn apply_mutations<K, VV, EV, M, S>(
+    vertices: &mut HashMap<K, VV, S>,
+    out_edges: &mut Csr<K, EV>,
+    halted: &mut HashSet<K>,
+    inbox: &mut HashMap<K, Vec<M>>,
+    mutations: Vec<GraphMutation<K, VV, EV>>,
+) -> GeaFlowResult<bool>
+where
+    K: Clone + Hash + Eq,
+    EV: Clone,
+    S: BuildHasher,
+{
+    let mut edges_changed = false;
+    for mutation in mutations {
+        match mutation {
+            GraphMutation::RemoveEdge { src_id, target_id } => {
+                edges_changed |= out_edges.remove(&src_id, &target_id);
+            }
+            GraphMutation::RemoveVertex(id) => {
+                vertices.remove(&id);
+                edges_changed |= out_edges.remove_row(&id);
+                halted.remove(&id);
+                inbox.remove(&id);
+            }
+            GraphMutation::AddVertex(Vertex { id, value }) => {
+                vertices.entry(id).or_insert(value);
+            }
+            GraphMutation::AddEdge(e) => {
+                out_edges.insert(e)?;
+                edges_changed = true;
+            }
+        }
+    }
+    Ok(edges_changed)
+}
+
+pub struct AppliedDelta<K, VV> {
+    /// Vertices whose value or adjacency the delta changed, including the targets of edge
+    /// mutations; may name vertices the delta removed.
+    pub affected: HashSet<K>,
+    /// Values the owners of removals held before the delta.
+    pub touched: Vec<VV>,
+    pub edges_changed: bool,
+}
+
+/// Applies an incremental-run delta to one partition.
+pub fn apply_delta<K, VV, EV, S>(
+    vertices: &mut HashMap<K, VV, S>,
+    out_edges: &mut Csr<K, EV>,
+    mut delta: Vec<GraphMutation<K, VV, EV>>,
+) -> GeaFlowResult<AppliedDelta<K, VV>>
+where
+    K: Clone + Hash + Eq,
+    VV: Clone,
+    EV: Clone,
+    S: BuildHasher,
+{
+    resolve_mutations(&mut delta);
+    let mut affected = HashSet::new();
+    let mut touched = Vec::new();
+    for m in &delta {
+        if matches!(
+            m,
+            GraphMutation::RemoveVertex(_) | GraphMutation::RemoveEdge { .. }
+        ) {
+            if let Some(value) = vertices.get(m.owner()) {
+                touched.push(value.clone());
+            }
+        }
+        match m {
+            GraphMutation::AddEdge(e) => {
+                affected.insert(e.target_id.clone());
+            }
+            GraphMutation::RemoveEdge { target_id, .. } => {
+                affected.insert(target_id.clone());
+            }
+            _ => {}
+        }
+        affected.insert(m.owner().clone());
+    }
+
+    let edges_changed = apply_mutations::<K, VV, EV, (), S>(
+        vertices,
+        out_edges,
+        &mut HashSet::new(),
+        &mut HashMap::new(),
+        delta,
+    )?;
+    Ok(AppliedDelta {
+        affected,
+        touched,
+        edges_changed,
+    })
+}
+
+/// Resets every vertex a removal made stale and adds it to `affected`.
+pub fn invalidate<K, VV, S>(
+    vertices: &mut HashMap<K, VV, S>,
+    touched: &[VV],
+    affected: &mut HashSet<K>,
+    invalidates: impl Fn(&VV, &VV) -> bool,
+    reset_value: impl Fn(&K) -> Option<VV>,
+) where
+    K: Clone + Hash + Eq,
+    S: BuildHasher,
+{
+    if touched.is_empty() {
+        return;
+    }
+    for (id, value) in vertices.iter_mut() {
+        if touched.iter().any(|t| invalidates(t, value)) {
+            if let Some(reset) = reset_value(id) {
+                *value = reset;
+            }
+            affected.insert(id.clone());
+        }
+    }
+}
@@ -1,19 +1,44 @@
+use crate::graph::csr::{Csr, InEdgeIndex, LazyEdges, PARTITION_TOO_LARGE};
+use crate::graph::inbox::{combine_outbox, deliver};
+use crate::graph::mutation::{apply_delta, apply_mutations, invalidate};
+use crate::graph::traversal::{traversal_superstep, TraversalInbox};
+use crate::graph::StableMap;
+use crate::shuffle::vertex_cut::{EdgePartitioning, EdgePlacer};
+use geaflow_api::aggregator::{AggregateValue, AggregateValues, Aggregators};
 use geaflow_api::function::{
-    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+    TraversalRequest, TraversalResponse, VertexCentricComputeAlgorithm,
+    VertexCentricComputeFuncContext, VertexCentricComputeFunction, VertexCentricTraversalAlgorithm,
 };
 use geaflow_api::graph::PGraphWindow;
+use geaflow_api::master::{MasterState, MasterStep};
+use geaflow_api::mutation::{resolve_mutations, GraphMutation};
 use geaflow_common::types::{Edge, Vertex};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+#[derive(Clone)]
 pub struct PartitionedGraph<K, VV, EV> {
     partitions: Vec<GraphPartition<K, VV, EV>>,
-    all_edges: Vec<Edge<K, EV>>,
+    edge_partitioning: EdgePartitioning,
+    /// Under a vertex-cut, the partitions holding a mirror of each vertex.
+    mirrors_of: StableMap<K, Vec<usize>>,
+    aggregated: AggregateValues,
 }
 
+#[derive(Clone)]
 struct GraphPartition<K, VV, EV> {
-    vertices: HashMap<K, VV>,
-    adjacency: HashMap<K, Vec<Edge<K, EV>>>,
+    vertices: StableMap<K, VV>,
+    /// Shared with the other partitions while they build their in-edge index.
+    out_edges: Arc<Csr<K, EV>>,
+    /// In-edges of this partition's vertices. Under a vertex-cut, the in-edges stored here, of
+    /// masters and mirrors alike.
+    in_edges: InEdgeIndex<K, EV>,
+    /// Copies of vertices mastered elsewhere that have edges here, refreshed from the master
+    /// at every barrier.
+    mirrors: StableMap<K, VV>,
+    /// Global out-degree of the vertices here whose edges are split across partitions.
+    out_degrees: HashMap<K, usize>,
 }
 
 impl<K, VV, EV> PartitionedGraph<K, VV, EV>
@@ -23,11 +48,26 @@ where
     EV: Clone + Send + Sync + 'static,
 {
     pub fn new(vertices: Vec<Vertex<K, VV>>, edges: Vec<Edge<K, EV>>, partitions: usize) -> Self {
+        Self::with_edge_partitioning(vertices, edges, partitions, EdgePartitioning::EdgeCut)
+    }
+
+    /// Places edges by `edge_partitioning`; a vertex-cut spreads the edges of high-degree
+    /// vertices over several partitions, each computing on a replica of the vertex. Panics if
+    /// one partition's edges name more than `u32::MAX` vertices.
+    pub fn with_edge_partitioning(
+        vertices: Vec<Vertex<K, VV>>,
+        edges: Vec<Edge<K, EV>>,
+        partitions: usize,
+        edge_partitioning: EdgePartitioning,
+    ) -> Self {
         let partitions = partitions.max(1);
         let mut parts: Vec<GraphPartition<K, VV, EV>> = (0..partitions)
             .map(|_| GraphPartition {
-                vertices: HashMap::new(),
-                adjacency: HashMap::new(),
+                vertices: StableMap::default(),
+                out_edges: Arc::default(),
+                in_edges: InEdgeIndex::default(),
+                mirrors: StableMap::default(),
+                out_degrees: HashMap::new(),
             })
             .collect();
 
@@ -36,271 +76,780 @@ where
             parts[p].vertices.insert(v.id, v.value);
         }
 
-        for e in &edges {
-            let p = partition_of(&e.src_id, partitions);
-            parts[p]
-                .adjacency
-                .entry(e.src_id.clone())
-                .or_default()
-                .push(e.clone());
+        let mut placer = EdgePlacer::new(edge_partitioning, partitions);
+        let mut placed: Vec<Vec<Edge<K, EV>>> = (0..partitions).map(|_| Vec::new()).collect();
+        for e in edges {
+            let p = placer.place(
+                &e.src_id,
+                &e.target_id,
+                partition_of(&e.src_id, partitions),
+                partition_of(&e.target_id, partitions),
+            );
+            placed[p].push(e);
+        }
+        drop(placer);
+        for (part, edges) in parts.iter_mut().zip(placed) {
+            part.out_edges = Arc::new(Csr::out_edges(edges).expect(PARTITION_TOO_LARGE));
         }
+        let mirrors_of = index_edges(parts.iter_mut().collect(), edge_partitioning);
 
         Self {
             partitions: parts,
-            all_edges: edges,
+            edge_partitioning,
+            mirrors_of,
+            aggregated: AggregateValues::new(),
         }
     }
 
     pub fn partitions(&self) -> usize {
         self.partitions.len()
     }
-}
-
-fn partition_of<K: std::hash::Hash>(k: &K, partitions: usize) -> usize {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::Hasher;
-    let mut h = DefaultHasher::new();
-    k.hash(&mut h);
-    (h.finish() as usize) % partitions
-}
 
-struct WorkerContext<'a, K, VV, EV, M> {
-    vertex_value: Option<VV>,
-    edges: &'a [Edge<K, EV>],
-    outbox: &'a mut Vec<(K, M)>,
-    iteration: u64,
-}
-
-impl<'a, K, VV, EV, M> VertexCentricComputeFuncContext<K, VV, EV, M>
-    for WorkerContext<'a, K, VV, EV, M>
-where
-    K: Clone,
-    VV: Clone,
-    EV: Clone,
-    M: Clone,
-{
-    fn vertex_value(&self) -> Option<&VV> {
-        self.vertex_value.as_ref()
+    pub fn edge_partitioning(&self) -> EdgePartitioning {
+        self.edge_partitioning
     }
 
-    fn set_new_vertex_value(&mut self, value: VV) {
-        self.vertex_value = Some(value);
+    /// Mirrors per partition; all zero under an edge-cut.
+    pub fn mirror_counts(&self) -> Vec<usize> {
+        self.partitions.iter().map(|p| p.mirrors.len()).collect()
     }
 
-    fn send_message(&mut self, target_id: K, message: M) {
-        self.outbox.push((target_id, message));
+    /// Aggregates of the last superstep the latest algorithm ran.
+    pub fn aggregated(&self) -> &AggregateValues {
+        &self.aggregated
     }
 
-    fn edges(&self) -> Box<dyn Iterator<Item = &geaflow_api::graph::Edge<K, EV>> + '_> {
-        Box::new(self.edges.iter())
+    /// Whether a compute function has read in-edges since the edges last changed.
+    pub fn in_edges_indexed(&self) -> bool {
+        self.partitions.iter().any(|p| p.in_edges.is_built())
     }
 
-    fn iteration(&self) -> u64 {
-        self.iteration
+    /// Edges stored per partition.
+    pub fn edge_counts(&self) -> Vec<usize> {
+        self.partitions.iter().map(|p| p.out_edges.len()).collect()
     }
-}
-
-struct LocalWorker<K, VV, EV, M> {
-    partition: GraphPartition<K, VV, EV>,
-    inbox: HashMap<K, Vec<M>>,
-    outbox: Vec<(K, M)>,
-    func: Box<dyn VertexCentricComputeFunction<K, VV, EV, M>>,
-}
 
-impl<K, VV, EV> PGraphWindow<K, VV, EV> for PartitionedGraph<K, VV, EV>
-where
-    K: Clone + Eq + std::hash::Hash + Send + Sync + 'static,
-    VV: Clone + Send + Sync + 'static,
-    EV: Clone + Send + Sync + 'static,
-{
-    fn compute<M, F>(self, mut compute_function: F, _parallelism: usize) -> Self
-    where
-        F: VertexCentricComputeFunction<K, VV, EV, M>,
-        M: Send + Sync + 'static + Clone,
-    {
-        let mut graph = self;
-
-        let mut init_ctx = WorkerContext::<K, VV, EV, M> {
-            vertex_value: None,
-            edges: &[],
-            outbox: &mut Vec::new(),
-            iteration: 0,
-        };
-        compute_function.init(&mut init_ctx);
-
-        let partitions = graph.partitions.len();
-        let mut inbox: Vec<HashMap<K, Vec<M>>> = (0..partitions).map(|_| HashMap::new()).collect();
-
-        let mut iteration: u64 = 1;
-        loop {
-            let mut outbox: Vec<(K, M)> = Vec::new();
-
-            for part in &mut graph.partitions {
-                let keys: Vec<K> = part.vertices.keys().cloned().collect();
-                for vertex_id in keys {
-                    let msgs = inbox[partition_of(&vertex_id, partitions)]
-                        .remove(&vertex_id)
-                        .unwrap_or_default();
-                    let mut msg_iter = msgs.into_iter();
-                    let edges = part
-                        .adjacency
-                        .get(&vertex_id)
-                        .map(|v| v.as_slice())
-                        .unwrap_or(&[]);
-                    let v_val = part.vertices.get(&vertex_id).cloned();
-                    let mut ctx = WorkerContext {
-                        vertex_value: v_val,
-                        edges,
-                        outbox: &mut outbox,
-                        iteration,
-                    };
-                    compute_function.compute(&vertex_id, &mut msg_iter, &mut ctx);
-                    if let Some(new_v) = ctx.vertex_value {
-                        part.vertices.insert(vertex_id.clone(), new_v);
-                    }
-                }
-            }
-
-            if outbox.is_empty() {
-                break;
-            }
-
-            let mut next: Vec<HashMap<K, Vec<M>>> =
-                (0..partitions).map(|_| HashMap::new()).collect();
-            for (target, msg) in outbox {
-                let p = partition_of(&target, partitions);
-                next[p].entry(target).or_default().push(msg);
-            }
-            inbox = next;
-            iteration += 1;
+    fn repartition(self, parallelism: usize) -> Self {
+        let partitions = parallelism.max(self.partitions());
+        if partitions == self.partitions() {
+            self
+        } else {
+            let vertices = self.vertices();
+            let edges = self.edges();
+            PartitionedGraph::with_edge_partitioning(
+                vertices,
+                edges,
+                partitions,
+                self.edge_partitioning,
+            )
         }
-
-        let mut finish_ctx = WorkerContext::<K, VV, EV, M> {
-            vertex_value: None,
-            edges: &[],
-            outbox: &mut Vec::new(),
-            iteration,
-        };
-        compute_function.finish(&mut finish_ctx);
-
-        graph
     }
 
-    fn compute_algorithm<M, A>(self, algorithm: &A, parallelism: usize) -> Self
+    /// Runs `algorithm` over the current partitions. Without `max_iterations` the run stops at
+    /// the first superstep that sends no messages, whether or not every vertex voted to halt.
+    /// With `evolving`, only the listed vertices start active and they evolve in the first
+    /// superstep.
+    fn run_algorithm<M, A>(
+        self,
+        algorithm: &A,
+        max_iterations: Option<u64>,
+        evolving: Option<HashSet<K>>,
+    ) -> Self
     where
         A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
         M: Send + Sync + 'static + Clone,
     {
-        let partitions = parallelism.max(self.partitions());
-        let mut graph = if partitions == self.partitions() {
-            self
-        } else {
-            let vertices = self.vertices();
-            let edges = self.edges();
-            PartitionedGraph::new(vertices, edges, partitions)
-        };
-
+        let evolve = evolving.is_some();
+        let mut graph = self;
         let mut workers: Vec<LocalWorker<K, VV, EV, M>> = graph
             .partitions
             .drain(..)
-            .map(|partition| LocalWorker {
-                partition,
-                inbox: HashMap::new(),
-                outbox: Vec::new(),
-                func: algorithm.create_function(),
+            .map(|partition| {
+                let func = algorithm.create_function();
+                let aggregators = Aggregators::new(&func.aggregators());
+                let halted = match &evolving {
+                    Some(affected) => partition
+                        .vertices
+                        .keys()
+                        .filter(|id| !affected.contains(*id))
+                        .cloned()
+                        .collect(),
+                    None => HashSet::new(),
+                };
+                LocalWorker {
+                    partition,
+                    inbox: HashMap::new(),
+                    outbox: Vec::new(),
+                    mutations: Vec::new(),
+                    func,
+                    aggregators,
+                    halted,
+                }
             })
             .collect();
+        let mut global_aggregators = workers
+            .first()
+            .map(|w| w.aggregators.clone())
+            .unwrap_or_default();
+        let mut master = algorithm.master_compute();
+        let mut master_state = MasterState::default();
 
         workers.par_iter_mut().for_each(|w| {
             let mut init_ctx = WorkerContext::<K, VV, EV, M> {
                 vertex_value: None,
-                edges: &[],
+                edges: LazyEdges::empty(),
+                in_edges: LazyEdges::empty(),
+                out_degree: None,
                 outbox: &mut Vec::new(),
                 iteration: 0,
+                aggregators: &mut w.aggregators,
+                halted: false,
+                master: &master_state,
+                mutations: &mut Vec::new(),
             };
             w.func.init(&mut init_ctx);
         });
+        sync_mirrors(
+            &graph.mirrors_of,
+            workers
+                .iter_mut()
+                .map(|w| (&mut w.partition, &mut w.halted))
+                .collect(),
+        );
 
-        let max_iterations = algorithm.iterations();
+        let until_quiet = max_iterations.is_none();
+        let max_iterations = max_iterations.unwrap_or(u64::MAX);
+        let combiner = algorithm.combiner();
+        let combiner = combiner.as_deref();
         let partitions = workers.len();
         let mut iteration: u64 = 1;
+        let mut messages_pending = true;
+        let mut active_vertices: u64 = workers
+            .iter()
+            .map(|w| w.partition.vertices.len() as u64)
+            .sum();
 
         while iteration <= max_iterations {
-            workers.par_iter_mut().for_each(|w| {
+            match master_state.before_superstep(
+                &mut master,
+                iteration,
+                messages_pending,
+                active_vertices,
+                global_aggregators.previous(),
+            ) {
+                MasterStep::Halt => break,
+                MasterStep::Continue { wake_all } => {
+                    if wake_all {
+                        workers.iter_mut().for_each(|w| w.halted.clear());
+                    }
+                }
+            }
+
+            let out_edges: Vec<Arc<Csr<K, EV>>> = workers
+                .iter()
+                .map(|w| Arc::clone(&w.partition.out_edges))
+                .collect();
+            let vertex_cut = graph.edge_partitioning.is_vertex_cut();
+            workers.par_iter_mut().enumerate().for_each(|(p, w)| {
+                let build_in_edges = || build_in_edges(&out_edges, p, vertex_cut);
                 w.outbox.clear();
-                let keys: Vec<K> = w.partition.vertices.keys().cloned().collect();
+                // Mirrors only send along their own edges: the master's value, aggregates and
+                // mutations are the ones that count.
+                let mut mirror_aggregators = w.aggregators.clone();
+                let mut mirror_mutations = Vec::new();
+                let keys: Vec<K> = w
+                    .partition
+                    .vertices
+                    .keys()
+                    .chain(w.partition.mirrors.keys())
+                    .cloned()
+                    .collect();
                 for vertex_id in keys {
                     let msgs = w.inbox.remove(&vertex_id).unwrap_or_default();
+                    if msgs.is_empty() && w.halted.contains(&vertex_id) {
+                        continue;
+                    }
                     let mut msg_iter = msgs.into_iter();
-                    let edges = w
+                    let mirror = !w.partition.vertices.contains_key(&vertex_id);
+                    let v_val = w
                         .partition
-                        .adjacency
+                        .vertices
                         .get(&vertex_id)
-                        .map(|v| v.as_slice())
-                        .unwrap_or(&[]);
-                    let v_val = w.partition.vertices.get(&vertex_id).cloned();
+                        .or_else(|| w.partition.mirrors.get(&vertex_id))
+                        .cloned();
                     let mut ctx = WorkerContext {
                         vertex_value: v_val,
-                        edges,
+                        edges: LazyEdges::new(&w.partition.out_edges, &vertex_id),
+                        in_edges: LazyEdges::in_edges(
+                            &w.partition.in_edges,
+                            &build_in_edges,
+                            &vertex_id,
+                        ),
+                        out_degree: w.partition.out_degrees.get(&vertex_id).copied(),
                         outbox: &mut w.outbox,
                         iteration,
+                        aggregators: if mirror {
+                            &mut mirror_aggregators
+                        } else {
+                            &mut w.aggregators
+                        },
+                        halted: false,
+                        master: &master_state,
+                        mutations: if mirror {
+                            &mut mirror_mutations
+                        } else {
+                            &mut w.mutations
+                        },
                     };
-                    w.func.compute(&vertex_id, &mut msg_iter, &mut ctx);
-                    if let Some(new_v) = ctx.vertex_value {
-                        w.partition.vertices.insert(vertex_id.clone(), new_v);
+                    if evolve && iteration == 1 {
+                        w.func.evolve(&vertex_id, &mut ctx);
+                    } else {
+                        w.func.compute(&vertex_id, &mut msg_iter, &mut ctx);
+                    }
+                    let voted = ctx.halted;
+                    match ctx.vertex_value {
+                        Some(new_v) if !mirror => {
+                            w.partition.vertices.insert(vertex_id.clone(), new_v);
+                        }
+                        _ => {}
+                    }
+                    if voted {
+                        w.halted.insert(vertex_id);
+                    } else {
+                        w.halted.remove(&vertex_id);
                     }
                 }
+                if let Some(c) = combiner {
+                    w.outbox = combine_outbox(std::mem::take(&mut w.outbox), c);
+                }
             });
+            drop(out_edges);
 
-            let mut any_msg = false;
             let mut next_inboxes: Vec<HashMap<K, Vec<M>>> =
                 (0..partitions).map(|_| HashMap::new()).collect();
 
             for w in &mut workers {
                 for (target, msg) in w.outbox.drain(..) {
+                    for &m in graph.mirrors_of.get(&target).into_iter().flatten() {
+                        deliver(&mut next_inboxes[m], target.clone(), msg.clone(), combiner);
+                    }
                     let p = partition_of(&target, partitions);
-                    next_inboxes[p].entry(target).or_default().push(msg);
-                    any_msg = true;
+                    deliver(&mut next_inboxes[p], target, msg, combiner);
                 }
             }
 
+            for w in &mut workers {
+                global_aggregators.merge(&w.aggregators.take_current());
+            }
+            global_aggregators.advance();
+
+            let mutations: Vec<GraphMutation<K, VV, EV>> = workers
+                .iter_mut()
+                .flat_map(|w| std::mem::take(&mut w.mutations))
+                .collect();
+            let mut routed = if mutations.is_empty() {
+                Vec::new()
+            } else {
+                route_mutations(mutations, partitions, graph.edge_partitioning)
+            };
+
+            let mut superstep_edges_changed = false;
             for (i, w) in workers.iter_mut().enumerate() {
                 w.inbox = std::mem::take(&mut next_inboxes[i]);
+                w.aggregators
+                    .set_previous(global_aggregators.previous().clone());
+                if let Some(batch) = routed.get_mut(i) {
+                    let batch = std::mem::take(batch);
+                    let part = &mut w.partition;
+                    superstep_edges_changed |= apply_mutations(
+                        &mut part.vertices,
+                        Arc::make_mut(&mut part.out_edges),
+                        &mut w.halted,
+                        &mut w.inbox,
+                        batch,
+                    )
+                    .expect(PARTITION_TOO_LARGE);
+                }
             }
+            if superstep_edges_changed {
+                graph.mirrors_of = index_edges(
+                    workers.iter_mut().map(|w| &mut w.partition).collect(),
+                    graph.edge_partitioning,
+                );
+            }
+            sync_mirrors(
+                &graph.mirrors_of,
+                workers
+                    .iter_mut()
+                    .map(|w| (&mut w.partition, &mut w.halted))
+                    .collect(),
+            );
 
             iteration += 1;
-            if !any_msg {
-                break;
-            }
+            messages_pending = workers.iter().any(|w| !w.inbox.is_empty());
+            // Mirrors follow their master's vote, so only masters are counted.
+            active_vertices = if until_quiet {
+                0
+            } else {
+                workers
+                    .iter()
+                    .map(|w| {
+                        w.partition
+                            .vertices
+                            .keys()
+                            .filter(|id| !w.halted.contains(*id))
+                            .count() as u64
+                    })
+                    .sum()
+            };
         }
 
         workers.par_iter_mut().for_each(|w| {
             let mut finish_ctx = WorkerContext::<K, VV, EV, M> {
                 vertex_value: None,
-                edges: &[],
+                edges: LazyEdges::empty(),
+                in_edges: LazyEdges::empty(),
+                out_degree: None,
                 outbox: &mut Vec::new(),
                 iteration,
+                aggregators: &mut w.aggregators,
+                halted: false,
+                master: &master_state,
+                mutations: &mut Vec::new(),
             };
             w.func.finish(&mut finish_ctx);
         });
 
-        let mut merged_vertices: HashMap<K, VV> = HashMap::new();
-        for w in workers {
-            for (k, v) in w.partition.vertices {
-                merged_vertices.insert(k, v);
+        graph.partitions = workers.into_iter().map(|w| w.partition).collect();
+        graph.aggregated = global_aggregators.previous().clone();
+        graph
+    }
+}
+
+/// Drops every in-edge index and, under a vertex-cut, rebuilds the mirrors; returns the
+/// partitions mirroring each vertex.
+fn index_edges<K, VV, EV>(
+    partitions: Vec<&mut GraphPartition<K, VV, EV>>,
+    edge_partitioning: EdgePartitioning,
+) -> StableMap<K, Vec<usize>>
+where
+    K: Clone + Eq + std::hash::Hash,
+    VV: Clone,
+    EV: Clone,
+{
+    let mut partitions = partitions;
+    for part in partitions.iter_mut() {
+        part.in_edges.invalidate();
+    }
+    if edge_partitioning.is_vertex_cut() {
+        index_replicas(partitions)
+    } else {
+        StableMap::default()
+    }
+}
+
+/// The in-edges partition `p` indexes: under a vertex-cut those it stores, otherwise those of
+/// its own vertices, from the out-edges of every partition.
+fn build_in_edges<K, EV>(out_edges: &[Arc<Csr<K, EV>>], p: usize, vertex_cut: bool) -> Csr<K, EV>
+where
+    K: Clone + Eq + std::hash::Hash,
+    EV: Clone,
+{
+    if vertex_cut {
+        return Csr::in_edges(out_edges[p].iter().collect()).expect(PARTITION_TOO_LARGE);
+    }
+    let n = out_edges.len();
+    Csr::in_edges(
+        out_edges
+            .iter()
+            .flat_map(|part| part.iter())
+            .filter(|e| partition_of(&e.target_id, n) == p)
+            .collect(),
+    )
+    .expect(PARTITION_TOO_LARGE)
+}
+
+/// Vertex-cut indexing: each partition indexes the in-edges it stores and mirrors every vertex
+/// it holds edges of without being its master.
+fn index_replicas<K, VV, EV>(
+    mut partitions: Vec<&mut GraphPartition<K, VV, EV>>,
+) -> StableMap<K, Vec<usize>>
+where
+    K: Clone + Eq + std::hash::Hash,
+    VV: Clone,
+    EV: Clone,
+{
+    let n = partitions.len();
+    let mut mirrors_of: StableMap<K, Vec<usize>> = StableMap::default();
+    let mut out_degrees: HashMap<K, usize> = HashMap::new();
+    for (p, part) in partitions.iter_mut().enumerate() {
+        for (src, degree) in part.out_edges.rows() {
+            *out_degrees.entry(src.clone()).or_default() += degree;
+        }
+        let stored = part
+            .out_edges
+            .rows()
+            .map(|(id, _)| id)
+            .chain(part.out_edges.neighbours());
+        for id in stored {
+            if partition_of(id, n) != p {
+                let mirrors = mirrors_of.entry(id.clone()).or_default();
+                if mirrors.last() != Some(&p) {
+                    mirrors.push(p);
+                }
             }
         }
+    }
+    mirrors_of.retain(|id, _| partitions[partition_of(id, n)].vertices.contains_key(id));
 
-        PartitionedGraph::new(
-            merged_vertices
-                .into_iter()
-                .map(|(id, value)| Vertex { id, value })
-                .collect(),
-            graph.all_edges.clone(),
-            partitions,
+    for part in partitions.iter_mut() {
+        part.mirrors.clear();
+        part.out_degrees.clear();
+    }
+    for (id, mirrors) in &mirrors_of {
+        let master = partition_of(id, n);
+        let degree = out_degrees.get(id).copied().unwrap_or(0);
+        let value = partitions[master].vertices[id].clone();
+        partitions[master].out_degrees.insert(id.clone(), degree);
+        for &p in mirrors {
+            partitions[p].mirrors.insert(id.clone(), value.clone());
+            partitions[p].out_degrees.insert(id.clone(), degree);
+        }
+    }
+    mirrors_of
+}
+
+/// A partition with the ids halted on it.
+type HaltedPartition<'a, K, VV, EV> = (&'a mut GraphPartition<K, VV, EV>, &'a mut HashSet<K>);
+
+/// The vertex-cut barrier: each mirror takes its master's value and halt state, and mirrors of
+/// removed vertices are dropped.
+fn sync_mirrors<K, VV, EV>(
+    mirrors_of: &StableMap<K, Vec<usize>>,
+    mut partitions: Vec<HaltedPartition<K, VV, EV>>,
+) where
+    K: Clone + Eq + std::hash::Hash,
+    VV: Clone,
+{
+    let n = partitions.len();
+    for (id, mirrors) in mirrors_of {
+        let (master, halted) = &partitions[partition_of(id, n)];
+        let value = master.vertices.get(id).cloned();
+        let is_halted = halted.contains(id);
+        for &p in mirrors {
+            let (part, halted) = &mut partitions[p];
+            match &value {
+                Some(value) => {
+                    part.mirrors.insert(id.clone(), value.clone());
+                }
+                None => {
+                    part.mirrors.remove(id);
+                }
+            }
+            if is_halted {
+                halted.insert(id.clone());
+            } else {
+                halted.remove(id);
+            }
+        }
+    }
+}
+
+/// xxh3 rather than `DefaultHasher`, whose output may change between Rust releases.
+fn partition_of<K: std::hash::Hash>(k: &K, partitions: usize) -> usize {
+    use std::hash::Hasher;
+    let mut h = xxhash_rust::xxh3::Xxh3::new();
+    k.hash(&mut h);
+    (h.finish() % partitions as u64) as usize
+}
+
+/// Sends each mutation to its owner's partition, where added edges are stored. Under a
+/// vertex-cut a removal may concern edges on any partition, so it goes to all of them.
+fn route_mutations<K, VV, EV>(
+    mut mutations: Vec<GraphMutation<K, VV, EV>>,
+    partitions: usize,
+    edge_partitioning: EdgePartitioning,
+) -> Vec<Vec<GraphMutation<K, VV, EV>>>
+where
+    K: Clone + std::hash::Hash,
+    VV: Clone,
+    EV: Clone,
+{
+    resolve_mutations(&mut mutations);
+    let mut routed: Vec<Vec<GraphMutation<K, VV, EV>>> =
+        (0..partitions).map(|_| Vec::new()).collect();
+    for m in mutations {
+        let removal = matches!(
+            m,
+            GraphMutation::RemoveVertex(_) | GraphMutation::RemoveEdge { .. }
+        );
+        if removal && edge_partitioning.is_vertex_cut() {
+            for batch in routed.iter_mut() {
+                batch.push(m.clone());
+            }
+        } else {
+            routed[partition_of(m.owner(), partitions)].push(m);
+        }
+    }
+    routed
+}
+
+struct WorkerContext<'a, K, VV, EV, M> {
+    vertex_value: Option<VV>,
+    edges: LazyEdges<'a, K, EV>,
+    in_edges: LazyEdges<'a, K, EV>,
+    out_degree: Option<usize>,
+    outbox: &'a mut Vec<(K, M)>,
+    iteration: u64,
+    aggregators: &'a mut Aggregators,
+    halted: bool,
+    master: &'a MasterState,
+    mutations: &'a mut Vec<GraphMutation<K, VV, EV>>,
+}
+
+impl<'a, K, VV, EV, M> VertexCentricComputeFuncContext<K, VV, EV, M>
+    for WorkerContext<'a, K, VV, EV, M>
+where
+    K: Clone + Eq + std::hash::Hash,
+    VV: Clone,
+    EV: Clone,
+    M: Clone,
+{
+    fn vertex_value(&self) -> Option<&VV> {
+        self.vertex_value.as_ref()
+    }
+
+    fn set_new_vertex_value(&mut self, value: VV) {
+        self.vertex_value = Some(value);
+    }
+
+    fn send_message(&mut self, target_id: K, message: M) {
+        self.outbox.push((target_id, message));
+    }
+
+    fn edges(&self) -> Box<dyn Iterator<Item = &geaflow_api::graph::Edge<K, EV>> + '_> {
+        Box::new(self.edges.get().iter())
+    }
+
+    fn in_edges(&self) -> Box<dyn Iterator<Item = &geaflow_api::graph::Edge<K, EV>> + '_> {
+        Box::new(self.in_edges.get().iter())
+    }
+
+    fn out_degree(&self) -> usize {
+        self.out_degree.unwrap_or(self.edges.len())
+    }
+
+    fn iteration(&self) -> u64 {
+        self.iteration
+    }
+
+    fn aggregate(&mut self, name: &str, value: AggregateValue) {
+        self.aggregators.aggregate(name, value);
+    }
+
+    fn get_aggregated(&self, name: &str) -> Option<AggregateValue> {
+        self.aggregators.get_aggregated(name)
+    }
+
+    fn vote_to_halt(&mut self) {
+        self.halted = true;
+    }
+
+    fn phase(&self) -> u64 {
+        self.master.phase
+    }
+
+    fn get_broadcast(&self, name: &str) -> Option<AggregateValue> {
+        self.master.get_broadcast(name)
+    }
+
+    fn add_vertex(&mut self, id: K, value: VV) {
+        self.mutations
+            .push(GraphMutation::AddVertex(Vertex::new(id, value)));
+    }
+
+    fn remove_vertex(&mut self, id: K) {
+        self.mutations.push(GraphMutation::RemoveVertex(id));
+    }
+
+    fn add_edge(&mut self, src_id: K, target_id: K, value: EV) {
+        self.mutations
+            .push(GraphMutation::AddEdge(Edge::new(src_id, target_id, value)));
+    }
+
+    fn remove_edge(&mut self, src_id: K, target_id: K) {
+        self.mutations
+            .push(GraphMutation::RemoveEdge { src_id, target_id });
+    }
+}
+
+/// Runs a bare compute function, each partition on its own clone.
+struct FunctionAlgorithm<F>(F);
+
+impl<K, VV, EV, M, F> VertexCentricComputeAlgorithm<K, VV, EV, M> for FunctionAlgorithm<F>
+where
+    F: VertexCentricComputeFunction<K, VV, EV, M> + Clone,
+{
+    fn name(&self) -> &str {
+        "compute"
+    }
+
+    fn iterations(&self) -> u64 {
+        u64::MAX
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<K, VV, EV, M>> {
+        Box::new(self.0.clone())
+    }
+}
+
+struct LocalWorker<K, VV, EV, M> {
+    partition: GraphPartition<K, VV, EV>,
+    inbox: HashMap<K, Vec<M>>,
+    outbox: Vec<(K, M)>,
+    mutations: Vec<GraphMutation<K, VV, EV>>,
+    func: Box<dyn VertexCentricComputeFunction<K, VV, EV, M>>,
+    aggregators: Aggregators,
+    halted: HashSet<K>,
+}
+
+impl<K, VV, EV> PGraphWindow<K, VV, EV> for PartitionedGraph<K, VV, EV>
+where
+    K: Clone + Eq + std::hash::Hash + Send + Sync + 'static,
+    VV: Clone + Send + Sync + 'static,
+    EV: Clone + Send + Sync + 'static,
+{
+    fn compute<M, F>(self, compute_function: F, parallelism: usize) -> Self
+    where
+        F: VertexCentricComputeFunction<K, VV, EV, M> + Clone,
+        M: Send + Sync + 'static + Clone,
+    {
+        self.repartition(parallelism).run_algorithm(
+            &FunctionAlgorithm(compute_function),
+            None,
+            None,
         )
     }
 
+    fn compute_algorithm<M, A>(self, algorithm: &A, parallelism: usize) -> Self
+    where
+        A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
+        M: Send + Sync + 'static + Clone,
+    {
+        self.repartition(parallelism)
+            .run_algorithm(algorithm, Some(algorithm.iterations()), None)
+    }
+
+    fn compute_incremental<M, A>(
+        self,
+        algorithm: &A,
+        delta: Vec<GraphMutation<K, VV, EV>>,
+        parallelism: usize,
+    ) -> Self
+    where
+        A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
+        M: Send + Sync + 'static + Clone,
+    {
+        let mut graph = self.repartition(parallelism);
+        let partitions = graph.partitions();
+        let mut affected = HashSet::new();
+        let mut touched = Vec::new();
+        let mut edges_changed = false;
+        for (part, batch) in graph.partitions.iter_mut().zip(route_mutations(
+            delta,
+            partitions,
+            graph.edge_partitioning,
+        )) {
+            let mut applied = apply_delta(
+                &mut part.vertices,
+                Arc::make_mut(&mut part.out_edges),
+                batch,
+            )
+            .expect(PARTITION_TOO_LARGE);
+            affected.extend(applied.affected);
+            touched.append(&mut applied.touched);
+            edges_changed |= applied.edges_changed;
+        }
+        for part in graph.partitions.iter_mut() {
+            invalidate(
+                &mut part.vertices,
+                &touched,
+                &mut affected,
+                |t, v| algorithm.invalidates(t, v),
+                |id| algorithm.reset_value(id),
+            );
+        }
+        if edges_changed {
+            graph.mirrors_of = index_edges(
+                graph.partitions.iter_mut().collect(),
+                graph.edge_partitioning,
+            );
+        }
+        graph.run_algorithm(algorithm, Some(algorithm.iterations()), Some(affected))
+    }
+
+    fn traverse<M, R, A>(
+        &self,
+        algorithm: &A,
+        requests: Vec<TraversalRequest<K>>,
+        parallelism: usize,
+    ) -> Vec<TraversalResponse<R>>
+    where
+        A: VertexCentricTraversalAlgorithm<K, VV, EV, M, R>,
+        M: Send + Sync + 'static + Clone,
+        R: Send + 'static,
+    {
+        if self.edge_partitioning.is_vertex_cut() {
+            // Traversals follow each vertex's edges from its own partition.
+            return PartitionedGraph::new(self.vertices(), self.edges(), self.partitions())
+                .traverse(algorithm, requests, parallelism);
+        }
+        let partitions = self.partitions.len();
+        let mut workers: Vec<_> = self
+            .partitions
+            .iter()
+            .map(|part| {
+                (
+                    part,
+                    algorithm.create_function(),
+                    TraversalInbox::<K, M>::new(),
+                )
+            })
+            .collect();
+        let mut responses = Vec::new();
+        let mut iteration: u64 = 1;
+        while iteration <= algorithm.iterations() {
+            let outputs: Vec<_> = workers
+                .par_iter_mut()
+                .map(|(part, func, inbox)| {
+                    traversal_superstep(
+                        &mut **func,
+                        &part.vertices,
+                        &part.out_edges,
+                        &requests,
+                        iteration,
+                        std::mem::take(inbox),
+                    )
+                })
+                .collect();
+
+            let mut any_msg = false;
+            for output in outputs {
+                responses.extend(output.responses);
+                for (key, msg) in output.outbox {
+                    any_msg = true;
+                    let p = partition_of(&key.1, partitions);
+                    workers[p].2.entry(key).or_default().push(msg);
+                }
+            }
+            if !any_msg {
+                break;
+            }
+            iteration += 1;
+        }
+        responses
+    }
+
     fn vertices(&self) -> Vec<Vertex<K, VV>> {
         self.partitions
             .iter()
@@ -314,6 +863,9 @@ where
     }
 
     fn edges(&self) -> Vec<Edge<K, EV>> {
-        self.all_edges.clone()
+        self.partitions
+            .iter()
+            .flat_map(|p| p.out_edges.iter())
+            .collect()
     }
 }
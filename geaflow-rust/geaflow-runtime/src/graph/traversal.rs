@@ -0,0 +1,117 @@
+use crate::graph::csr::{Csr, LazyEdges};
+use geaflow_api::function::{
+    TraversalRequest, TraversalResponse, VertexCentricTraversalFuncContext,
+    VertexCentricTraversalFunction,
+};
+use geaflow_common::types::Edge;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/// Messages keyed by (request index, target vertex)
+pub type TraversalInbox<K, M> = HashMap<(usize, K), Vec<M>>;
+
+pub struct TraversalOutput<K, M, R> {
+    pub outbox: Vec<((usize, K), M)>,
+    pub responses: Vec<TraversalResponse<R>>,
+}
+
+struct TraversalContext<'a, K, VV, EV, M, R> {
+    request_index: usize,
+    request: &'a TraversalRequest<K>,
+    vertex_value: Option<&'a VV>,
+    edges: LazyEdges<'a, K, EV>,
+    iteration: u64,
+    output: &'a mut TraversalOutput<K, M, R>,
+}
+
+impl<'a, K, VV, EV, M, R> VertexCentricTraversalFuncContext<K, VV, EV, M, R>
+    for TraversalContext<'a, K, VV, EV, M, R>
+where
+    K: Clone + Eq + Hash,
+    EV: Clone,
+{
+    fn request(&self) -> &TraversalRequest<K> {
+        self.request
+    }
+
+    fn vertex_value(&self) -> Option<&VV> {
+        self.vertex_value
+    }
+
+    fn edges(&self) -> Box<dyn Iterator<Item = &Edge<K, EV>> + '_> {
+        Box::new(self.edges.get().iter())
+    }
+
+    fn iteration(&self) -> u64 {
+        self.iteration
+    }
+
+    fn send_message(&mut self, target_id: K, message: M) {
+        self.output
+            .outbox
+            .push(((self.request_index, target_id), message));
+    }
+
+    fn take_response(&mut self, response: R) {
+        self.output.responses.push(TraversalResponse {
+            request_id: self.request.request_id,
+            response,
+        });
+    }
+}
+
+/// Runs one traversal superstep over the vertices of a single partition. The first superstep
+/// initializes every request whose root lives here.
+pub fn traversal_superstep<K, VV, EV, M, R, S>(
+    func: &mut dyn VertexCentricTraversalFunction<K, VV, EV, M, R>,
+    vertices: &HashMap<K, VV, S>,
+    adjacency: &Csr<K, EV>,
+    requests: &[TraversalRequest<K>],
+    iteration: u64,
+    inbox: TraversalInbox<K, M>,
+) -> TraversalOutput<K, M, R>
+where
+    K: Clone + Hash + Eq + 'static,
+    VV: 'static,
+    EV: Clone + 'static,
+    M: 'static,
+    R: 'static,
+    S: BuildHasher,
+{
+    let mut output = TraversalOutput {
+        outbox: Vec::new(),
+        responses: Vec::new(),
+    };
+
+    if iteration == 1 {
+        for (request_index, request) in requests.iter().enumerate() {
+            let Some(value) = vertices.get(&request.root) else {
+                continue;
+            };
+            func.init(&mut TraversalContext {
+                request_index,
+                request,
+                vertex_value: Some(value),
+                edges: LazyEdges::new(adjacency, &request.root),
+                iteration,
+                output: &mut output,
+            });
+        }
+    }
+
+    for ((request_index, vertex_id), msgs) in inbox {
+        let Some(value) = vertices.get(&vertex_id) else {
+            continue;
+        };
+        let mut ctx = TraversalContext {
+            request_index,
+            request: &requests[request_index],
+            vertex_value: Some(value),
+            edges: LazyEdges::new(adjacency, &vertex_id),
+            iteration,
+            output: &mut output,
+        };
+        func.compute(&vertex_id, &mut msgs.into_iter(), &mut ctx);
+    }
+    output
+}
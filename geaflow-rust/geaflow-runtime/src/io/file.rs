@@ -1,4 +1,7 @@
+use crate::io::id_dictionary::IdDictionary;
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use geaflow_common::property::{Properties, PropertyValue};
+use geaflow_common::schema::{GraphSchema, LabelSchema};
 use geaflow_common::types::{Edge, Vertex};
 use std::path::Path;
 
@@ -126,3 +129,165 @@ pub fn read_edges_u64_u8(
     }
     Ok(out)
 }
+
+/// Reads `id[,value]` rows whose ids are arbitrary strings, interning them in `dict`; a missing
+/// value is `default_value` of the internal id.
+pub fn read_vertices_dict<VV>(
+    path: impl AsRef<Path>,
+    dict: &mut IdDictionary,
+    default_value: impl Fn(u64) -> VV,
+) -> GeaFlowResult<Vec<Vertex<u64, VV>>>
+where
+    VV: std::str::FromStr,
+    VV::Err: std::fmt::Display,
+{
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| GeaFlowError::Io(e.into()))?;
+
+    let mut out = Vec::new();
+    for rec in rdr.records() {
+        let rec = rec.map_err(|e| GeaFlowError::Internal(format!("csv read: {e}")))?;
+        let id = dict.intern(
+            rec.get(0)
+                .ok_or_else(|| GeaFlowError::InvalidArgument("vertex id missing".to_string()))?
+                .trim(),
+        );
+        let value = rec
+            .get(1)
+            .map(|s| s.trim().parse())
+            .transpose()
+            .map_err(|e| GeaFlowError::InvalidArgument(format!("vertex value parse: {e}")))?
+            .unwrap_or_else(|| default_value(id));
+        out.push(Vertex::new(id, value));
+    }
+    Ok(out)
+}
+
+/// Reads `src,target[,value]` rows with string ids, interning both endpoints in `dict`.
+pub fn read_edges_dict_u8(
+    path: impl AsRef<Path>,
+    dict: &mut IdDictionary,
+    default_value: u8,
+) -> GeaFlowResult<Vec<Edge<u64, u8>>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| GeaFlowError::Io(e.into()))?;
+
+    let mut out = Vec::new();
+    for rec in rdr.records() {
+        let rec = rec.map_err(|e| GeaFlowError::Internal(format!("csv read: {e}")))?;
+        let src = dict.intern(
+            rec.get(0)
+                .ok_or_else(|| GeaFlowError::InvalidArgument("edge src missing".to_string()))?
+                .trim(),
+        );
+        let target = dict.intern(
+            rec.get(1)
+                .ok_or_else(|| GeaFlowError::InvalidArgument("edge target missing".to_string()))?
+                .trim(),
+        );
+        let value: u8 = rec
+            .get(2)
+            .map(|s| s.trim().parse())
+            .transpose()
+            .map_err(|e| GeaFlowError::InvalidArgument(format!("edge value parse: {e}")))?
+            .unwrap_or(default_value);
+        out.push(Edge::new(src, target, value));
+    }
+    Ok(out)
+}
+
+/// Reads `id,label,<properties>` rows, properties in the label's schema order; an empty cell
+/// leaves that property unset.
+pub fn read_property_vertices(
+    path: impl AsRef<Path>,
+    schema: &GraphSchema,
+) -> GeaFlowResult<Vec<Vertex<u64, Properties>>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| GeaFlowError::Io(e.into()))?;
+
+    let mut out = Vec::new();
+    for rec in rdr.records() {
+        let rec = rec.map_err(|e| GeaFlowError::Internal(format!("csv read: {e}")))?;
+        let id: u64 = rec
+            .get(0)
+            .ok_or_else(|| GeaFlowError::InvalidArgument("vertex id missing".to_string()))?
+            .trim()
+            .parse()
+            .map_err(|e| GeaFlowError::InvalidArgument(format!("vertex id parse: {e}")))?;
+        let label = rec
+            .get(1)
+            .ok_or_else(|| GeaFlowError::InvalidArgument("vertex label missing".to_string()))?
+            .trim();
+        let label_schema = schema.vertex_label(label)?;
+        out.push(Vertex::new(id, read_properties(&rec, 2, label_schema)?));
+    }
+    Ok(out)
+}
+
+/// Reads `src,target,label,<properties>` rows, laid out like `read_property_vertices`.
+pub fn read_property_edges(
+    path: impl AsRef<Path>,
+    schema: &GraphSchema,
+) -> GeaFlowResult<Vec<Edge<u64, Properties>>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| GeaFlowError::Io(e.into()))?;
+
+    let mut out = Vec::new();
+    for rec in rdr.records() {
+        let rec = rec.map_err(|e| GeaFlowError::Internal(format!("csv read: {e}")))?;
+        let src: u64 = rec
+            .get(0)
+            .ok_or_else(|| GeaFlowError::InvalidArgument("edge src missing".to_string()))?
+            .trim()
+            .parse()
+            .map_err(|e| GeaFlowError::InvalidArgument(format!("edge src parse: {e}")))?;
+        let target: u64 = rec
+            .get(1)
+            .ok_or_else(|| GeaFlowError::InvalidArgument("edge target missing".to_string()))?
+            .trim()
+            .parse()
+            .map_err(|e| GeaFlowError::InvalidArgument(format!("edge target parse: {e}")))?;
+        let label = rec
+            .get(2)
+            .ok_or_else(|| GeaFlowError::InvalidArgument("edge label missing".to_string()))?
+            .trim();
+        let label_schema = schema.edge_label(label)?;
+        out.push(Edge::new(
+            src,
+            target,
+            read_properties(&rec, 3, label_schema)?,
+        ));
+    }
+    Ok(out)
+}
+
+fn read_properties(
+    rec: &csv::StringRecord,
+    first: usize,
+    label_schema: &LabelSchema,
+) -> GeaFlowResult<Properties> {
+    let mut props = Properties::new(label_schema.label.clone());
+    for (i, column) in label_schema.properties.iter().enumerate() {
+        let cell = rec.get(first + i).map(str::trim).unwrap_or("");
+        if !cell.is_empty() {
+            props.set(
+                column.name.clone(),
+                PropertyValue::parse(column.property_type, cell)?,
+            );
+        }
+    }
+    label_schema.validate(&props)?;
+    Ok(props)
+}
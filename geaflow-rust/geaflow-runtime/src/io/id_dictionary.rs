@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+/// Maps external vertex ids (account names, UUIDs, composite keys joined into one string) to
+/// dense internal `u64` ids, assigned from 0 in the order they are first seen.
+#[derive(Debug, Clone, Default)]
+pub struct IdDictionary {
+    ids: HashMap<String, u64>,
+    external: Vec<String>,
+}
+
+impl IdDictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The internal id of `external`, assigning the next one if it is new.
+    pub fn intern(&mut self, external: &str) -> u64 {
+        if let Some(&id) = self.ids.get(external) {
+            return id;
+        }
+        let id = self.external.len() as u64;
+        self.ids.insert(external.to_string(), id);
+        self.external.push(external.to_string());
+        id
+    }
+
+    pub fn get(&self, external: &str) -> Option<u64> {
+        self.ids.get(external).copied()
+    }
+
+    pub fn external(&self, id: u64) -> Option<&str> {
+        self.external.get(id as usize).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.external.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.external.is_empty()
+    }
+
+    /// `(internal, external)` pairs in internal id order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &str)> + '_ {
+        self.external
+            .iter()
+            .enumerate()
+            .map(|(id, external)| (id as u64, external.as_str()))
+    }
+}
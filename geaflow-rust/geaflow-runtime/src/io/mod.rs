@@ -1,2 +1,4 @@
 pub mod file;
+pub mod id_dictionary;
 pub mod socket;
+pub mod source;
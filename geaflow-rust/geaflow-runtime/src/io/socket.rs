@@ -1,3 +1,4 @@
+use crate::io::source::{forward_lines, LineSource};
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
 use std::net::SocketAddr;
 use tokio::io::{AsyncBufReadExt, BufReader};
@@ -29,3 +30,40 @@ pub async fn listen_lines(addr: SocketAddr, max_lines: usize) -> GeaFlowResult<V
     }
     Ok(lines)
 }
+
+/// Accepts any number of clients on `addr` and merges their lines into one unbounded source.
+pub async fn tcp_lines(addr: SocketAddr, capacity: usize) -> GeaFlowResult<LineSource> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| GeaFlowError::Internal(format!("bind socket source: {e}")))?;
+    let local_addr = listener
+        .local_addr()
+        .map_err(|e| GeaFlowError::Internal(format!("socket source addr: {e}")))?;
+    Ok(LineSource::spawn(
+        capacity,
+        Some(local_addr),
+        |tx| async move {
+            loop {
+                let (stream, _) = tokio::select! {
+                    _ = tx.closed() => return Ok(()),
+                    accepted = listener.accept() => accepted
+                        .map_err(|e| GeaFlowError::Internal(format!("accept socket source: {e}")))?,
+                };
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let mut partial = String::new();
+                    let mut reader = BufReader::new(stream);
+                    match forward_lines(&mut reader, &mut partial, &tx).await {
+                        Ok(true) if !partial.is_empty() => {
+                            let _ = tx.send(Ok(partial)).await;
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            let _ = tx.send(Err(e)).await;
+                        }
+                    }
+                });
+            }
+        },
+    ))
+}
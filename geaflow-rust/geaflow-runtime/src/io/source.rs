@@ -0,0 +1,312 @@
+use crate::stream::LocalStream;
+use futures::Stream;
+use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::io::{ErrorKind, SeekFrom};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::fs::File;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+pub type LineSender = mpsc::Sender<GeaFlowResult<String>>;
+
+/// An unbounded stream of lines. Producers wait once `capacity` lines are buffered, so a slow
+/// consumer throttles the source instead of growing memory. Dropping it stops the producer.
+pub struct LineSource {
+    rx: mpsc::Receiver<GeaFlowResult<String>>,
+    task: JoinHandle<()>,
+    local_addr: Option<SocketAddr>,
+}
+
+impl LineSource {
+    pub fn spawn<F, Fut>(capacity: usize, local_addr: Option<SocketAddr>, produce: F) -> Self
+    where
+        F: FnOnce(LineSender) -> Fut,
+        Fut: Future<Output = GeaFlowResult<()>> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        let errors = tx.clone();
+        let run = produce(tx);
+        let task = tokio::spawn(async move {
+            if let Err(e) = run.await {
+                let _ = errors.send(Err(e)).await;
+            }
+        });
+        Self {
+            rx,
+            task,
+            local_addr,
+        }
+    }
+
+    /// The address a socket source is listening on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Waits for at least one line, then takes whatever else is already buffered, up to
+    /// `max_lines`. `None` once the source has ended.
+    pub async fn next_batch(
+        &mut self,
+        max_lines: usize,
+    ) -> GeaFlowResult<Option<LocalStream<String>>> {
+        let Some(first) = self.rx.recv().await else {
+            return Ok(None);
+        };
+        let mut lines = vec![first?];
+        while lines.len() < max_lines {
+            match self.rx.try_recv() {
+                Ok(line) => lines.push(line?),
+                Err(_) => break,
+            }
+        }
+        Ok(Some(LocalStream::from_vec(lines)))
+    }
+}
+
+impl Stream for LineSource {
+    type Item = GeaFlowResult<String>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for LineSource {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Forwards complete lines until EOF; returns false once the consumer is gone. A trailing line
+/// without a newline stays in `partial`.
+pub async fn forward_lines<R>(
+    reader: &mut R,
+    partial: &mut String,
+    tx: &LineSender,
+) -> GeaFlowResult<bool>
+where
+    R: AsyncBufRead + Unpin,
+{
+    loop {
+        let n = tokio::select! {
+            _ = tx.closed() => return Ok(false),
+            n = reader.read_line(partial) => {
+                n.map_err(|e| GeaFlowError::Internal(format!("read line: {e}")))?
+            }
+        };
+        if n == 0 || !partial.ends_with('\n') {
+            return Ok(true);
+        }
+        let line = partial.trim_end_matches(&['\n', '\r'][..]).to_string();
+        partial.clear();
+        if tx.send(Ok(line)).await.is_err() {
+            return Ok(false);
+        }
+    }
+}
+
+/// Sleeps for `interval`; false if the consumer went away meanwhile.
+async fn wait(tx: &LineSender, interval: Duration) -> bool {
+    tokio::select! {
+        _ = tx.closed() => false,
+        _ = tokio::time::sleep(interval) => true,
+    }
+}
+
+#[cfg(unix)]
+fn file_identity(meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(_meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+struct TailedFile {
+    reader: BufReader<File>,
+    identity: Option<(u64, u64)>,
+    pos: u64,
+}
+
+impl TailedFile {
+    async fn open(path: &Path, from_end: bool) -> GeaFlowResult<Option<Self>> {
+        let file = match File::open(path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(GeaFlowError::Io(e)),
+        };
+        let meta = file.metadata().await.map_err(GeaFlowError::Io)?;
+        let mut reader = BufReader::new(file);
+        let pos = if from_end { meta.len() } else { 0 };
+        reader
+            .seek(SeekFrom::Start(pos))
+            .await
+            .map_err(GeaFlowError::Io)?;
+        Ok(Some(Self {
+            reader,
+            identity: file_identity(&meta),
+            pos,
+        }))
+    }
+
+    /// Whether `path` now names a different or truncated file.
+    async fn rotated(&self, path: &Path) -> GeaFlowResult<bool> {
+        match tokio::fs::metadata(path).await {
+            Ok(meta) => Ok(meta.len() < self.pos
+                || (self.identity.is_some() && file_identity(&meta) != self.identity)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(GeaFlowError::Io(e)),
+        }
+    }
+
+    async fn drain(&mut self, partial: &mut String, tx: &LineSender) -> GeaFlowResult<bool> {
+        let open = forward_lines(&mut self.reader, partial, tx).await?;
+        self.pos = self
+            .reader
+            .stream_position()
+            .await
+            .map_err(GeaFlowError::Io)?;
+        Ok(open)
+    }
+}
+
+/// Follows lines appended to `path`, like `tail -F`. When the file is rotated away or truncated
+/// the rest of the old file is read, then the new one from its start. `from_end` skips what
+/// the file already holds when the source starts.
+pub fn tail_file(
+    path: impl Into<PathBuf>,
+    from_end: bool,
+    poll_interval: Duration,
+    capacity: usize,
+) -> LineSource {
+    let path = path.into();
+    LineSource::spawn(capacity, None, move |tx| async move {
+        let mut current = TailedFile::open(&path, from_end).await?;
+        let mut partial = String::new();
+        loop {
+            if current.is_none() {
+                current = TailedFile::open(&path, false).await?;
+            }
+            if let Some(file) = current.as_mut() {
+                if !file.drain(&mut partial, &tx).await? {
+                    return Ok(());
+                }
+                if file.rotated(&path).await? {
+                    if !file.drain(&mut partial, &tx).await? {
+                        return Ok(());
+                    }
+                    if !partial.is_empty()
+                        && tx.send(Ok(std::mem::take(&mut partial))).await.is_err()
+                    {
+                        return Ok(());
+                    }
+                    current = None;
+                    continue;
+                }
+            }
+            if !wait(&tx, poll_interval).await {
+                return Ok(());
+            }
+        }
+    })
+}
+
+/// Emits the lines of every file that appears in `dir`, files in name order. A file is read
+/// once its size has held still for one poll, so writers get a chance to finish it.
+pub fn watch_dir(dir: impl Into<PathBuf>, poll_interval: Duration, capacity: usize) -> LineSource {
+    let dir = dir.into();
+    LineSource::spawn(capacity, None, move |tx| async move {
+        let mut done: HashSet<PathBuf> = HashSet::new();
+        let mut sizes: HashMap<PathBuf, u64> = HashMap::new();
+        loop {
+            let mut entries = tokio::fs::read_dir(&dir).await.map_err(GeaFlowError::Io)?;
+            let mut files = Vec::new();
+            while let Some(entry) = entries.next_entry().await.map_err(GeaFlowError::Io)? {
+                let meta = entry.metadata().await.map_err(GeaFlowError::Io)?;
+                if meta.is_file() && !done.contains(&entry.path()) {
+                    files.push((entry.path(), meta.len()));
+                }
+            }
+            files.sort();
+            for (path, len) in files {
+                if sizes.get(&path) != Some(&len) {
+                    sizes.insert(path, len);
+                    continue;
+                }
+                let file = File::open(&path).await.map_err(GeaFlowError::Io)?;
+                let mut partial = String::new();
+                if !forward_lines(&mut BufReader::new(file), &mut partial, &tx).await? {
+                    return Ok(());
+                }
+                if !partial.is_empty() && tx.send(Ok(partial)).await.is_err() {
+                    return Ok(());
+                }
+                sizes.remove(&path);
+                done.insert(path);
+            }
+            if !wait(&tx, poll_interval).await {
+                return Ok(());
+            }
+        }
+    })
+}
+
+/// Reads the complete lines appended to a file from a byte offset, so a checkpoint can record
+/// where to resume. Unlike `tail_file` it does not follow rotation.
+pub struct FileOffsetSource {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl FileOffsetSource {
+    pub fn new(path: impl Into<PathBuf>, offset: u64) -> Self {
+        Self {
+            path: path.into(),
+            offset,
+        }
+    }
+
+    /// The byte offset just past the last line read.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Up to `max_lines` lines past the offset; empty while nothing new has been written. A
+    /// trailing line without a newline is left for a later read.
+    pub async fn read_batch(&mut self, max_lines: usize) -> GeaFlowResult<Vec<String>> {
+        let file = match File::open(&self.path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(GeaFlowError::Io(e)),
+        };
+        let mut reader = BufReader::new(file);
+        reader
+            .seek(SeekFrom::Start(self.offset))
+            .await
+            .map_err(GeaFlowError::Io)?;
+        let mut lines = Vec::new();
+        let mut buf = String::new();
+        while lines.len() < max_lines {
+            buf.clear();
+            let n = reader
+                .read_line(&mut buf)
+                .await
+                .map_err(|e| GeaFlowError::Internal(format!("read line: {e}")))?;
+            if n == 0 || !buf.ends_with('\n') {
+                break;
+            }
+            self.offset += n as u64;
+            lines.push(buf.trim_end_matches(&['\n', '\r'][..]).to_string());
+        }
+        Ok(lines)
+    }
+}
@@ -19,6 +19,9 @@ impl ExecutionPlan {
             crate::plan::job_spec::AlgorithmSpec::PageRank { iterations, .. } => {
                 ("pagerank".to_string(), *iterations)
             }
+            crate::plan::job_spec::AlgorithmSpec::Sssp { iterations, .. } => {
+                ("sssp".to_string(), *iterations)
+            }
         };
         Self {
             job_id: job.job_id.clone(),
@@ -1,3 +1,4 @@
+use crate::algorithms::sssp::SsspWeights;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +9,8 @@ pub struct JobSpec {
     pub graph: GraphSpec,
     pub algorithm: AlgorithmSpec,
     pub checkpoint: CheckpointSpec,
+    #[serde(default)]
+    pub partitioner: PartitionerSpec,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +23,20 @@ pub enum JobMode {
 pub struct GraphSpec {
     pub vertices: FileSource,
     pub edges: FileSource,
+    #[serde(default)]
+    pub ids: IdSpec,
+}
+
+/// How vertex ids in the input files are read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum IdSpec {
+    /// Ids are `u64`s.
+    #[default]
+    U64,
+    /// Ids are arbitrary strings, mapped to dense `u64`s by an `IdDictionary` kept with the
+    /// graph state. Results come back under the external ids, and so do values that are
+    /// themselves ids, such as WCC component labels.
+    Dictionary,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,8 +46,26 @@ pub enum FileSource {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AlgorithmSpec {
-    Wcc { iterations: u64 },
-    PageRank { iterations: u64, alpha: f64 },
+    Wcc {
+        iterations: u64,
+    },
+    /// With a `tolerance` it stops once a superstep changes the ranks by less than that in
+    /// total.
+    PageRank {
+        iterations: u64,
+        alpha: f64,
+        #[serde(default)]
+        tolerance: Option<f64>,
+    },
+    /// `source` is the id as written in the vertex file, so it may be an external id under
+    /// `IdSpec::Dictionary`. With a `delta` it runs by delta-stepping with buckets that wide.
+    Sssp {
+        iterations: u64,
+        source: String,
+        weights: SsspWeights,
+        #[serde(default)]
+        delta: Option<f64>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,3 +74,16 @@ pub struct CheckpointSpec {
     pub interval_iters: u64,
     pub base_dir: String,
 }
+
+/// How vertex ids are assigned to workers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum PartitionerSpec {
+    #[default]
+    Hash,
+    Range {
+        upper_bounds: Vec<u64>,
+    },
+    Mapping {
+        path: String,
+    },
+}
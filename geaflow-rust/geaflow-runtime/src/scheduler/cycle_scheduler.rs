@@ -1,6 +1,7 @@
 use crate::distributed::driver::DistributedDriver;
 use crate::plan::job_spec::JobSpec;
 use crate::state::checkpoint_meta::CheckpointMeta;
+use geaflow_api::master::MasterStep;
 use geaflow_common::error::GeaFlowResult;
 use std::path::Path;
 
@@ -26,16 +27,19 @@ impl CycleScheduler {
         let max_iterations = match &job.algorithm {
             crate::plan::job_spec::AlgorithmSpec::Wcc { iterations } => *iterations,
             crate::plan::job_spec::AlgorithmSpec::PageRank { iterations, .. } => *iterations,
+            crate::plan::job_spec::AlgorithmSpec::Sssp { iterations, .. } => *iterations,
         };
 
         let mut state = State::Init;
         let mut inboxes = DistributedDriver::new_inboxes(driver.worker_count());
         let mut start_iteration: u64 = 1;
+        let mut messages_pending = true;
 
         if job.checkpoint.enabled && job.checkpoint.interval_iters > 0 {
             let latest_path = CheckpointMeta::latest_path(&job.checkpoint.base_dir, &job.job_id);
             if latest_path.exists() {
                 let meta = CheckpointMeta::read_json(&latest_path)?;
+                meta.check_partitioner(&driver.partitioner().id())?;
                 driver
                     .load_checkpoint_all(Path::new(&meta.checkpoint_dir))
                     .await?;
@@ -43,6 +47,9 @@ impl CycleScheduler {
                     .map_err(geaflow_common::error::GeaFlowError::Io)?;
                 inboxes = bincode::deserialize(&bytes)
                     .map_err(|e| geaflow_common::error::GeaFlowError::Internal(format!("{e}")))?;
+                driver.restore_aggregated(meta.aggregates);
+                driver.restore_master_state(meta.master);
+                driver.restore_active_vertices(meta.active_vertices);
                 start_iteration = meta.iteration + 1;
             }
         }
@@ -53,7 +60,9 @@ impl CycleScheduler {
                     iteration: start_iteration,
                 },
                 State::Running { iteration } => {
-                    if iteration > max_iterations {
+                    if iteration > max_iterations
+                        || driver.before_superstep(iteration, messages_pending) == MasterStep::Halt
+                    {
                         State::Finished {
                             executed: iteration - 1,
                         }
@@ -89,6 +98,12 @@ impl CycleScheduler {
                                 iteration,
                                 checkpoint_dir: checkpoint_dir.to_string_lossy().to_string(),
                                 inboxes_path: inboxes_path.to_string_lossy().to_string(),
+                                aggregates: driver.aggregated().clone(),
+                                master: driver.master_state().clone(),
+                                active_vertices: driver.active_vertices(),
+                                source_offsets: Default::default(),
+                                max_timestamp: None,
+                                partitioner: Some(driver.partitioner().id()),
                             };
                             meta.write_json(CheckpointMeta::meta_path(
                                 &job.checkpoint.base_dir,
@@ -100,14 +115,9 @@ impl CycleScheduler {
                                 &job.job_id,
                             ))?;
                         }
-                        if any_msg {
-                            State::Running {
-                                iteration: iteration + 1,
-                            }
-                        } else {
-                            State::Finished {
-                                executed: iteration,
-                            }
+                        messages_pending = any_msg;
+                        State::Running {
+                            iteration: iteration + 1,
                         }
                     }
                 }
@@ -1 +1,2 @@
 pub mod cycle_scheduler;
+pub mod stream_scheduler;
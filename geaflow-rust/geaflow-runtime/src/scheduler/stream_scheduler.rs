@@ -0,0 +1,168 @@
+use crate::distributed::driver::DistributedDriver;
+use crate::distributed::stream::{StreamRecord, WindowSink};
+use crate::plan::job_spec::CheckpointSpec;
+use crate::state::checkpoint_meta::CheckpointMeta;
+use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Source offsets by source name.
+pub type SourceOffsets = BTreeMap<String, u64>;
+
+/// Drives a distributed window aggregate batch by batch, taking a checkpoint barrier every
+/// `interval_iters` batches. Window state goes to the workers' checkpoints, source offsets and
+/// the driver's event time to `CheckpointMeta`, and sink output is committed per checkpoint, so
+/// replaying the sources from the restored offsets yields every result exactly once.
+///
+/// Barriers are sent in lockstep rather than flowing through an operator graph, by design: the
+/// distributed job is a single stage, so each worker's window operator has exactly one input,
+/// the driver's connection, and a barrier sent on it after a batch is already aligned. A
+/// multi-stage job would need per-input alignment like `LocalStream::union` does.
+pub struct StreamScheduler {
+    job_id: String,
+    checkpoint: CheckpointSpec,
+    batches: u64,
+    last_checkpoint: Option<u64>,
+}
+
+impl StreamScheduler {
+    pub fn new(job_id: impl Into<String>, checkpoint: CheckpointSpec) -> Self {
+        Self {
+            job_id: job_id.into(),
+            checkpoint,
+            batches: 0,
+            last_checkpoint: None,
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        self.checkpoint.enabled && self.checkpoint.interval_iters > 0
+    }
+
+    /// Restores the workers, the driver and `sink` from the latest checkpoint and returns the
+    /// offsets to resume the sources from; empty when there is none. Call it after
+    /// `set_window_aggregate`, which resets the driver's event time.
+    pub async fn recover(
+        &mut self,
+        driver: &mut DistributedDriver,
+        sink: &mut dyn WindowSink,
+    ) -> GeaFlowResult<SourceOffsets> {
+        let latest_path = CheckpointMeta::latest_path(&self.checkpoint.base_dir, &self.job_id);
+        if !self.enabled() || !latest_path.exists() {
+            sink.recover(None)?;
+            return Ok(SourceOffsets::new());
+        }
+        let meta = CheckpointMeta::read_json(&latest_path)?;
+        meta.check_partitioner(&driver.partitioner().id())?;
+        let checkpoint_id = meta.checkpoint_id.parse::<u64>().map_err(|e| {
+            GeaFlowError::Internal(format!("bad checkpoint id {}: {e}", meta.checkpoint_id))
+        })?;
+        driver
+            .load_checkpoint_all(Path::new(&meta.checkpoint_dir))
+            .await?;
+        driver.restore_stream_max_timestamp(meta.max_timestamp);
+        sink.recover(Some(checkpoint_id))?;
+        self.batches = meta.iteration;
+        self.last_checkpoint = Some(checkpoint_id);
+        // Workers now write into the loaded checkpoint itself; take a fresh one so a second
+        // failure still restores this state.
+        self.checkpoint(driver, &meta.source_offsets, sink).await?;
+        Ok(meta.source_offsets)
+    }
+
+    /// Pushes one batch and hands what fired to `sink`. `offsets` are the source positions
+    /// just past `records`.
+    pub async fn process(
+        &mut self,
+        driver: &mut DistributedDriver,
+        records: Vec<StreamRecord>,
+        offsets: &SourceOffsets,
+        sink: &mut dyn WindowSink,
+    ) -> GeaFlowResult<()> {
+        let results = driver.push_records(records).await?;
+        sink.write(&results)?;
+        self.batches += 1;
+        if !self.enabled() {
+            self.commit_unchecked(sink)
+        } else if self.batches.is_multiple_of(self.checkpoint.interval_iters) {
+            self.checkpoint(driver, offsets, sink).await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fires the windows still open and commits them with a final checkpoint.
+    pub async fn finish(
+        &mut self,
+        driver: &mut DistributedDriver,
+        offsets: &SourceOffsets,
+        sink: &mut dyn WindowSink,
+    ) -> GeaFlowResult<()> {
+        let results = driver.finish_stream().await?;
+        sink.write(&results)?;
+        if self.enabled() {
+            self.checkpoint(driver, offsets, sink).await
+        } else {
+            self.commit_unchecked(sink)
+        }
+    }
+
+    fn next_checkpoint_id(&self) -> u64 {
+        self.last_checkpoint.map_or(1, |id| id + 1)
+    }
+
+    /// Without checkpoints nothing is replayed, so output is published as it comes.
+    fn commit_unchecked(&mut self, sink: &mut dyn WindowSink) -> GeaFlowResult<()> {
+        let id = self.next_checkpoint_id();
+        sink.pre_commit(id)?;
+        sink.commit(id)?;
+        self.last_checkpoint = Some(id);
+        Ok(())
+    }
+
+    async fn checkpoint(
+        &mut self,
+        driver: &mut DistributedDriver,
+        offsets: &SourceOffsets,
+        sink: &mut dyn WindowSink,
+    ) -> GeaFlowResult<()> {
+        let checkpoint_id = self.next_checkpoint_id();
+        let checkpoint_dir: PathBuf = Path::new(&self.checkpoint.base_dir)
+            .join(&self.job_id)
+            .join(format!("stream_cp_{checkpoint_id}"));
+        // Left over from an attempt that failed before its metadata was written.
+        if checkpoint_dir.exists() {
+            std::fs::remove_dir_all(&checkpoint_dir).map_err(GeaFlowError::Io)?;
+        }
+        std::fs::create_dir_all(&checkpoint_dir).map_err(GeaFlowError::Io)?;
+        driver
+            .stream_barrier(checkpoint_id, &checkpoint_dir)
+            .await?;
+        sink.pre_commit(checkpoint_id)?;
+
+        let meta = CheckpointMeta {
+            checkpoint_id: checkpoint_id.to_string(),
+            iteration: self.batches,
+            checkpoint_dir: checkpoint_dir.to_string_lossy().to_string(),
+            inboxes_path: String::new(),
+            aggregates: Default::default(),
+            master: Default::default(),
+            active_vertices: 0,
+            source_offsets: offsets.clone(),
+            max_timestamp: driver.stream_max_timestamp(),
+            partitioner: Some(driver.partitioner().id()),
+        };
+        meta.write_json(CheckpointMeta::meta_path(
+            &self.checkpoint.base_dir,
+            &self.job_id,
+            &meta.checkpoint_id,
+        ))?;
+        meta.write_json(CheckpointMeta::latest_path(
+            &self.checkpoint.base_dir,
+            &self.job_id,
+        ))?;
+        sink.commit(checkpoint_id)?;
+        self.last_checkpoint = Some(checkpoint_id);
+        Ok(())
+    }
+}
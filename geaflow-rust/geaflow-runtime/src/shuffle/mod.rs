@@ -1,22 +1,81 @@
 pub mod partitioner;
+pub mod vertex_cut;
 
+use crate::shuffle::partitioner::Partitioner;
+use geaflow_common::error::GeaFlowResult;
 use std::collections::HashMap;
 
 pub type Outbox = Vec<(Vec<u8>, Vec<u8>)>;
 pub type Inbox = HashMap<Vec<u8>, Vec<Vec<u8>>>;
 pub type Inboxes = Vec<Inbox>;
 
+pub trait BytesCombiner: Send + Sync {
+    fn combine(&self, old_message: &[u8], new_message: &[u8]) -> GeaFlowResult<Vec<u8>>;
+}
+
 pub trait MessageShuffle: Send + Sync {
-    fn route_outbox(&self, outbox: Outbox, partitions: usize, next_inboxes: &mut Inboxes);
+    fn route_outbox(
+        &self,
+        outbox: Outbox,
+        partitions: usize,
+        next_inboxes: &mut Inboxes,
+    ) -> GeaFlowResult<()>;
+}
+
+#[derive(Default)]
+pub struct DriverShuffle<'a> {
+    pub combiner: Option<&'a dyn BytesCombiner>,
+    /// Hash partitioning when unset.
+    pub partitioner: Option<&'a dyn Partitioner>,
 }
 
-pub struct DriverShuffle;
+impl<'a> DriverShuffle<'a> {
+    pub fn new(combiner: Option<&'a dyn BytesCombiner>) -> Self {
+        Self {
+            combiner,
+            partitioner: None,
+        }
+    }
+
+    pub fn with_partitioner(mut self, partitioner: &'a dyn Partitioner) -> Self {
+        self.partitioner = Some(partitioner);
+        self
+    }
+}
 
-impl MessageShuffle for DriverShuffle {
-    fn route_outbox(&self, outbox: Outbox, partitions: usize, next_inboxes: &mut Inboxes) {
+impl<'a> MessageShuffle for DriverShuffle<'a> {
+    fn route_outbox(
+        &self,
+        outbox: Outbox,
+        partitions: usize,
+        next_inboxes: &mut Inboxes,
+    ) -> GeaFlowResult<()> {
         for (target, msg) in outbox {
-            let p = partitioner::partition_of_bytes(&target, partitions);
-            next_inboxes[p].entry(target).or_default().push(msg);
+            let p = match self.partitioner {
+                Some(partitioner) => partitioner.partition(&target, partitions),
+                None => partitioner::partition_of_bytes(&target, partitions),
+            };
+            let msgs = next_inboxes[p].entry(target).or_default();
+            match self.combiner {
+                Some(c) if !msgs.is_empty() => {
+                    let last = msgs.len() - 1;
+                    msgs[last] = c.combine(&msgs[last], &msg)?;
+                }
+                _ => msgs.push(msg),
+            }
         }
+        Ok(())
+    }
+}
+
+pub fn combine_outbox(outbox: Outbox, combiner: &dyn BytesCombiner) -> GeaFlowResult<Outbox> {
+    let mut combined: HashMap<Vec<u8>, Vec<u8>> = HashMap::with_capacity(outbox.len());
+    for (target, msg) in outbox {
+        let msg = match combined.remove(&target) {
+            Some(old) => combiner.combine(&old, &msg)?,
+            None => msg,
+        };
+        combined.insert(target, msg);
     }
+    Ok(combined.into_iter().collect())
 }
@@ -1,7 +1,124 @@
+use crate::plan::job_spec::PartitionerSpec;
+use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Assigns encoded keys to partitions. Worker state and checkpoints are laid out by it, so the
+/// assignment must not depend on the process or toolchain that computes it.
+pub trait Partitioner: Send + Sync {
+    /// Identifies the assignment: partitioners with equal ids route every key the same way.
+    fn id(&self) -> String;
+    fn partition(&self, key: &[u8], partitions: usize) -> usize;
+}
+
+/// xxh3 over the key bytes; the default.
+pub struct HashPartitioner;
+
+impl Partitioner for HashPartitioner {
+    fn id(&self) -> String {
+        "hash:xxh3".to_string()
+    }
+
+    fn partition(&self, key: &[u8], partitions: usize) -> usize {
+        (xxh3_64(key) % partitions.max(1) as u64) as usize
+    }
+}
+
+/// Keys are bincode `u64` ids; partition `i` takes ids below `upper_bounds[i]` and the last
+/// partition the rest. Other keys are hashed.
+pub struct RangePartitioner {
+    pub upper_bounds: Vec<u64>,
+}
+
+impl Partitioner for RangePartitioner {
+    fn id(&self) -> String {
+        let bounds: Vec<String> = self.upper_bounds.iter().map(|b| b.to_string()).collect();
+        format!("range:{}", bounds.join(","))
+    }
+
+    fn partition(&self, key: &[u8], partitions: usize) -> usize {
+        match decode_id(key) {
+            Some(id) => self
+                .upper_bounds
+                .partition_point(|&bound| bound <= id)
+                .min(partitions.max(1) - 1),
+            None => HashPartitioner.partition(key, partitions),
+        }
+    }
+}
+
+/// Explicit `id,partition` assignments read from a file; ids it does not list are hashed.
+pub struct MappingPartitioner {
+    mapping: HashMap<u64, usize>,
+    digest: u64,
+}
+
+impl MappingPartitioner {
+    /// One `id,partition` pair per line; blank lines and `#` comments are skipped.
+    pub fn from_file(path: impl AsRef<Path>) -> GeaFlowResult<Self> {
+        let text = std::fs::read_to_string(path.as_ref()).map_err(GeaFlowError::Io)?;
+        let mut mapping = HashMap::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parsed = line
+                .split_once(',')
+                .and_then(|(id, p)| Some((id.trim().parse().ok()?, p.trim().parse().ok()?)));
+            let Some((id, partition)) = parsed else {
+                return Err(GeaFlowError::InvalidArgument(format!(
+                    "{}:{}: expected id,partition",
+                    path.as_ref().display(),
+                    n + 1
+                )));
+            };
+            mapping.insert(id, partition);
+        }
+        Ok(Self {
+            mapping,
+            digest: xxh3_64(text.as_bytes()),
+        })
+    }
+}
+
+impl Partitioner for MappingPartitioner {
+    /// By content, so a moved file still matches and an edited one does not.
+    fn id(&self) -> String {
+        format!("mapping:{:016x}", self.digest)
+    }
+
+    fn partition(&self, key: &[u8], partitions: usize) -> usize {
+        match decode_id(key).and_then(|id| self.mapping.get(&id)) {
+            Some(&p) => p.min(partitions.max(1) - 1),
+            None => HashPartitioner.partition(key, partitions),
+        }
+    }
+}
+
+fn decode_id(key: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(key.try_into().ok()?))
+}
+
+pub fn create_partitioner(spec: &PartitionerSpec) -> GeaFlowResult<Arc<dyn Partitioner>> {
+    match spec {
+        PartitionerSpec::Hash => Ok(Arc::new(HashPartitioner)),
+        PartitionerSpec::Range { upper_bounds } => {
+            if upper_bounds.windows(2).any(|w| w[0] > w[1]) {
+                return Err(GeaFlowError::InvalidArgument(
+                    "range partitioner bounds must be ascending".to_string(),
+                ));
+            }
+            Ok(Arc::new(RangePartitioner {
+                upper_bounds: upper_bounds.clone(),
+            }))
+        }
+        PartitionerSpec::Mapping { path } => Ok(Arc::new(MappingPartitioner::from_file(path)?)),
+    }
+}
+
 pub fn partition_of_bytes(id: &[u8], partitions: usize) -> usize {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::Hasher;
-    let mut h = DefaultHasher::new();
-    h.write(id);
-    (h.finish() as usize) % partitions.max(1)
+    HashPartitioner.partition(id, partitions)
 }
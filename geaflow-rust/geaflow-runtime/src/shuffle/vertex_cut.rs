@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How edges are spread over partitions. Vertices always live on the partition their
+/// partitioner assigns, their master; under a vertex-cut, a partition holding some of a
+/// vertex's edges but not the vertex keeps a mirror of it instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum EdgePartitioning {
+    /// Each edge lives with its source vertex.
+    #[default]
+    EdgeCut,
+    /// Partitions form a `rows x cols` grid, as square as the partition count allows; an edge
+    /// goes to its source's row and its target's column, so a vertex is replicated on at most
+    /// `rows + cols - 1` partitions however high its degree.
+    Grid2D,
+    /// Greedy HDRF: each edge goes where its endpoints already have replicas, preferring to cut
+    /// the higher-degree one, traded against partition load by `lambda`.
+    Hdrf { lambda: f64 },
+}
+
+impl EdgePartitioning {
+    pub fn is_vertex_cut(&self) -> bool {
+        !matches!(self, EdgePartitioning::EdgeCut)
+    }
+}
+
+/// Places edges one at a time and, under a vertex-cut, records which partitions each vertex's
+/// edges landed on.
+pub struct EdgePlacer<K> {
+    partitioning: EdgePartitioning,
+    partitions: usize,
+    cols: usize,
+    /// Partitions holding edges of each vertex, ascending.
+    replicas: HashMap<K, Vec<usize>>,
+    out_degrees: HashMap<K, u64>,
+    /// In and out edges placed so far, for HDRF.
+    degrees: HashMap<K, u64>,
+    loads: Vec<u64>,
+}
+
+impl<K: Clone + Eq + Hash> EdgePlacer<K> {
+    pub fn new(partitioning: EdgePartitioning, partitions: usize) -> Self {
+        let partitions = partitions.max(1);
+        let rows = (1..=partitions)
+            .take_while(|r| r * r <= partitions)
+            .filter(|r| partitions.is_multiple_of(*r))
+            .last()
+            .unwrap_or(1);
+        Self {
+            partitioning,
+            partitions,
+            cols: partitions / rows,
+            replicas: HashMap::new(),
+            out_degrees: HashMap::new(),
+            degrees: HashMap::new(),
+            loads: vec![0; partitions],
+        }
+    }
+
+    pub fn partitioning(&self) -> EdgePartitioning {
+        self.partitioning
+    }
+
+    /// The partition storing `src -> target`, given the masters of both endpoints.
+    pub fn place(&mut self, src: &K, target: &K, src_master: usize, target_master: usize) -> usize {
+        let p = match self.partitioning {
+            EdgePartitioning::EdgeCut => return src_master,
+            EdgePartitioning::Grid2D => {
+                let rows = self.partitions / self.cols;
+                (src_master % rows) * self.cols + target_master % self.cols
+            }
+            EdgePartitioning::Hdrf { lambda } => self.hdrf(src, target, lambda),
+        };
+        for id in [src, target] {
+            let replicas = self.replicas.entry(id.clone()).or_default();
+            if let Err(i) = replicas.binary_search(&p) {
+                replicas.insert(i, p);
+            }
+        }
+        *self.out_degrees.entry(src.clone()).or_default() += 1;
+        self.loads[p] += 1;
+        p
+    }
+
+    fn hdrf(&mut self, src: &K, target: &K, lambda: f64) -> usize {
+        let mut degree = |id: &K| {
+            let d = self.degrees.entry(id.clone()).or_default();
+            *d += 1;
+            *d as f64
+        };
+        let (src_degree, target_degree) = (degree(src), degree(target));
+        let src_theta = src_degree / (src_degree + target_degree);
+        let max_load = self.loads.iter().copied().max().unwrap_or(0);
+        let min_load = self.loads.iter().copied().min().unwrap_or(0);
+
+        let replicated = |id: &K, p: usize| {
+            self.replicas
+                .get(id)
+                .is_some_and(|r| r.binary_search(&p).is_ok())
+        };
+        let mut best = (f64::MIN, 0);
+        for p in 0..self.partitions {
+            let mut score =
+                lambda * (max_load - self.loads[p]) as f64 / (1.0 + (max_load - min_load) as f64);
+            if replicated(src, p) {
+                score += 2.0 - src_theta;
+            }
+            if replicated(target, p) {
+                score += 1.0 + src_theta;
+            }
+            if score > best.0 {
+                best = (score, p);
+            }
+        }
+        best.1
+    }
+
+    /// Vertices with edges placed so far and the partitions holding them, ascending. Empty
+    /// under an edge-cut.
+    pub fn replicas(&self) -> impl Iterator<Item = (&K, &[usize])> + '_ {
+        self.replicas.iter().map(|(id, r)| (id, r.as_slice()))
+    }
+
+    /// Edges placed so far leaving `id`, under a vertex-cut.
+    pub fn out_degree(&self, id: &K) -> u64 {
+        self.out_degrees.get(id).copied().unwrap_or(0)
+    }
+}
@@ -1,5 +1,8 @@
+use geaflow_api::aggregator::AggregateValues;
+use geaflow_api::master::MasterState;
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +11,21 @@ pub struct CheckpointMeta {
     pub iteration: u64,
     pub checkpoint_dir: String,
     pub inboxes_path: String,
+    #[serde(default)]
+    pub aggregates: AggregateValues,
+    #[serde(default)]
+    pub master: MasterState,
+    /// Vertices that had not voted to halt when the checkpoint was taken.
+    #[serde(default)]
+    pub active_vertices: u64,
+    /// Where each stream source resumes after recovery, by source name.
+    #[serde(default)]
+    pub source_offsets: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub max_timestamp: Option<u64>,
+    /// `Partitioner::id` of the partitioner the worker state was laid out by.
+    #[serde(default)]
+    pub partitioner: Option<String>,
 }
 
 impl CheckpointMeta {
@@ -34,6 +52,21 @@ impl CheckpointMeta {
         std::fs::write(path, s).map_err(GeaFlowError::Io)
     }
 
+    /// Fails unless the checkpoint was taken under the partitioner `partitioner_id` names.
+    pub fn check_partitioner(&self, partitioner_id: &str) -> GeaFlowResult<()> {
+        match &self.partitioner {
+            Some(id) if id == partitioner_id => Ok(()),
+            Some(id) => Err(GeaFlowError::InvalidArgument(format!(
+                "checkpoint {} was partitioned by {id}, not {partitioner_id}",
+                self.checkpoint_id
+            ))),
+            None => Err(GeaFlowError::InvalidArgument(format!(
+                "checkpoint {} does not record its partitioner",
+                self.checkpoint_id
+            ))),
+        }
+    }
+
     pub fn read_json(path: impl AsRef<Path>) -> GeaFlowResult<Self> {
         let s = std::fs::read_to_string(path).map_err(GeaFlowError::Io)?;
         serde_json::from_str(&s).map_err(|e| GeaFlowError::Internal(format!("{e}")))
@@ -1,7 +1,10 @@
 pub mod checkpoint_meta;
 pub mod rocksdb_graph_state;
 
+use geaflow_api::window::TimeWindow;
 use geaflow_common::error::GeaFlowResult;
+use geaflow_common::property::Properties;
+use geaflow_common::schema::GraphSchema;
 use geaflow_common::types::{Edge, Vertex};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -15,6 +18,91 @@ pub trait GraphState<K, VV, EV>: Send + Sync {
 
     fn put_edge_batch(&self, edges: &[Edge<K, EV>]) -> GeaFlowResult<()>;
     fn get_out_edges(&self, src_id: &K) -> GeaFlowResult<Vec<Edge<K, EV>>>;
+
+    /// Indexes edges under `target_id` for `get_in_edges`; kept apart from the out-edges so a
+    /// partition can hold the in-edges of its own vertices.
+    fn put_in_edge_batch(&self, edges: &[Edge<K, EV>]) -> GeaFlowResult<()>;
+    fn get_in_edges(&self, target_id: &K) -> GeaFlowResult<Vec<Edge<K, EV>>>;
+
+    /// Removes the vertex together with its out-edges.
+    fn remove_vertex(&self, id: &K) -> GeaFlowResult<()>;
+    fn remove_edges(&self, src_id: &K, target_id: &K) -> GeaFlowResult<()>;
+    fn remove_in_edges(&self, src_id: &K, target_id: &K) -> GeaFlowResult<()>;
+
+    fn list_active_vertex_ids(&self) -> GeaFlowResult<Vec<K>>;
+    fn update_active_batch(&self, activated: &[K], halted: &[K]) -> GeaFlowResult<()>;
+}
+
+/// A property graph store; the `put_property_*` writes are checked against the stored schema.
+pub trait PropertyGraphState<K>: GraphState<K, Properties, Properties> {
+    fn put_schema(&self, schema: &GraphSchema) -> GeaFlowResult<()>;
+    fn get_schema(&self) -> GeaFlowResult<Option<GraphSchema>>;
+
+    fn put_property_vertex_batch(&self, vertices: &[Vertex<K, Properties>]) -> GeaFlowResult<()> {
+        if let Some(schema) = self.get_schema()? {
+            for v in vertices {
+                schema.validate_vertex(&v.value)?;
+            }
+        }
+        self.put_vertex_batch(vertices)
+    }
+
+    fn put_property_edge_batch(&self, edges: &[Edge<K, Properties>]) -> GeaFlowResult<()> {
+        if let Some(schema) = self.get_schema()? {
+            for e in edges {
+                schema.validate_edge(&e.value)?;
+            }
+        }
+        self.put_edge_batch(edges)
+    }
+
+    fn list_vertices_by_label(&self, label: &str) -> GeaFlowResult<Vec<Vertex<K, Properties>>> {
+        Ok(self
+            .list_vertices()?
+            .into_iter()
+            .filter(|v| v.value.label == label)
+            .collect())
+    }
+}
+
+/// One key's accumulator for one window of a stream operator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowEntry {
+    pub key: Vec<u8>,
+    pub window: TimeWindow,
+    /// Whether the window has fired; late updates within the allowed lateness fire it again.
+    pub fired: bool,
+    pub acc: Vec<u8>,
+}
+
+/// Keyed window state, ordered by window end so closed windows are a prefix scan.
+pub trait WindowState: Send + Sync {
+    fn get_window(&self, key: &[u8], window: &TimeWindow) -> GeaFlowResult<Option<WindowEntry>>;
+    fn put_window(&self, entry: &WindowEntry) -> GeaFlowResult<()>;
+    fn remove_window(&self, key: &[u8], window: &TimeWindow) -> GeaFlowResult<()>;
+    /// Windows ending at or before `end`, earliest end first.
+    fn windows_ending_by(&self, end: u64) -> GeaFlowResult<Vec<WindowEntry>>;
+    /// The watermark the window operator had reached at its last snapshot.
+    fn get_window_watermark(&self) -> GeaFlowResult<Option<u64>>;
+    fn put_window_watermark(&self, watermark: u64) -> GeaFlowResult<()>;
+}
+
+/// Vertex-cut replication on a worker: the global out-degree of each vertex whose edges are
+/// split across workers, and copies of those mastered elsewhere.
+pub trait ReplicaState: Send + Sync {
+    fn put_out_degree_batch(&self, degrees: &[(Vec<u8>, u64)]) -> GeaFlowResult<()>;
+    fn get_out_degree(&self, id: &[u8]) -> GeaFlowResult<Option<u64>>;
+    fn put_mirror_batch(&self, mirrors: &[(Vec<u8>, Vec<u8>)]) -> GeaFlowResult<()>;
+    fn get_mirror(&self, id: &[u8]) -> GeaFlowResult<Option<Vec<u8>>>;
+    fn list_mirror_ids(&self) -> GeaFlowResult<Vec<Vec<u8>>>;
+}
+
+/// External ids of vertices loaded through an `IdDictionary`, keyed by raw internal id, so
+/// results can be written back under the ids they were loaded with.
+pub trait ExternalIdState: Send + Sync {
+    fn put_external_id_batch(&self, ids: &[(Vec<u8>, String)]) -> GeaFlowResult<()>;
+    fn get_external_id(&self, id: &[u8]) -> GeaFlowResult<Option<String>>;
+    fn list_external_ids(&self) -> GeaFlowResult<Vec<(Vec<u8>, String)>>;
 }
 
 pub trait SerdeKey: Serialize + DeserializeOwned + Send + Sync + 'static {}
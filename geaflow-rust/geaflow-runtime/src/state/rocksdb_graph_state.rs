@@ -1,16 +1,34 @@
-use crate::state::{GraphState, SerdeKey, SerdeValue};
+use crate::state::{
+    ExternalIdState, GraphState, PropertyGraphState, ReplicaState, SerdeKey, SerdeValue,
+    WindowEntry, WindowState,
+};
+use geaflow_api::window::TimeWindow;
 use geaflow_common::error::{GeaFlowError, GeaFlowResult};
+use geaflow_common::schema::GraphSchema;
 use geaflow_common::types::{Edge, Vertex};
 use rocksdb::checkpoint::Checkpoint;
 use rocksdb::{ColumnFamilyDescriptor, IteratorMode, Options, WriteBatch, DB};
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 const CF_VERTICES: &str = "vertices";
 const CF_EDGES: &str = "edges";
+const CF_IN_EDGES: &str = "in_edges";
+const CF_ACTIVE: &str = "active";
+const CF_META: &str = "meta";
+const CF_WINDOWS: &str = "windows";
+const CF_OUT_DEGREES: &str = "out_degrees";
+const CF_MIRRORS: &str = "mirrors";
+const CF_EXTERNAL_IDS: &str = "external_ids";
+const SCHEMA_KEY: &[u8] = b"schema";
+const WINDOW_WATERMARK_KEY: &[u8] = b"window_watermark";
 static EDGE_BATCH_NONCE: AtomicU64 = AtomicU64::new(1);
 
+/// An edge with the raw key it is stored under.
+type KeyedEdge<K, EV> = (Box<[u8]>, Edge<K, EV>);
+
 #[derive(Clone)]
 pub struct RocksDbGraphState {
     db: Arc<DB>,
@@ -25,6 +43,13 @@ impl RocksDbGraphState {
         let cfs = vec![
             ColumnFamilyDescriptor::new(CF_VERTICES, Options::default()),
             ColumnFamilyDescriptor::new(CF_EDGES, Options::default()),
+            ColumnFamilyDescriptor::new(CF_IN_EDGES, Options::default()),
+            ColumnFamilyDescriptor::new(CF_ACTIVE, Options::default()),
+            ColumnFamilyDescriptor::new(CF_META, Options::default()),
+            ColumnFamilyDescriptor::new(CF_WINDOWS, Options::default()),
+            ColumnFamilyDescriptor::new(CF_OUT_DEGREES, Options::default()),
+            ColumnFamilyDescriptor::new(CF_MIRRORS, Options::default()),
+            ColumnFamilyDescriptor::new(CF_EXTERNAL_IDS, Options::default()),
         ];
 
         let db = DB::open_cf_descriptors(&opts, path, cfs)
@@ -55,7 +80,106 @@ impl RocksDbGraphState {
             .map_err(|e| GeaFlowError::Internal(format!("bincode decode: {e}")))
     }
 
-    pub fn dump_vertices_csv_u64_u64(&self, output_path: impl AsRef<Path>) -> GeaFlowResult<()> {
+    /// Writes edges into `cf_name` under the key `key_of` picks, after which they can be read
+    /// back by prefix.
+    fn put_edges_keyed<K, EV>(
+        &self,
+        cf_name: &str,
+        edges: &[Edge<K, EV>],
+        key_of: impl Fn(&Edge<K, EV>) -> &K,
+    ) -> GeaFlowResult<()>
+    where
+        K: serde::Serialize,
+        EV: serde::Serialize,
+    {
+        let cf = self.cf(cf_name)?;
+        let nonce = EDGE_BATCH_NONCE.fetch_add(1, Ordering::Relaxed);
+        let mut batch = WriteBatch::default();
+        for (i, e) in edges.iter().enumerate() {
+            let mut key = Self::encode(key_of(e))?;
+            key.extend_from_slice(&nonce.to_le_bytes());
+            key.extend_from_slice(&(i as u32).to_le_bytes());
+            batch.put_cf(cf, key, Self::encode(e)?);
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb write batch (edges): {e}")))?;
+        Ok(())
+    }
+
+    /// Edges stored in `cf_name` under `id`, with their raw keys.
+    fn edges_keyed<K, EV>(&self, cf_name: &str, id: &K) -> GeaFlowResult<Vec<KeyedEdge<K, EV>>>
+    where
+        K: serde::Serialize + serde::de::DeserializeOwned,
+        EV: serde::de::DeserializeOwned,
+    {
+        let cf = self.cf(cf_name)?;
+        let prefix = Self::encode(id)?;
+        let iter = self
+            .db
+            .iterator_cf(cf, IteratorMode::From(&prefix, rocksdb::Direction::Forward));
+        let mut out = Vec::new();
+        for kv in iter {
+            let (k, v) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
+            if !k.starts_with(&prefix) {
+                break;
+            }
+            out.push((k, Self::decode(&v)?));
+        }
+        Ok(out)
+    }
+
+    fn delete_keys(&self, cf_name: &str, keys: Vec<Box<[u8]>>) -> GeaFlowResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let cf = self.cf(cf_name)?;
+        let mut batch = WriteBatch::default();
+        for k in keys {
+            batch.delete_cf(cf, k);
+        }
+        self.db
+            .write(batch)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb remove edges: {e}")))?;
+        Ok(())
+    }
+
+    /// A `u64` id as written to a dump: its external id if it has one.
+    fn dump_id(&self, id_bytes: &[u8]) -> GeaFlowResult<String> {
+        if let Some(external) = self.get_external_id(id_bytes)? {
+            return Ok(external);
+        }
+        let id: u64 = bincode::deserialize(id_bytes)
+            .map_err(|e| GeaFlowError::Internal(format!("decode id: {e}")))?;
+        Ok(id.to_string())
+    }
+
+    /// External ids of the vertices whose value is their own id: the labels, when values label
+    /// vertices with another vertex's id.
+    pub fn list_label_external_ids(&self) -> GeaFlowResult<Vec<(Vec<u8>, String)>> {
+        let cf = self.cf(CF_VERTICES)?;
+        let mut out = Vec::new();
+        for kv in self.db.iterator_cf(cf, IteratorMode::Start) {
+            let (k, v) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
+            let id_bytes: Vec<u8> = Self::decode(&k)?;
+            let value_bytes: Vec<u8> = Self::decode(&v)?;
+            if id_bytes != value_bytes {
+                continue;
+            }
+            if let Some(external) = self.get_external_id(&id_bytes)? {
+                out.push((id_bytes, external));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Values found in `labels` are written as the label's external id.
+    pub fn dump_vertices_csv_u64_u64(
+        &self,
+        output_path: impl AsRef<Path>,
+        labels: &HashMap<Vec<u8>, String>,
+    ) -> GeaFlowResult<()> {
         let cf = self.cf(CF_VERTICES)?;
         let mut f = std::fs::File::create(output_path.as_ref()).map_err(GeaFlowError::Io)?;
         use std::io::Write;
@@ -64,10 +188,13 @@ impl RocksDbGraphState {
             let (k, v) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
             let id_bytes: Vec<u8> = Self::decode(&k)?;
             let value_bytes: Vec<u8> = Self::decode(&v)?;
-            let id: u64 = bincode::deserialize(&id_bytes)
-                .map_err(|e| GeaFlowError::Internal(format!("decode id: {e}")))?;
-            let value: u64 = bincode::deserialize(&value_bytes)
-                .map_err(|e| GeaFlowError::Internal(format!("decode value: {e}")))?;
+            let id = self.dump_id(&id_bytes)?;
+            let value = match labels.get(&value_bytes) {
+                Some(label) => label.clone(),
+                None => bincode::deserialize::<u64>(&value_bytes)
+                    .map_err(|e| GeaFlowError::Internal(format!("decode value: {e}")))?
+                    .to_string(),
+            };
             writeln!(&mut f, "{id},{value}")
                 .map_err(|e| GeaFlowError::Internal(format!("write csv: {e}")))?;
         }
@@ -83,8 +210,7 @@ impl RocksDbGraphState {
             let (k, v) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
             let id_bytes: Vec<u8> = Self::decode(&k)?;
             let value_bytes: Vec<u8> = Self::decode(&v)?;
-            let id: u64 = bincode::deserialize(&id_bytes)
-                .map_err(|e| GeaFlowError::Internal(format!("decode id: {e}")))?;
+            let id = self.dump_id(&id_bytes)?;
             let value: f64 = bincode::deserialize(&value_bytes)
                 .map_err(|e| GeaFlowError::Internal(format!("decode value: {e}")))?;
             writeln!(&mut f, "{id},{value}")
@@ -149,36 +275,306 @@ where
     }
 
     fn put_edge_batch(&self, edges: &[Edge<K, EV>]) -> GeaFlowResult<()> {
-        let cf = self.cf(CF_EDGES)?;
-        let nonce = EDGE_BATCH_NONCE.fetch_add(1, Ordering::Relaxed);
+        self.put_edges_keyed(CF_EDGES, edges, |e| &e.src_id)
+    }
+
+    fn get_out_edges(&self, src_id: &K) -> GeaFlowResult<Vec<Edge<K, EV>>> {
+        Ok(self
+            .edges_keyed(CF_EDGES, src_id)?
+            .into_iter()
+            .map(|(_, e)| e)
+            .collect())
+    }
+
+    fn put_in_edge_batch(&self, edges: &[Edge<K, EV>]) -> GeaFlowResult<()> {
+        self.put_edges_keyed(CF_IN_EDGES, edges, |e| &e.target_id)
+    }
+
+    fn get_in_edges(&self, target_id: &K) -> GeaFlowResult<Vec<Edge<K, EV>>> {
+        Ok(self
+            .edges_keyed(CF_IN_EDGES, target_id)?
+            .into_iter()
+            .map(|(_, e)| e)
+            .collect())
+    }
+
+    fn remove_vertex(&self, id: &K) -> GeaFlowResult<()> {
+        let key = Self::encode(id)?;
         let mut batch = WriteBatch::default();
-        for (i, e) in edges.iter().enumerate() {
-            let mut key = Self::encode(&e.src_id)?;
-            key.extend_from_slice(&nonce.to_le_bytes());
-            key.extend_from_slice(&(i as u32).to_le_bytes());
-            batch.put_cf(cf, key, Self::encode(e)?);
+        batch.delete_cf(self.cf(CF_VERTICES)?, &key);
+        batch.delete_cf(self.cf(CF_ACTIVE)?, &key);
+        let cf = self.cf(CF_EDGES)?;
+        let iter = self
+            .db
+            .iterator_cf(cf, IteratorMode::From(&key, rocksdb::Direction::Forward));
+        for kv in iter {
+            let (k, _) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
+            if !k.starts_with(&key) {
+                break;
+            }
+            batch.delete_cf(cf, k);
+        }
+        self.db
+            .write(batch)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb remove vertex: {e}")))?;
+        Ok(())
+    }
+
+    fn remove_edges(&self, src_id: &K, target_id: &K) -> GeaFlowResult<()> {
+        let keys = self
+            .edges_keyed::<K, EV>(CF_EDGES, src_id)?
+            .into_iter()
+            .filter(|(_, e)| &e.target_id == target_id)
+            .map(|(k, _)| k)
+            .collect();
+        self.delete_keys(CF_EDGES, keys)
+    }
+
+    fn remove_in_edges(&self, src_id: &K, target_id: &K) -> GeaFlowResult<()> {
+        let keys = self
+            .edges_keyed::<K, EV>(CF_IN_EDGES, target_id)?
+            .into_iter()
+            .filter(|(_, e)| &e.src_id == src_id)
+            .map(|(k, _)| k)
+            .collect();
+        self.delete_keys(CF_IN_EDGES, keys)
+    }
+
+    fn list_active_vertex_ids(&self) -> GeaFlowResult<Vec<K>> {
+        let cf = self.cf(CF_ACTIVE)?;
+        let mut out = Vec::new();
+        let iter = self.db.iterator_cf(cf, IteratorMode::Start);
+        for kv in iter {
+            let (k, _) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
+            out.push(Self::decode(&k)?);
         }
+        Ok(out)
+    }
 
+    fn update_active_batch(&self, activated: &[K], halted: &[K]) -> GeaFlowResult<()> {
+        if activated.is_empty() && halted.is_empty() {
+            return Ok(());
+        }
+        let cf = self.cf(CF_ACTIVE)?;
+        let mut batch = WriteBatch::default();
+        for id in activated {
+            batch.put_cf(cf, Self::encode(id)?, b"");
+        }
+        for id in halted {
+            batch.delete_cf(cf, Self::encode(id)?);
+        }
         self.db
             .write(batch)
-            .map_err(|e| GeaFlowError::Internal(format!("rocksdb write batch (edges): {e}")))?;
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb write batch (active): {e}")))?;
         Ok(())
     }
+}
 
-    fn get_out_edges(&self, src_id: &K) -> GeaFlowResult<Vec<Edge<K, EV>>> {
-        let cf = self.cf(CF_EDGES)?;
-        let prefix = Self::encode(src_id)?;
-        let iter = self
+impl<K> PropertyGraphState<K> for RocksDbGraphState
+where
+    K: SerdeKey + Clone + Eq + std::hash::Hash,
+{
+    fn put_schema(&self, schema: &GraphSchema) -> GeaFlowResult<()> {
+        self.db
+            .put_cf(self.cf(CF_META)?, SCHEMA_KEY, Self::encode(schema)?)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb put schema: {e}")))
+    }
+
+    fn get_schema(&self) -> GeaFlowResult<Option<GraphSchema>> {
+        let bytes = self
             .db
-            .iterator_cf(cf, IteratorMode::From(&prefix, rocksdb::Direction::Forward));
+            .get_cf(self.cf(CF_META)?, SCHEMA_KEY)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb get schema: {e}")))?;
+        bytes.map(|b| Self::decode(&b)).transpose()
+    }
+}
+
+/// Big-endian end, then start, then the key, so a forward scan visits windows by end.
+fn window_key(key: &[u8], window: &TimeWindow) -> Vec<u8> {
+    let mut out = Vec::with_capacity(16 + key.len());
+    out.extend_from_slice(&window.end.to_be_bytes());
+    out.extend_from_slice(&window.start.to_be_bytes());
+    out.extend_from_slice(key);
+    out
+}
+
+fn parse_window_key(raw: &[u8]) -> GeaFlowResult<(Vec<u8>, TimeWindow)> {
+    if raw.len() < 16 {
+        return Err(GeaFlowError::Internal("short window key".to_string()));
+    }
+    let (end, rest) = raw.split_at(8);
+    let (start, key) = rest.split_at(8);
+    let word = |b: &[u8]| u64::from_be_bytes(b.try_into().unwrap_or_default());
+    Ok((key.to_vec(), TimeWindow::new(word(start), word(end))))
+}
+
+impl WindowState for RocksDbGraphState {
+    fn get_window(&self, key: &[u8], window: &TimeWindow) -> GeaFlowResult<Option<WindowEntry>> {
+        let value = self
+            .db
+            .get_cf(self.cf(CF_WINDOWS)?, window_key(key, window))
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb get window: {e}")))?;
+        value
+            .map(|bytes| {
+                let (fired, acc) = Self::decode(&bytes)?;
+                Ok(WindowEntry {
+                    key: key.to_vec(),
+                    window: *window,
+                    fired,
+                    acc,
+                })
+            })
+            .transpose()
+    }
+
+    fn put_window(&self, entry: &WindowEntry) -> GeaFlowResult<()> {
+        self.db
+            .put_cf(
+                self.cf(CF_WINDOWS)?,
+                window_key(&entry.key, &entry.window),
+                Self::encode(&(entry.fired, &entry.acc))?,
+            )
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb put window: {e}")))
+    }
+
+    fn remove_window(&self, key: &[u8], window: &TimeWindow) -> GeaFlowResult<()> {
+        self.db
+            .delete_cf(self.cf(CF_WINDOWS)?, window_key(key, window))
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb remove window: {e}")))
+    }
+
+    fn windows_ending_by(&self, end: u64) -> GeaFlowResult<Vec<WindowEntry>> {
+        let cf = self.cf(CF_WINDOWS)?;
         let mut out = Vec::new();
-        for kv in iter {
+        for kv in self.db.iterator_cf(cf, IteratorMode::Start) {
             let (k, v) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
-            if !k.starts_with(&prefix) {
+            let (key, window) = parse_window_key(&k)?;
+            if window.end > end {
                 break;
             }
-            let e: Edge<K, EV> = Self::decode(&v)?;
-            out.push(e);
+            let (fired, acc) = Self::decode(&v)?;
+            out.push(WindowEntry {
+                key,
+                window,
+                fired,
+                acc,
+            });
+        }
+        Ok(out)
+    }
+
+    fn get_window_watermark(&self) -> GeaFlowResult<Option<u64>> {
+        let bytes = self
+            .db
+            .get_cf(self.cf(CF_META)?, WINDOW_WATERMARK_KEY)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb get watermark: {e}")))?;
+        bytes.map(|b| Self::decode(&b)).transpose()
+    }
+
+    fn put_window_watermark(&self, watermark: u64) -> GeaFlowResult<()> {
+        self.db
+            .put_cf(
+                self.cf(CF_META)?,
+                WINDOW_WATERMARK_KEY,
+                Self::encode(&watermark)?,
+            )
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb put watermark: {e}")))
+    }
+}
+
+/// Keyed by the raw vertex id, like the window state.
+impl ReplicaState for RocksDbGraphState {
+    fn put_out_degree_batch(&self, degrees: &[(Vec<u8>, u64)]) -> GeaFlowResult<()> {
+        let cf = self.cf(CF_OUT_DEGREES)?;
+        let mut batch = WriteBatch::default();
+        for (id, degree) in degrees {
+            batch.put_cf(cf, id, degree.to_le_bytes());
+        }
+        self.db
+            .write(batch)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb write batch (degrees): {e}")))
+    }
+
+    fn get_out_degree(&self, id: &[u8]) -> GeaFlowResult<Option<u64>> {
+        let bytes = self
+            .db
+            .get_cf(self.cf(CF_OUT_DEGREES)?, id)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb get degree: {e}")))?;
+        bytes
+            .map(|b| {
+                let word = b[..]
+                    .try_into()
+                    .map_err(|_| GeaFlowError::Internal("bad out-degree".to_string()))?;
+                Ok(u64::from_le_bytes(word))
+            })
+            .transpose()
+    }
+
+    fn put_mirror_batch(&self, mirrors: &[(Vec<u8>, Vec<u8>)]) -> GeaFlowResult<()> {
+        let cf = self.cf(CF_MIRRORS)?;
+        let mut batch = WriteBatch::default();
+        for (id, value) in mirrors {
+            batch.put_cf(cf, id, value);
+        }
+        self.db
+            .write(batch)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb write batch (mirrors): {e}")))
+    }
+
+    fn get_mirror(&self, id: &[u8]) -> GeaFlowResult<Option<Vec<u8>>> {
+        self.db
+            .get_cf(self.cf(CF_MIRRORS)?, id)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb get mirror: {e}")))
+    }
+
+    fn list_mirror_ids(&self) -> GeaFlowResult<Vec<Vec<u8>>> {
+        let mut out = Vec::new();
+        for kv in self
+            .db
+            .iterator_cf(self.cf(CF_MIRRORS)?, IteratorMode::Start)
+        {
+            let (k, _) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
+            out.push(k.to_vec());
+        }
+        Ok(out)
+    }
+}
+
+impl ExternalIdState for RocksDbGraphState {
+    fn put_external_id_batch(&self, ids: &[(Vec<u8>, String)]) -> GeaFlowResult<()> {
+        let cf = self.cf(CF_EXTERNAL_IDS)?;
+        let mut batch = WriteBatch::default();
+        for (id, external) in ids {
+            batch.put_cf(cf, id, external.as_bytes());
+        }
+        self.db
+            .write(batch)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb write batch (ids): {e}")))
+    }
+
+    fn get_external_id(&self, id: &[u8]) -> GeaFlowResult<Option<String>> {
+        let bytes = self
+            .db
+            .get_cf(self.cf(CF_EXTERNAL_IDS)?, id)
+            .map_err(|e| GeaFlowError::Internal(format!("rocksdb get external id: {e}")))?;
+        bytes
+            .map(|b| {
+                String::from_utf8(b)
+                    .map_err(|e| GeaFlowError::Internal(format!("bad external id: {e}")))
+            })
+            .transpose()
+    }
+
+    fn list_external_ids(&self) -> GeaFlowResult<Vec<(Vec<u8>, String)>> {
+        let mut out = Vec::new();
+        for kv in self
+            .db
+            .iterator_cf(self.cf(CF_EXTERNAL_IDS)?, IteratorMode::Start)
+        {
+            let (k, v) = kv.map_err(|e| GeaFlowError::Internal(format!("rocksdb iter: {e}")))?;
+            let external = String::from_utf8(v.to_vec())
+                .map_err(|e| GeaFlowError::Internal(format!("bad external id: {e}")))?;
+            out.push((k.to_vec(), external));
         }
         Ok(out)
     }
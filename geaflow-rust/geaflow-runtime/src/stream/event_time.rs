@@ -0,0 +1,182 @@
+use geaflow_api::function::TimestampExtractor;
+use geaflow_api::window::{EventTimeWindow, TimeWindow, WatermarkStrategy, WindowType};
+
+/// A stream whose records carry event timestamps, in arrival order.
+#[derive(Debug, Clone)]
+pub struct LocalTimedStream<T> {
+    data: Vec<(u64, T)>,
+    watermarks: WatermarkStrategy,
+}
+
+impl<T> LocalTimedStream<T> {
+    pub fn new<F>(data: Vec<T>, extractor: F, watermarks: WatermarkStrategy) -> Self
+    where
+        F: TimestampExtractor<T>,
+    {
+        let data = data
+            .into_iter()
+            .map(|v| (extractor.extract_timestamp(&v), v))
+            .collect();
+        Self { data, watermarks }
+    }
+
+    pub fn window<W: EventTimeWindow>(self, window: W) -> LocalEventWindowedStream<T, W> {
+        LocalEventWindowedStream {
+            data: self.data,
+            watermarks: self.watermarks,
+            window,
+            allowed_lateness: 0,
+        }
+    }
+}
+
+pub struct LocalEventWindowedStream<T, W> {
+    data: Vec<(u64, T)>,
+    watermarks: WatermarkStrategy,
+    window: W,
+    allowed_lateness: u64,
+}
+
+/// Fired windows in firing order plus the records that arrived too late for any window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowOutput<T> {
+    pub windows: Vec<(TimeWindow, Vec<T>)>,
+    pub late: Vec<T>,
+}
+
+impl<T> WindowOutput<T> {
+    pub fn map<R>(self, func: impl Fn(T) -> R) -> WindowOutput<R> {
+        WindowOutput {
+            windows: self
+                .windows
+                .into_iter()
+                .map(|(w, records)| (w, records.into_iter().map(&func).collect()))
+                .collect(),
+            late: self.late.into_iter().map(func).collect(),
+        }
+    }
+}
+
+struct PendingWindow<T> {
+    window: TimeWindow,
+    records: Vec<(u64, T)>,
+    fired: bool,
+}
+
+impl<T: Clone> PendingWindow<T> {
+    fn contents(&self) -> (TimeWindow, Vec<T>) {
+        let mut records = self.records.clone();
+        records.sort_by_key(|(ts, _)| *ts);
+        (self.window, records.into_iter().map(|(_, v)| v).collect())
+    }
+}
+
+impl<T: Clone, W: EventTimeWindow> LocalEventWindowedStream<T, W> {
+    /// Keeps a closed window open for records up to `lateness` behind the watermark; each such
+    /// record fires the window again with its full contents.
+    pub fn allowed_lateness(mut self, lateness: u64) -> Self {
+        self.allowed_lateness = lateness;
+        self
+    }
+
+    /// Replays the records in arrival order, advancing the watermark after each one; windows
+    /// still open at the end of input fire in order of their end time.
+    pub fn collect_windows(self) -> WindowOutput<T> {
+        let merging = matches!(self.window.get_type(), WindowType::Session);
+        let lateness = self.allowed_lateness;
+        let mut pending: Vec<PendingWindow<T>> = Vec::new();
+        let mut out = WindowOutput {
+            windows: Vec::new(),
+            late: Vec::new(),
+        };
+        let mut watermark: Option<u64> = None;
+        let mut max_ts = 0u64;
+
+        for (ts, value) in self.data {
+            let closed = |w: &TimeWindow| watermark.is_some_and(|wm| w.end + lateness <= wm);
+            let assigned: Vec<TimeWindow> = self
+                .window
+                .assign_windows(ts)
+                .into_iter()
+                .filter(|w| !closed(w))
+                .collect();
+            if assigned.is_empty() {
+                out.late.push(value);
+                continue;
+            }
+
+            for window in assigned {
+                let slot = if merging {
+                    merge_into(&mut pending, window)
+                } else {
+                    match pending.iter().position(|p| p.window == window) {
+                        Some(i) => i,
+                        None => {
+                            pending.push(PendingWindow {
+                                window,
+                                records: Vec::new(),
+                                fired: false,
+                            });
+                            pending.len() - 1
+                        }
+                    }
+                };
+                let p = &mut pending[slot];
+                p.records.push((ts, value.clone()));
+                if p.fired || watermark.is_some_and(|wm| p.window.end <= wm) {
+                    p.fired = true;
+                    out.windows.push(p.contents());
+                }
+            }
+
+            max_ts = max_ts.max(ts);
+            let wm = self.watermarks.watermark(max_ts);
+            watermark = Some(wm);
+            fire(&mut pending, &mut out, |w| w.end <= wm);
+            pending.retain(|p| p.window.end + lateness > wm);
+        }
+
+        fire(&mut pending, &mut out, |_| true);
+        out
+    }
+}
+
+/// Fires every unfired window matching `ready`, earliest end first.
+fn fire<T: Clone>(
+    pending: &mut [PendingWindow<T>],
+    out: &mut WindowOutput<T>,
+    ready: impl Fn(&TimeWindow) -> bool,
+) {
+    let mut ready: Vec<&mut PendingWindow<T>> = pending
+        .iter_mut()
+        .filter(|p| !p.fired && ready(&p.window))
+        .collect();
+    ready.sort_by_key(|p| (p.window.end, p.window.start));
+    for p in ready {
+        p.fired = true;
+        out.windows.push(p.contents());
+    }
+}
+
+/// Folds every pending session that intersects `window` into one and returns its index.
+fn merge_into<T>(pending: &mut Vec<PendingWindow<T>>, window: TimeWindow) -> usize {
+    let mut merged = PendingWindow {
+        window,
+        records: Vec::new(),
+        fired: false,
+    };
+    let mut i = 0;
+    while i < pending.len() {
+        if pending[i].window.intersects(&merged.window) {
+            let p = pending.swap_remove(i);
+            merged.window = merged.window.cover(&p.window);
+            merged.records.extend(p.records);
+            merged.fired |= p.fired;
+            i = 0;
+        } else {
+            i += 1;
+        }
+    }
+    pending.push(merged);
+    pending.len() - 1
+}
@@ -0,0 +1,202 @@
+use crate::graph::csr::{Csr, PARTITION_TOO_LARGE};
+use crate::graph::mutation::apply_delta;
+use crate::graph::partitioned_graph::PartitionedGraph;
+use crate::stream::event_time::WindowOutput;
+use crate::stream::{LocalStream, LocalWindowedStream};
+use geaflow_api::function::VertexCentricComputeAlgorithm;
+use geaflow_api::graph::PGraphWindow;
+use geaflow_api::mutation::GraphMutation;
+use geaflow_api::window::TimeWindow;
+use geaflow_common::types::Vertex;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// The graph updates one window collected.
+pub type GraphUpdates<K, VV, EV> = Vec<GraphMutation<K, VV, EV>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotMode {
+    /// Every window builds a graph from its own updates and computes it from scratch.
+    Fresh,
+    /// Every window applies its updates to the previous snapshot and computes incrementally;
+    /// meant for tumbling or session windows, where each update lands in one window.
+    Accumulate,
+}
+
+/// The graph a closed window produced, after the algorithm ran on it.
+pub struct GraphSnapshot<W, K, VV, EV> {
+    pub window: W,
+    pub graph: PartitionedGraph<K, VV, EV>,
+}
+
+/// Closed windows of graph updates, turned into one graph snapshot each.
+pub struct LocalGraphStream<W, K, VV, EV> {
+    windows: Vec<(W, GraphUpdates<K, VV, EV>)>,
+    mode: SnapshotMode,
+    partitions: usize,
+}
+
+impl<W, K, VV, EV> LocalGraphStream<W, K, VV, EV>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    VV: Clone + Send + Sync + 'static,
+    EV: Clone + Send + Sync + 'static,
+{
+    pub fn new(
+        windows: Vec<(W, GraphUpdates<K, VV, EV>)>,
+        mode: SnapshotMode,
+        partitions: usize,
+    ) -> Self {
+        Self {
+            windows,
+            mode,
+            partitions: partitions.max(1),
+        }
+    }
+
+    /// Runs `algorithm` on each window's snapshot. Edge endpoints the updates never add are
+    /// created with `vertex_init`; a window's updates apply in barrier order.
+    pub fn compute_algorithm<M, A, F>(
+        self,
+        algorithm: &A,
+        vertex_init: F,
+    ) -> LocalStream<GraphSnapshot<W, K, VV, EV>>
+    where
+        A: VertexCentricComputeAlgorithm<K, VV, EV, M>,
+        M: Send + Sync + 'static + Clone,
+        F: Fn(&K) -> VV,
+    {
+        let mut out = Vec::new();
+        let mut previous: Option<PartitionedGraph<K, VV, EV>> = None;
+        for (window, mut updates) in self.windows {
+            add_endpoints(&mut updates, &vertex_init);
+            let graph = match (self.mode, previous.take()) {
+                (SnapshotMode::Accumulate, Some(graph)) => {
+                    graph.compute_incremental(algorithm, updates, self.partitions)
+                }
+                _ => build_graph(updates, self.partitions)
+                    .compute_algorithm(algorithm, self.partitions),
+            };
+            if self.mode == SnapshotMode::Accumulate {
+                previous = Some(graph.clone());
+            }
+            out.push(GraphSnapshot { window, graph });
+        }
+        LocalStream::from_vec(out)
+    }
+}
+
+/// Adds an `AddVertex` for every edge endpoint the window does not remove; existing vertices
+/// keep their value.
+fn add_endpoints<K, VV, EV>(updates: &mut Vec<GraphMutation<K, VV, EV>>, init: impl Fn(&K) -> VV)
+where
+    K: Clone + Eq + Hash,
+{
+    let removed: HashSet<K> = updates
+        .iter()
+        .filter_map(|m| match m {
+            GraphMutation::RemoveVertex(id) => Some(id.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut seen = HashSet::new();
+    let mut endpoints = Vec::new();
+    for m in updates.iter() {
+        if let GraphMutation::AddEdge(e) = m {
+            for id in [&e.src_id, &e.target_id] {
+                if !removed.contains(id) && seen.insert(id.clone()) {
+                    endpoints.push(GraphMutation::AddVertex(Vertex::new(id.clone(), init(id))));
+                }
+            }
+        }
+    }
+    updates.append(&mut endpoints);
+}
+
+fn build_graph<K, VV, EV>(
+    updates: Vec<GraphMutation<K, VV, EV>>,
+    partitions: usize,
+) -> PartitionedGraph<K, VV, EV>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    VV: Clone + Send + Sync + 'static,
+    EV: Clone + Send + Sync + 'static,
+{
+    let mut vertices = HashMap::new();
+    let mut out_edges = Csr::default();
+    apply_delta(&mut vertices, &mut out_edges, updates).expect(PARTITION_TOO_LARGE);
+    PartitionedGraph::new(
+        vertices
+            .into_iter()
+            .map(|(id, value)| Vertex::new(id, value))
+            .collect(),
+        out_edges.iter().collect(),
+        partitions,
+    )
+}
+
+impl<K, VV, EV> LocalWindowedStream<GraphMutation<K, VV, EV>>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    VV: Clone + Send + Sync + 'static,
+    EV: Clone + Send + Sync + 'static,
+{
+    /// Windows are numbered from 0 in arrival order.
+    pub fn into_graph(
+        self,
+        mode: SnapshotMode,
+        partitions: usize,
+    ) -> LocalGraphStream<usize, K, VV, EV> {
+        let windows = self.collect_windows().into_iter().enumerate().collect();
+        LocalGraphStream::new(windows, mode, partitions)
+    }
+}
+
+impl<K, VV, EV> WindowOutput<GraphMutation<K, VV, EV>>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    VV: Clone + PartialEq + Send + Sync + 'static,
+    EV: Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Late records are left out. Under `Accumulate`, a window that fires again only carries
+    /// the updates its earlier firings missed.
+    pub fn into_graph(
+        self,
+        mode: SnapshotMode,
+        partitions: usize,
+    ) -> LocalGraphStream<TimeWindow, K, VV, EV> {
+        let mut fired: Vec<(TimeWindow, GraphUpdates<K, VV, EV>)> = Vec::new();
+        let mut windows = Vec::new();
+        for (window, records) in self.windows {
+            // A session that merged after firing covers the earlier firings it absorbed.
+            let mut earlier: GraphUpdates<K, VV, EV> = Vec::new();
+            fired.retain_mut(|(w, r)| {
+                let overlaps = w.intersects(&window);
+                if overlaps {
+                    earlier.append(r);
+                }
+                !overlaps
+            });
+            let updates = match mode {
+                SnapshotMode::Accumulate => {
+                    let mut earlier = earlier;
+                    records
+                        .iter()
+                        .filter(|r| match earlier.iter().position(|e| e == *r) {
+                            Some(i) => {
+                                earlier.swap_remove(i);
+                                false
+                            }
+                            None => true,
+                        })
+                        .cloned()
+                        .collect()
+                }
+                SnapshotMode::Fresh => records.clone(),
+            };
+            fired.push((window, records));
+            windows.push((window, updates));
+        }
+        LocalGraphStream::new(windows, mode, partitions)
+    }
+}
@@ -1,15 +1,76 @@
-use geaflow_api::function::{FilterFunction, MapFunction};
-use geaflow_api::stream::PStream;
-use geaflow_api::window::SizeTumblingWindow;
+pub mod event_time;
+pub mod graph_stream;
 
+use event_time::LocalTimedStream;
+use geaflow_api::function::{
+    AggregateFunction, FilterFunction, FlatMapFunction, JoinFunction, KeySelector, MapFunction,
+    ReduceFunction, TimestampExtractor,
+};
+use geaflow_api::stream::{PKeyedStream, PStream};
+use geaflow_api::window::{EventTimeWindow, SizeTumblingWindow, TimeWindow, WatermarkStrategy};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// A checkpoint barrier between records: the first `offset` records of the stream come before
+/// it and belong to checkpoint `checkpoint_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Barrier {
+    pub checkpoint_id: u64,
+    pub offset: usize,
+}
+
+/// An in-memory stream. Checkpoint barriers travel in band with the records: per-record
+/// operators keep each barrier behind the records that preceded it, two-input operators align
+/// them per input, and the windowed and timed views end the barrier-carrying stream.
 #[derive(Debug, Clone)]
 pub struct LocalStream<T> {
     data: Vec<T>,
+    barriers: Vec<Barrier>,
 }
 
 impl<T> LocalStream<T> {
     pub fn from_vec(data: Vec<T>) -> Self {
-        Self { data }
+        Self {
+            data,
+            barriers: Vec::new(),
+        }
+    }
+
+    /// Injects barriers 1, 2, ... after every `interval` records, as a source checkpointing
+    /// by offset would.
+    pub fn checkpoint_every(mut self, interval: usize) -> Self {
+        let interval = interval.max(1);
+        self.barriers = (interval..=self.data.len())
+            .step_by(interval)
+            .zip(1..)
+            .map(|(offset, checkpoint_id)| Barrier {
+                checkpoint_id,
+                offset,
+            })
+            .collect();
+        self
+    }
+
+    pub fn barriers(&self) -> &[Barrier] {
+        &self.barriers
+    }
+
+    /// Records grouped by the barrier that follows them; those after the last barrier come
+    /// under `None`, as a sink would hold them until the next checkpoint.
+    pub fn collect_checkpoints(self) -> Vec<(Option<u64>, Vec<T>)> {
+        let mut records = self.data.into_iter();
+        let mut taken = 0;
+        let mut out: Vec<(Option<u64>, Vec<T>)> = self
+            .barriers
+            .iter()
+            .map(|b| {
+                let segment = records.by_ref().take(b.offset - taken).collect();
+                taken = b.offset;
+                (Some(b.checkpoint_id), segment)
+            })
+            .collect();
+        out.push((None, records.collect()));
+        out
     }
 
     pub fn window_tumbling(self, window: SizeTumblingWindow) -> LocalWindowedStream<T> {
@@ -18,6 +79,59 @@ impl<T> LocalStream<T> {
             window_size: window.size.max(1),
         }
     }
+
+    pub fn assign_timestamps<F>(
+        self,
+        extractor: F,
+        watermarks: WatermarkStrategy,
+    ) -> LocalTimedStream<T>
+    where
+        F: TimestampExtractor<T>,
+    {
+        LocalTimedStream::new(self.data, extractor, watermarks)
+    }
+}
+
+/// Runs `op` over each record, moving every barrier to just after the output of the records
+/// that preceded it.
+fn pass_barriers<T, R>(
+    data: Vec<T>,
+    barriers: Vec<Barrier>,
+    mut op: impl FnMut(T, &mut Vec<R>),
+) -> (Vec<R>, Vec<Barrier>) {
+    let mut out = Vec::with_capacity(data.len());
+    let mut moved = Vec::with_capacity(barriers.len());
+    let mut pending = barriers.into_iter().peekable();
+    for (i, v) in data.into_iter().enumerate() {
+        while let Some(b) = pending.next_if(|b| b.offset <= i) {
+            moved.push(Barrier {
+                offset: out.len(),
+                ..b
+            });
+        }
+        op(v, &mut out);
+    }
+    moved.extend(pending.map(|b| Barrier {
+        offset: out.len(),
+        ..b
+    }));
+    (out, moved)
+}
+
+/// Checkpoint ids both inputs carry, with each side's offset, in `left`'s order. A barrier is
+/// only forwarded once every input has delivered it; ids one side never sends cannot align.
+fn aligned(left: &[Barrier], right: &[Barrier]) -> Vec<(u64, usize, usize)> {
+    let right: HashMap<u64, usize> = right.iter().map(|b| (b.checkpoint_id, b.offset)).collect();
+    let mut out: Vec<(u64, usize, usize)> = Vec::new();
+    for b in left {
+        let Some(&r) = right.get(&b.checkpoint_id) else {
+            continue;
+        };
+        if out.last().is_none_or(|&(_, _, prev)| prev <= r) {
+            out.push((b.checkpoint_id, b.offset, r));
+        }
+    }
+    out
 }
 
 pub struct LocalWindowedStream<T> {
@@ -36,24 +150,223 @@ impl<T: Clone> LocalWindowedStream<T> {
 
 impl<T> PStream<T> for LocalStream<T> {
     type Output<R> = LocalStream<R>;
+    type Keyed<K: Eq + Hash + Clone> = LocalKeyedStream<K, T>;
 
     fn map<R, F>(self, func: F) -> Self::Output<R>
     where
         F: MapFunction<T, R>,
     {
-        let out = self.data.into_iter().map(|v| func.map(v)).collect();
-        LocalStream { data: out }
+        let (data, barriers) = pass_barriers(self.data, self.barriers, |v, out| {
+            out.push(func.map(v));
+        });
+        LocalStream { data, barriers }
+    }
+
+    fn flat_map<R, F>(self, func: F) -> Self::Output<R>
+    where
+        F: FlatMapFunction<T, R>,
+    {
+        let (data, barriers) = pass_barriers(self.data, self.barriers, |v, out| {
+            out.extend(func.flat_map(v));
+        });
+        LocalStream { data, barriers }
     }
 
     fn filter<F>(self, func: F) -> Self
     where
         F: FilterFunction<T>,
     {
-        let out = self.data.into_iter().filter(|v| func.filter(v)).collect();
-        LocalStream { data: out }
+        let (data, barriers) = pass_barriers(self.data, self.barriers, |v, out| {
+            if func.filter(&v) {
+                out.push(v);
+            }
+        });
+        LocalStream { data, barriers }
+    }
+
+    fn key_by<K, F>(self, func: F) -> Self::Keyed<K>
+    where
+        K: Eq + Hash + Clone,
+        F: KeySelector<T, K>,
+    {
+        let data = self
+            .data
+            .into_iter()
+            .map(|v| (func.get_key(&v), v))
+            .collect();
+        LocalKeyedStream {
+            data,
+            barriers: self.barriers,
+        }
+    }
+
+    /// Aligns barriers per input: the records each side sent before barrier `n` come out ahead
+    /// of it, and whatever either side sent after it waits until both have delivered it.
+    fn union(self, other: Self) -> Self {
+        let mut left = self.data.into_iter();
+        let mut right = other.data.into_iter();
+        let (mut left_at, mut right_at) = (0, 0);
+        let mut data = Vec::new();
+        let mut barriers = Vec::new();
+        for (checkpoint_id, l, r) in aligned(&self.barriers, &other.barriers) {
+            data.extend(left.by_ref().take(l - left_at));
+            data.extend(right.by_ref().take(r - right_at));
+            (left_at, right_at) = (l, r);
+            barriers.push(Barrier {
+                checkpoint_id,
+                offset: data.len(),
+            });
+        }
+        data.extend(left);
+        data.extend(right);
+        LocalStream { data, barriers }
     }
 
     fn collect(self) -> Vec<T> {
         self.data
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct LocalKeyedStream<K, T> {
+    data: Vec<(K, T)>,
+    barriers: Vec<Barrier>,
+}
+
+impl<K, T> LocalKeyedStream<K, T> {
+    pub fn collect(self) -> Vec<(K, T)> {
+        self.data
+    }
+}
+
+/// Keyed aggregates and joins only emit at the end of their bounded input, so every barrier
+/// passes ahead of their output.
+fn ahead_of_output(checkpoint_ids: impl Iterator<Item = u64>) -> Vec<Barrier> {
+    checkpoint_ids
+        .map(|checkpoint_id| Barrier {
+            checkpoint_id,
+            offset: 0,
+        })
+        .collect()
+}
+
+/// Groups values by key, keys in order of first appearance.
+fn group_by_key<K: Eq + Hash + Clone, T>(data: Vec<(K, T)>) -> Vec<(K, Vec<T>)> {
+    let mut index: HashMap<K, usize> = HashMap::new();
+    let mut groups: Vec<(K, Vec<T>)> = Vec::new();
+    for (key, value) in data {
+        let slot = *index.entry(key.clone()).or_insert_with(|| {
+            groups.push((key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[slot].1.push(value);
+    }
+    groups
+}
+
+impl<K: Eq + Hash + Clone, T> PKeyedStream<K, T> for LocalKeyedStream<K, T> {
+    type Output<R> = LocalStream<R>;
+    type Keyed<U> = LocalKeyedStream<K, U>;
+
+    fn reduce<F>(self, func: F) -> Self::Output<(K, T)>
+    where
+        F: ReduceFunction<T>,
+    {
+        let barriers = ahead_of_output(self.barriers.iter().map(|b| b.checkpoint_id));
+        let data = group_by_key(self.data)
+            .into_iter()
+            .filter_map(|(key, values)| {
+                let reduced = values.into_iter().reduce(|acc, v| func.reduce(acc, v))?;
+                Some((key, reduced))
+            })
+            .collect();
+        LocalStream { data, barriers }
+    }
+
+    fn aggregate<ACC, R, F>(self, func: F) -> Self::Output<(K, R)>
+    where
+        F: AggregateFunction<T, ACC, R>,
+    {
+        let barriers = ahead_of_output(self.barriers.iter().map(|b| b.checkpoint_id));
+        let data = group_by_key(self.data)
+            .into_iter()
+            .map(|(key, values)| {
+                let mut acc = func.create_accumulator();
+                for v in values {
+                    func.add(&mut acc, v);
+                }
+                (key, func.get_result(acc))
+            })
+            .collect();
+        LocalStream { data, barriers }
+    }
+
+    fn join<U, O, W, TL, TR, F>(
+        self,
+        other: Self::Keyed<U>,
+        window: W,
+        timestamps: (TL, TR),
+        func: F,
+    ) -> Self::Output<O>
+    where
+        W: EventTimeWindow,
+        TL: TimestampExtractor<T>,
+        TR: TimestampExtractor<U>,
+        F: JoinFunction<T, U, O>,
+    {
+        let (left_time, right_time) = timestamps;
+        let mut panes = JoinPanes::default();
+        for (key, value) in &self.data {
+            for w in window.assign_windows(left_time.extract_timestamp(value)) {
+                panes.group(w, key).0.push(value);
+            }
+        }
+        for (key, value) in &other.data {
+            for w in window.assign_windows(right_time.extract_timestamp(value)) {
+                panes.group(w, key).1.push(value);
+            }
+        }
+        let barriers = ahead_of_output(
+            aligned(&self.barriers, &other.barriers)
+                .into_iter()
+                .map(|(checkpoint_id, _, _)| checkpoint_id),
+        );
+        let mut data = Vec::new();
+        for (left, right) in panes.windows.into_values().flatten() {
+            for l in left {
+                for r in &right {
+                    data.push(func.join(l, r));
+                }
+            }
+        }
+        LocalStream { data, barriers }
+    }
+}
+
+/// Both sides of a windowed join, buffered per window and per key.
+struct JoinPanes<'a, K, T, U> {
+    windows: BTreeMap<TimeWindow, Vec<JoinGroup<'a, T, U>>>,
+    slots: HashMap<(TimeWindow, &'a K), usize>,
+}
+
+type JoinGroup<'a, T, U> = (Vec<&'a T>, Vec<&'a U>);
+
+impl<K, T, U> Default for JoinPanes<'_, K, T, U> {
+    fn default() -> Self {
+        Self {
+            windows: BTreeMap::new(),
+            slots: HashMap::new(),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, T, U> JoinPanes<'a, K, T, U> {
+    fn group(&mut self, window: TimeWindow, key: &'a K) -> &mut JoinGroup<'a, T, U> {
+        let groups = self.windows.entry(window).or_default();
+        let slot = *self.slots.entry((window, key)).or_insert_with(|| {
+            groups.push((Vec::new(), Vec::new()));
+            groups.len() - 1
+        });
+        &mut groups[slot]
+    }
+}
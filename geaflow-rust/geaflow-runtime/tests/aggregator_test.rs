@@ -0,0 +1,151 @@
+use geaflow_api::aggregator::{AggregateOp, AggregateValue, AggregatorSpec};
+use geaflow_api::function::{
+    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+};
+use geaflow_common::types::Vertex;
+use geaflow_runtime::distributed::algorithm::WCC_UPDATED_VERTICES;
+use geaflow_runtime::distributed::driver::DistributedDriver;
+
+mod common;
+use common::{
+    directed, load, run_algorithm, run_function, start_workers, stop_workers, temp_dirs,
+    undirected, Graph,
+};
+
+#[derive(Clone)]
+struct GraphStatsFunction;
+
+impl VertexCentricComputeFunction<u64, i64, u8, u64> for GraphStatsFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        _message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, i64, u8, u64>,
+    ) {
+        context.vote_to_halt();
+        if context.iteration() == 1 {
+            assert_eq!(context.get_aggregated("vertices"), None);
+            context.aggregate("vertices", AggregateValue::Long(0));
+            context.aggregate("max_id", (*vertex_id).into());
+            context.aggregate("value_sum", AggregateValue::Double(0.5));
+            context.aggregate("unregistered", AggregateValue::Long(1));
+            context.send_message(*vertex_id, 0);
+            return;
+        }
+        assert_eq!(context.get_aggregated("unregistered"), None);
+        let vertices = context.get_aggregated("vertices").unwrap().as_i64();
+        let max_id = context.get_aggregated("max_id").unwrap().as_i64();
+        let value_sum = context.get_aggregated("value_sum").unwrap().as_f64();
+        context.set_new_vertex_value(vertices * 1000 + max_id * 10 + value_sum as i64);
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        vec![
+            AggregatorSpec::new("vertices", AggregateOp::Count),
+            AggregatorSpec::new("max_id", AggregateOp::Max),
+            AggregatorSpec::new("value_sum", AggregateOp::Sum),
+        ]
+    }
+}
+
+struct GraphStatsAlgorithm;
+
+impl VertexCentricComputeAlgorithm<u64, i64, u8, u64> for GraphStatsAlgorithm {
+    fn name(&self) -> &str {
+        "graph_stats"
+    }
+
+    fn iterations(&self) -> u64 {
+        2
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, i64, u8, u64>> {
+        Box::new(GraphStatsFunction)
+    }
+}
+
+/// Chain 1 -> 2 -> ... -> 10.
+fn graph() -> Graph<i64> {
+    let pairs: Vec<(u64, u64)> = (1..10).map(|id| (id, id + 1)).collect();
+    common::graph(1..=10, |_| 0, directed(&pairs))
+}
+
+fn assert_stats(vertices: Vec<Vertex<u64, i64>>) {
+    assert_eq!(vertices.len(), 10);
+    for v in vertices {
+        assert_eq!(v.value, 10 * 1000 + 10 * 10 + 5, "vertex {}", v.id);
+    }
+}
+
+#[test]
+fn test_graph_aggregators() {
+    for (vertices, _) in run_algorithm(graph, &GraphStatsAlgorithm, 3) {
+        assert_stats(vertices);
+    }
+    for (vertices, _) in run_function(graph, GraphStatsFunction, 3) {
+        assert_stats(vertices);
+    }
+}
+
+#[test]
+fn test_u64_aggregate_values_saturate() {
+    assert_eq!(AggregateValue::from(42u64), AggregateValue::Long(42));
+    assert_eq!(
+        AggregateValue::from(i64::MAX as u64),
+        AggregateValue::Long(i64::MAX)
+    );
+    assert_eq!(
+        AggregateValue::from(i64::MAX as u64 + 1),
+        AggregateValue::Long(i64::MAX)
+    );
+    assert_eq!(
+        AggregateValue::from(u64::MAX),
+        AggregateValue::Long(i64::MAX)
+    );
+}
+
+#[tokio::test]
+async fn test_distributed_driver_merges_worker_aggregates() {
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    load(
+        &mut driver,
+        &common::graph(1..=3, |id| id, undirected(&[(1, 2), (2, 3)])),
+    )
+    .await;
+    driver
+        .set_algorithm("wcc".to_string(), 10, Vec::new())
+        .await
+        .unwrap();
+
+    let updated = |driver: &DistributedDriver| {
+        driver
+            .aggregated()
+            .get(WCC_UPDATED_VERTICES)
+            .map(|v| v.as_i64())
+    };
+
+    let mut inboxes = DistributedDriver::new_inboxes(driver.worker_count());
+    let mut expected = vec![Some(3), Some(2), Some(1), None];
+    expected.reverse();
+    let mut iteration = 1;
+    loop {
+        let (next, any_msg) = driver
+            .superstep_round(iteration, &mut inboxes)
+            .await
+            .unwrap();
+        assert_eq!(
+            updated(&driver),
+            expected.pop().unwrap(),
+            "iteration {iteration}"
+        );
+        inboxes = next;
+        iteration += 1;
+        if !any_msg {
+            break;
+        }
+    }
+    assert!(expected.is_empty());
+
+    stop_workers(driver, handles).await;
+}
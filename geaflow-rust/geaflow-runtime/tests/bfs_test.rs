@@ -0,0 +1,354 @@
+use geaflow_api::aggregator::AggregateValues;
+use geaflow_api::graph::PGraphWindow;
+use geaflow_api::master::{MasterCompute, MasterState, MasterStep};
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::bfs::{
+    BfsAlgorithm, BfsMaster, BfsMessage, BfsMode, BfsSend, BfsValue, BFS_BOTTOM_UP, BFS_EDGES,
+    BFS_FRONTIER, BFS_FRONTIER_EDGES, BFS_LEVEL, BFS_PROBING, BFS_PUSH_LEVEL, BFS_TOP_DOWN,
+    BFS_VERTICES, UNREACHED,
+};
+use geaflow_runtime::distributed::algorithm::BfsParams;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+mod common;
+use common::{enc, fetch_sorted, load, start_workers, stop_workers, temp_dirs, Graph};
+
+const VERTICES: u64 = 300;
+
+/// Pseudo-random edges over `0..VERTICES`, mirrored when `undirected`, plus an edge 300-301 the
+/// searches below never reach.
+fn graph(undirected: bool) -> Graph<BfsValue> {
+    let vertices = (0..VERTICES + 2)
+        .map(|id| Vertex::new(id, BfsValue::unreached()))
+        .collect();
+    let mut edges = vec![Edge::new(VERTICES, VERTICES + 1, 0)];
+    let mut seed: u64 = 11;
+    for _ in 0..900 {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let src = (seed >> 33) % VERTICES;
+        let target = (seed >> 17) % VERTICES;
+        edges.push(Edge::new(src, target, 0));
+        if undirected {
+            edges.push(Edge::new(target, src, 0));
+        }
+    }
+    (vertices, edges)
+}
+
+fn expected_levels(edges: &[Edge<u64, u8>], source: u64) -> HashMap<u64, u64> {
+    let mut adjacency: HashMap<u64, Vec<u64>> = HashMap::new();
+    for e in edges {
+        adjacency.entry(e.src_id).or_default().push(e.target_id);
+    }
+    let mut levels = HashMap::from([(source, 0)]);
+    let mut queue = VecDeque::from([source]);
+    while let Some(id) = queue.pop_front() {
+        let level = levels[&id];
+        for &next in adjacency.get(&id).into_iter().flatten() {
+            if let std::collections::hash_map::Entry::Vacant(e) = levels.entry(next) {
+                e.insert(level + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    levels
+}
+
+/// Levels match a plain BFS and every parent is an in-neighbour one level up.
+fn check_tree(edges: &[Edge<u64, u8>], source: u64, tree: &HashMap<u64, BfsValue>) {
+    let expected = expected_levels(edges, source);
+    let edge_set: HashSet<(u64, u64)> = edges.iter().map(|e| (e.src_id, e.target_id)).collect();
+    assert_eq!(tree.len() as u64, VERTICES + 2);
+    for (id, value) in tree {
+        match expected.get(id) {
+            None => assert_eq!(*value, BfsValue::unreached(), "vertex {id}"),
+            Some(0) => assert_eq!((value.level, value.parent), (0, source)),
+            Some(level) => {
+                assert_eq!(value.level, *level, "vertex {id}");
+                assert!(edge_set.contains(&(value.parent, *id)), "vertex {id}");
+                assert_eq!(tree[&value.parent].level + 1, value.level, "vertex {id}");
+            }
+        }
+    }
+    assert!(expected.len() > 250);
+    assert_eq!(tree[&VERTICES].level, UNREACHED);
+}
+
+#[test]
+fn test_local_bfs_levels_and_parents() {
+    for undirected in [true, false] {
+        for parallelism in [1, 3] {
+            for source in [0, 17] {
+                let (vertices, edges) = graph(undirected);
+                let algo = BfsAlgorithm::new(source, 1000);
+                let tree: HashMap<u64, BfsValue> =
+                    PartitionedGraph::new(vertices, edges.clone(), parallelism)
+                        .compute_algorithm(&algo, parallelism)
+                        .vertices()
+                        .into_iter()
+                        .map(|v| (v.id, v.value))
+                        .collect();
+                check_tree(&edges, source, &tree);
+            }
+        }
+    }
+}
+
+/// Runs `modes` as supersteps over `edges`, every vertex in each, and returns the values after
+/// along with the number of messages sent.
+fn run_modes(
+    edges: &[(u64, u64)],
+    mut values: HashMap<u64, BfsValue>,
+    modes: &[BfsMode],
+) -> (HashMap<u64, BfsValue>, usize) {
+    let mut out_edges: HashMap<u64, Vec<u64>> = HashMap::new();
+    let mut in_edges: HashMap<u64, Vec<u64>> = HashMap::new();
+    for &(src, target) in edges {
+        out_edges.entry(src).or_default().push(target);
+        in_edges.entry(target).or_default().push(src);
+    }
+    let mut inbox: HashMap<u64, Vec<BfsMessage>> = HashMap::new();
+    let mut sent = 0;
+    for (i, mode) in modes.iter().enumerate() {
+        let mut next: HashMap<u64, Vec<BfsMessage>> = HashMap::new();
+        let ids: Vec<u64> = values.keys().copied().collect();
+        for id in ids {
+            let messages = inbox.remove(&id).unwrap_or_default();
+            let step = mode.step(0, id, i as u64 + 2, Some(values[&id]), messages);
+            values.insert(id, step.value);
+            let neighbours = |adjacency: &HashMap<u64, Vec<u64>>| {
+                adjacency.get(&id).cloned().unwrap_or_default()
+            };
+            let (targets, message) = match step.send {
+                BfsSend::None => continue,
+                BfsSend::OutNeighbours(m) => (neighbours(&out_edges), m),
+                BfsSend::InNeighbours {
+                    message,
+                    skip,
+                    take,
+                } => (
+                    neighbours(&in_edges)
+                        .into_iter()
+                        .skip(skip)
+                        .take(take)
+                        .collect(),
+                    message,
+                ),
+                BfsSend::Vertices(targets, m) => (targets, m),
+            };
+            sent += targets.len();
+            for target in targets {
+                next.entry(target).or_default().push(message);
+            }
+        }
+        inbox = next;
+    }
+    (values, sent)
+}
+
+#[test]
+fn test_bottom_up_stops_at_first_parent() {
+    // Source 0 reached a frontier 1..=20 that links to itself and to the targets 21..=30. Vertex
+    // 31's first in-neighbours are targets, so it finds frontier vertex 1 only in round 1.
+    let frontier = 1..=20u64;
+    let targets = 21..=30u64;
+    let late = 31;
+    let mut edges: Vec<(u64, u64)> = vec![(21, late), (22, late), (1, late)];
+    for u in frontier.clone() {
+        edges.extend(frontier.clone().filter(|&v| v != u).map(|v| (u, v)));
+        edges.extend(targets.clone().map(|v| (u, v)));
+    }
+    let mut values: HashMap<u64, BfsValue> =
+        (0..=late).map(|id| (id, BfsValue::unreached())).collect();
+    values.insert(
+        0,
+        BfsValue {
+            level: 0,
+            parent: 0,
+        },
+    );
+    for id in frontier.clone() {
+        values.insert(
+            id,
+            BfsValue {
+                level: 1,
+                parent: 0,
+            },
+        );
+    }
+
+    let top_down = [
+        BfsMode::TopDown {
+            push_level: Some(1),
+        },
+        BfsMode::TopDown { push_level: None },
+        BfsMode::TopDown { push_level: None },
+    ];
+    let (pushed, push_messages) = run_modes(&edges, values.clone(), &top_down);
+    let bottom_up = [
+        BfsMode::Request { round: 0 },
+        BfsMode::Reply { level: 1 },
+        BfsMode::Request { round: 1 },
+        BfsMode::Reply { level: 1 },
+        BfsMode::Request { round: 2 },
+    ];
+    let (pulled, pull_messages) = run_modes(&edges, values, &bottom_up);
+
+    assert_eq!(pulled, pushed);
+    for id in targets.clone().chain([late]) {
+        assert_eq!(
+            pulled[&id],
+            BfsValue {
+                level: 2,
+                parent: 1
+            },
+            "vertex {id}"
+        );
+    }
+    // Every frontier edge, plus 1 -> 31 and the two target edges into 31 a level later.
+    assert_eq!(push_messages, 20 * (19 + 10) + 3);
+    // One request and one reply per target, and 31's three requests and one reply.
+    assert_eq!(pull_messages, 2 * 10 + 4);
+}
+
+type Master = Option<Box<dyn MasterCompute>>;
+
+fn superstep(
+    master: &mut Master,
+    state: &mut MasterState,
+    iteration: u64,
+    pending: bool,
+    values: &[(&str, i64)],
+) -> MasterStep {
+    let aggregated: AggregateValues = values
+        .iter()
+        .map(|(name, v)| (name.to_string(), (*v).into()))
+        .collect();
+    state.before_superstep(master, iteration, pending, 0, &aggregated)
+}
+
+#[test]
+fn test_master_switches_direction_with_frontier_size() {
+    let mut master: Master = Some(Box::new(BfsMaster::default()));
+    let mut state = MasterState::default();
+
+    assert_eq!(
+        superstep(&mut master, &mut state, 1, true, &[]),
+        MasterStep::Continue { wake_all: false }
+    );
+    let source = [
+        (BFS_VERTICES, 1000),
+        (BFS_EDGES, 10_000),
+        (BFS_FRONTIER, 1),
+        (BFS_FRONTIER_EDGES, 5),
+        (BFS_LEVEL, 0),
+    ];
+    assert_eq!(
+        superstep(&mut master, &mut state, 2, true, &source),
+        MasterStep::Continue { wake_all: false }
+    );
+    // 1000 frontier edges against 9000 unexplored: bottom-up from here on.
+    let wide = [
+        (BFS_FRONTIER, 100),
+        (BFS_FRONTIER_EDGES, 1000),
+        (BFS_LEVEL, 1),
+    ];
+    assert_eq!(
+        superstep(&mut master, &mut state, 3, true, &wide),
+        MasterStep::Continue { wake_all: true }
+    );
+    assert_eq!(state.phase, BFS_BOTTOM_UP);
+    let mode = |state: &MasterState, iteration| {
+        BfsMode::new(state.phase, iteration, |name| state.get_broadcast(name))
+    };
+    assert_eq!(mode(&state, 3), BfsMode::Request { round: 0 });
+    // The first request superstep completes level 2; 40 vertices have more in-neighbours to ask.
+    let wider = [
+        (BFS_FRONTIER, 500),
+        (BFS_FRONTIER_EDGES, 5000),
+        (BFS_LEVEL, 2),
+        (BFS_PROBING, 40),
+    ];
+    assert_eq!(
+        superstep(&mut master, &mut state, 4, true, &wider),
+        MasterStep::Continue { wake_all: false }
+    );
+    assert_eq!(mode(&state, 4), BfsMode::Reply { level: 2 });
+    assert_eq!(
+        superstep(&mut master, &mut state, 5, true, &[]),
+        MasterStep::Continue { wake_all: false }
+    );
+    assert_eq!(mode(&state, 5), BfsMode::Request { round: 1 });
+    // Level 3 so far: 15 vertices from round 0, and nobody left probing.
+    let partial = [
+        (BFS_FRONTIER, 15),
+        (BFS_FRONTIER_EDGES, 150),
+        (BFS_LEVEL, 3),
+    ];
+    assert_eq!(
+        superstep(&mut master, &mut state, 6, true, &partial),
+        MasterStep::Continue { wake_all: false }
+    );
+    assert_eq!(mode(&state, 6), BfsMode::Reply { level: 2 });
+    assert_eq!(
+        superstep(&mut master, &mut state, 7, true, &[]),
+        MasterStep::Continue { wake_all: false }
+    );
+    assert_eq!(mode(&state, 7), BfsMode::Request { round: 0 });
+    // 5 more make a level of 20, below 1000 / 24 vertices: back to top-down, pushing from level 3.
+    let rest = [(BFS_FRONTIER, 5), (BFS_FRONTIER_EDGES, 50), (BFS_LEVEL, 3)];
+    assert_eq!(
+        superstep(&mut master, &mut state, 8, true, &rest),
+        MasterStep::Continue { wake_all: true }
+    );
+    assert_eq!(state.phase, BFS_TOP_DOWN);
+    assert_eq!(state.get_broadcast(BFS_PUSH_LEVEL).unwrap().as_i64(), 3);
+    assert_eq!(
+        superstep(&mut master, &mut state, 9, false, &[]),
+        MasterStep::Halt
+    );
+
+    // Bottom-up stops once a level reaches nobody.
+    let mut master: Master = Some(Box::new(BfsMaster::new(1, 1)));
+    let mut state = MasterState::default();
+    superstep(&mut master, &mut state, 1, true, &[]);
+    let first = [
+        (BFS_VERTICES, 10),
+        (BFS_EDGES, 10),
+        (BFS_FRONTIER, 1),
+        (BFS_FRONTIER_EDGES, 10),
+    ];
+    assert_eq!(
+        superstep(&mut master, &mut state, 2, true, &first),
+        MasterStep::Continue { wake_all: true }
+    );
+    assert_eq!(
+        superstep(&mut master, &mut state, 3, true, &[(BFS_FRONTIER, 0)]),
+        MasterStep::Halt
+    );
+}
+
+#[tokio::test]
+async fn test_distributed_bfs_reruns_on_loaded_graph() {
+    for undirected in [true, false] {
+        let dirs = temp_dirs(3);
+        let (mut driver, handles) = start_workers(&dirs).await;
+        let graph = graph(undirected);
+        load(&mut driver, &graph).await;
+
+        for source in [0, 17, 0] {
+            driver
+                .set_algorithm("bfs".to_string(), 1000, enc(&BfsParams { source }))
+                .await
+                .unwrap();
+            driver.execute(1000).await.unwrap();
+            let tree: HashMap<u64, BfsValue> =
+                fetch_sorted(&mut driver).await.into_iter().collect();
+            check_tree(&graph.1, source, &tree);
+        }
+        stop_workers(driver, handles).await;
+    }
+}
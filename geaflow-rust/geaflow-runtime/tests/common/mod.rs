@@ -0,0 +1,193 @@
+//! Fixtures shared by the integration tests; each test binary uses only part of this module.
+#![allow(dead_code)]
+
+use geaflow_api::function::{VertexCentricComputeAlgorithm, VertexCentricComputeFunction};
+use geaflow_api::graph::PGraphWindow;
+use geaflow_api::mutation::GraphMutation;
+use geaflow_common::error::GeaFlowResult;
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::distributed::driver::DistributedDriver;
+use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
+use geaflow_runtime::graph::mem_graph::InMemoryGraph;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use tokio::task::JoinHandle;
+
+/// Vertices and unweighted edges of a local test graph.
+pub type Graph<VV> = (Vec<Vertex<u64, VV>>, Vec<Edge<u64, u8>>);
+
+/// Vertices `ids`, each holding `value(id)`, joined by `edges`.
+pub fn graph<VV>(
+    ids: impl IntoIterator<Item = u64>,
+    value: impl Fn(u64) -> VV,
+    edges: Vec<Edge<u64, u8>>,
+) -> Graph<VV> {
+    let vertices = ids
+        .into_iter()
+        .map(|id| Vertex::new(id, value(id)))
+        .collect();
+    (vertices, edges)
+}
+
+pub fn directed(pairs: &[(u64, u64)]) -> Vec<Edge<u64, u8>> {
+    pairs.iter().map(|&(s, t)| Edge::new(s, t, 0)).collect()
+}
+
+/// Both directions of every pair.
+pub fn undirected(pairs: &[(u64, u64)]) -> Vec<Edge<u64, u8>> {
+    pairs
+        .iter()
+        .flat_map(|&(a, b)| [Edge::new(a, b, 0), Edge::new(b, a, 0)])
+        .collect()
+}
+
+/// `(id, value)` pairs in id order.
+pub fn sorted<VV>(vertices: Vec<Vertex<u64, VV>>) -> Vec<(u64, VV)> {
+    let mut out: Vec<(u64, VV)> = vertices.into_iter().map(|v| (v.id, v.value)).collect();
+    out.sort_by_key(|(id, _)| *id);
+    out
+}
+
+fn result<VV, G: PGraphWindow<u64, VV, u8>>(graph: G) -> Graph<VV> {
+    (graph.vertices(), graph.edges())
+}
+
+/// Runs `algorithm` on a fresh `fixture()` in a single-threaded `InMemoryGraph` and in a
+/// `PartitionedGraph` split `parallelism` ways, returning both results in that order.
+pub fn run_algorithm<VV, M, A>(
+    fixture: impl Fn() -> Graph<VV>,
+    algorithm: &A,
+    parallelism: usize,
+) -> [Graph<VV>; 2]
+where
+    VV: Clone + Send + Sync + 'static,
+    M: Clone + Send + Sync + 'static,
+    A: VertexCentricComputeAlgorithm<u64, VV, u8, M>,
+{
+    let (vertices, edges) = fixture();
+    let in_memory = InMemoryGraph::new(vertices, edges).compute_algorithm(algorithm, 1);
+    let (vertices, edges) = fixture();
+    let partitioned = PartitionedGraph::new(vertices, edges, parallelism)
+        .compute_algorithm(algorithm, parallelism);
+    [result(in_memory), result(partitioned)]
+}
+
+/// Like [`run_algorithm`], with a bare function and no iteration limit.
+pub fn run_function<VV, M, F>(
+    fixture: impl Fn() -> Graph<VV>,
+    function: F,
+    parallelism: usize,
+) -> [Graph<VV>; 2]
+where
+    VV: Clone + Send + Sync + 'static,
+    M: Clone + Send + Sync + 'static,
+    F: VertexCentricComputeFunction<u64, VV, u8, M> + Clone,
+{
+    let (vertices, edges) = fixture();
+    let in_memory = InMemoryGraph::new(vertices, edges).compute(function.clone(), 1);
+    let (vertices, edges) = fixture();
+    let partitioned =
+        PartitionedGraph::new(vertices, edges, parallelism).compute(function, parallelism);
+    [result(in_memory), result(partitioned)]
+}
+
+pub fn free_local_addr() -> SocketAddr {
+    let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    addr
+}
+
+pub fn enc<T: Serialize>(v: &T) -> Vec<u8> {
+    bincode::serialize(v).unwrap()
+}
+
+pub fn dec<T: DeserializeOwned>(bytes: &[u8]) -> T {
+    bincode::deserialize(bytes).unwrap()
+}
+
+pub fn temp_dirs(count: usize) -> Vec<tempfile::TempDir> {
+    (0..count).map(|_| tempfile::tempdir().unwrap()).collect()
+}
+
+pub type WorkerHandle = JoinHandle<GeaFlowResult<()>>;
+
+/// Starts one worker per state dir and connects a driver to them.
+pub async fn start_workers(dirs: &[impl AsRef<Path>]) -> (DistributedDriver, Vec<WorkerHandle>) {
+    let addrs: Vec<SocketAddr> = dirs.iter().map(|_| free_local_addr()).collect();
+    let handles = addrs
+        .iter()
+        .zip(dirs)
+        .map(|(addr, dir)| {
+            tokio::spawn(run_worker(WorkerConfig {
+                listen_addr: *addr,
+                state_dir: PathBuf::from(dir.as_ref()),
+                master_addr: None,
+            }))
+        })
+        .collect();
+    let driver = DistributedDriver::connect(&addrs).await.unwrap();
+    (driver, handles)
+}
+
+/// Shuts the driver down and waits for every worker to exit cleanly.
+pub async fn stop_workers(driver: DistributedDriver, handles: Vec<WorkerHandle>) {
+    driver.shutdown().await.unwrap();
+    for h in handles {
+        h.await.unwrap().unwrap();
+    }
+}
+
+/// Loads a local test graph into the cluster, bincode-encoded.
+pub async fn load<VV: Serialize>(driver: &mut DistributedDriver, (vertices, edges): &Graph<VV>) {
+    driver
+        .load_graph(
+            vertices
+                .iter()
+                .map(|v| (enc(&v.id), enc(&v.value)))
+                .collect(),
+            edges
+                .iter()
+                .map(|e| (enc(&e.src_id), enc(&e.target_id), enc(&e.value)))
+                .collect(),
+        )
+        .await
+        .unwrap();
+}
+
+/// Decoded vertices of the cluster in id order.
+pub async fn fetch_sorted<VV: DeserializeOwned>(driver: &mut DistributedDriver) -> Vec<(u64, VV)> {
+    let mut out: Vec<(u64, VV)> = driver
+        .fetch_vertices()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, value)| (dec(&id), dec(&value)))
+        .collect();
+    out.sort_by_key(|(id, _)| *id);
+    out
+}
+
+pub fn enc_delta<VV: Serialize>(
+    delta: Vec<GraphMutation<u64, VV, u8>>,
+) -> Vec<GraphMutation<Vec<u8>, Vec<u8>, Vec<u8>>> {
+    delta
+        .into_iter()
+        .map(|m| match m {
+            GraphMutation::AddVertex(v) => {
+                GraphMutation::AddVertex(Vertex::new(enc(&v.id), enc(&v.value)))
+            }
+            GraphMutation::RemoveVertex(id) => GraphMutation::RemoveVertex(enc(&id)),
+            GraphMutation::AddEdge(e) => {
+                GraphMutation::AddEdge(Edge::new(enc(&e.src_id), enc(&e.target_id), enc(&e.value)))
+            }
+            GraphMutation::RemoveEdge { src_id, target_id } => GraphMutation::RemoveEdge {
+                src_id: enc(&src_id),
+                target_id: enc(&target_id),
+            },
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+use geaflow_api::graph::PGraphWindow;
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::wcc::WccAlgorithm;
+use geaflow_runtime::graph::csr::Csr;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+
+fn pairs(edges: impl Iterator<Item = Edge<u64, u8>>) -> Vec<(u64, u64, u8)> {
+    let mut out: Vec<(u64, u64, u8)> = edges.map(|e| (e.src_id, e.target_id, e.value)).collect();
+    out.sort();
+    out
+}
+
+#[test]
+fn test_csr_rows() {
+    let edges = vec![
+        Edge::new(3u64, 1u64, 7u8),
+        Edge::new(1, 2, 1),
+        Edge::new(3, 2, 8),
+        Edge::new(1, 3, 2),
+    ];
+    let out = Csr::out_edges(edges.clone()).unwrap();
+    assert_eq!(out.len(), 4);
+    assert_eq!(out.degree(&1), 2);
+    assert_eq!(out.degree(&2), 0);
+    assert_eq!(pairs(out.edges_of(&3)), vec![(3, 1, 7), (3, 2, 8)]);
+    assert_eq!(out.rows().count(), 2);
+
+    let inbound = Csr::in_edges(edges.clone()).unwrap();
+    assert_eq!(inbound.degree(&2), 2);
+    assert_eq!(pairs(inbound.edges_of(&2)), vec![(1, 2, 1), (3, 2, 8)]);
+    assert_eq!(pairs(inbound.iter()), pairs(edges.clone().into_iter()));
+
+    let mut out = out;
+    assert!(out.remove_row(&3));
+    assert!(!out.remove_row(&3));
+    assert_eq!(pairs(out.iter()), vec![(1, 2, 1), (1, 3, 2)]);
+}
+
+#[test]
+fn test_csr_keeps_input_order() {
+    let edges: Vec<Edge<u64, u8>> = [(5, 1), (2, 9), (5, 3), (7, 5), (2, 4)]
+        .into_iter()
+        .map(|(s, t)| Edge::new(s, t, 0))
+        .collect();
+    let mut out = Csr::out_edges(edges).unwrap();
+    let order = |csr: &Csr<u64, u8>| -> Vec<(u64, u64)> {
+        csr.iter().map(|e| (e.src_id, e.target_id)).collect()
+    };
+    assert_eq!(order(&out), vec![(5, 1), (5, 3), (2, 9), (2, 4), (7, 5)]);
+    assert_eq!(
+        out.rows()
+            .map(|(id, degree)| (*id, degree))
+            .collect::<Vec<_>>(),
+        vec![(5, 2), (2, 2), (7, 1)]
+    );
+
+    // Mutations land in place: new edges at the end of their row, new rows at the end.
+    out.insert(Edge::new(5, 8, 0)).unwrap();
+    out.insert(Edge::new(6, 2, 0)).unwrap();
+    assert!(out.remove(&2, &9));
+    assert!(!out.remove(&2, &9));
+    assert_eq!(
+        order(&out),
+        vec![(5, 1), (5, 3), (5, 8), (2, 4), (7, 5), (6, 2)]
+    );
+    assert_eq!(out.len(), 6);
+    assert_eq!(out.degree(&5), 3);
+
+    for i in 0..10 {
+        out.insert(Edge::new(7, 100 + i, 0)).unwrap();
+    }
+    assert!(out.remove_row(&5));
+    assert_eq!(out.len(), 13);
+    assert_eq!(out.degree(&5), 0);
+    assert_eq!(order(&out)[..3], [(2, 4), (7, 5), (7, 100)]);
+    assert_eq!(out.neighbours().count(), out.len());
+}
+
+#[test]
+fn test_partitioned_graph_keeps_edges_once() {
+    let vertices = (1..=6u64).map(|id| Vertex::new(id, id)).collect();
+    let edges: Vec<Edge<u64, u8>> = [(1, 2), (2, 3), (4, 5), (5, 6), (6, 4)]
+        .into_iter()
+        .map(|(s, t)| Edge::new(s, t, 0))
+        .collect();
+    let graph = PartitionedGraph::new(vertices, edges.clone(), 3);
+    assert_eq!(graph.edge_counts().iter().sum::<usize>(), edges.len());
+
+    let result = graph.compute_algorithm(&WccAlgorithm::new(10), 3);
+    assert_eq!(pairs(result.edges().into_iter()), pairs(edges.into_iter()));
+    let mut components: Vec<(u64, u64)> = result
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id, v.value))
+        .collect();
+    components.sort();
+    assert_eq!(
+        components,
+        vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4), (6, 4)]
+    );
+}
@@ -0,0 +1,132 @@
+use geaflow_api::graph::PGraphWindow;
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::sssp::{DeltaSteppingAlgorithm, SsspAlgorithm, SsspWeights};
+use geaflow_runtime::distributed::algorithm::{DeltaSteppingParams, SsspParams};
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+
+mod common;
+use common::{enc, fetch_sorted, load, sorted, start_workers, stop_workers, temp_dirs, Graph};
+
+const VERTICES: u64 = 60;
+
+/// A ring 0 -> 1 -> ... with heavy edges plus pseudo-random chords of weight 1..=20; vertex
+/// `VERTICES` has no in-edges.
+fn graph() -> Graph<f64> {
+    let vertices = (0..=VERTICES)
+        .map(|id| Vertex::new(id, f64::INFINITY))
+        .collect();
+    let mut edges: Vec<Edge<u64, u8>> = (0..VERTICES)
+        .map(|id| Edge::new(id, (id + 1) % VERTICES, 25))
+        .collect();
+    let mut seed: u64 = 7;
+    for _ in 0..180 {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let src = (seed >> 33) % VERTICES;
+        let target = (seed >> 17) % VERTICES;
+        let weight = ((seed >> 7) % 20 + 1) as u8;
+        edges.push(Edge::new(src, target, weight));
+    }
+    edges.push(Edge::new(VERTICES, 0, 1));
+    (vertices, edges)
+}
+
+fn local_sssp() -> Vec<(u64, f64)> {
+    let (vertices, edges) = graph();
+    let algo = SsspAlgorithm::new(0, 1000, SsspWeights::EdgeValue);
+    sorted(
+        PartitionedGraph::new(vertices, edges, 1)
+            .compute_algorithm(&algo, 1)
+            .vertices(),
+    )
+}
+
+#[test]
+fn test_delta_stepping_matches_sssp() {
+    let expected = local_sssp();
+    assert_eq!(expected[VERTICES as usize].1, f64::INFINITY);
+    assert!(expected[..VERTICES as usize]
+        .iter()
+        .all(|(_, d)| d.is_finite()));
+
+    for delta in [1.0, 3.0, 10.0, 1000.0] {
+        for parallelism in [1, 3] {
+            let (vertices, edges) = graph();
+            let algo = DeltaSteppingAlgorithm::new(0, 1000, SsspWeights::EdgeValue, delta);
+            let result = PartitionedGraph::new(vertices, edges, parallelism)
+                .compute_algorithm(&algo, parallelism);
+            assert_eq!(
+                sorted(result.vertices()),
+                expected,
+                "delta {delta}, parallelism {parallelism}"
+            );
+        }
+    }
+
+    // Unit weights give hop counts.
+    let (vertices, edges) = graph();
+    let unit = SsspAlgorithm::new(0, 1000, SsspWeights::Unit);
+    let hops = sorted(
+        PartitionedGraph::new(vertices, edges, 1)
+            .compute_algorithm(&unit, 1)
+            .vertices(),
+    );
+    let (vertices, edges) = graph();
+    let algo = DeltaSteppingAlgorithm::new(0, 1000, SsspWeights::Unit, 2.0);
+    let result = PartitionedGraph::new(vertices, edges, 2).compute_algorithm(&algo, 2);
+    assert_eq!(sorted(result.vertices()), hops);
+}
+
+async fn distributed(name: &str, params: Vec<u8>) -> Vec<(u64, f64)> {
+    let dirs = temp_dirs(3);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    load(&mut driver, &graph()).await;
+    driver
+        .set_algorithm(name.to_string(), 1000, params)
+        .await
+        .unwrap();
+    driver.execute(1000).await.unwrap();
+    let out = fetch_sorted(&mut driver).await;
+    stop_workers(driver, handles).await;
+    out
+}
+
+#[tokio::test]
+async fn test_distributed_delta_stepping() {
+    let expected = local_sssp();
+    let sssp = SsspParams {
+        source: 0,
+        weights: SsspWeights::EdgeValue,
+    };
+    assert_eq!(distributed("sssp", enc(&sssp)).await, expected);
+
+    for delta in [2.0, 8.0] {
+        let params = DeltaSteppingParams {
+            source: 0,
+            weights: SsspWeights::EdgeValue,
+            delta,
+        };
+        assert_eq!(
+            distributed("delta_stepping", enc(&params)).await,
+            expected,
+            "delta {delta}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_delta_stepping_rejects_bad_delta() {
+    let params = DeltaSteppingParams {
+        source: 0,
+        weights: SsspWeights::Unit,
+        delta: 0.0,
+    };
+    let dirs = temp_dirs(1);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    assert!(driver
+        .set_algorithm("delta_stepping".to_string(), 10, enc(&params))
+        .await
+        .is_err());
+    stop_workers(driver, handles).await;
+}
@@ -1,7 +1,7 @@
 use geaflow_runtime::distributed::driver::DistributedDriver;
 use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
 use geaflow_runtime::plan::job_spec::{
-    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, JobMode, JobSpec,
+    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, IdSpec, JobMode, JobSpec, PartitionerSpec,
 };
 use geaflow_runtime::scheduler::cycle_scheduler::CycleScheduler;
 use std::net::{Ipv4Addr, SocketAddr};
@@ -55,6 +55,7 @@ async fn test_distributed_checkpoint_restore_vertices() {
             edges: FileSource::Csv {
                 path: edges_path.to_string_lossy().to_string(),
             },
+            ids: IdSpec::U64,
         },
         algorithm: AlgorithmSpec::Wcc { iterations: 2 },
         checkpoint: CheckpointSpec {
@@ -62,6 +63,7 @@ async fn test_distributed_checkpoint_restore_vertices() {
             interval_iters: 1,
             base_dir: checkpoint_dir.path().to_string_lossy().to_string(),
         },
+        partitioner: PartitionerSpec::Hash,
     };
 
     let mut driver = DistributedDriver::connect(&[w1_addr, w2_addr])
@@ -1,6 +1,10 @@
+use geaflow_api::graph::PGraphWindow;
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::pagerank::{PageRankAlgorithm, PAGERANK_DELTA};
 use geaflow_runtime::distributed::algorithm::PageRankParams;
 use geaflow_runtime::distributed::driver::DistributedDriver;
 use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
 use std::net::{Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 
@@ -53,7 +57,10 @@ async fn test_distributed_pagerank_two_cycle() {
         .collect();
 
     driver.load_graph(vertices, edges).await.unwrap();
-    let params = PageRankParams { alpha: 0.85 };
+    let params = PageRankParams {
+        alpha: 0.85,
+        tolerance: None,
+    };
     driver
         .set_algorithm("pagerank".to_string(), 3, enc(&params))
         .await
@@ -76,3 +83,135 @@ async fn test_distributed_pagerank_two_cycle() {
     assert!((decoded[0].1 - 1.0).abs() < 1e-9);
     assert!((decoded[1].1 - 1.0).abs() < 1e-9);
 }
+
+#[tokio::test]
+async fn test_distributed_pagerank_dangling_rank_and_tolerance() {
+    // Vertex 5 has no out-edges and 6 no edges at all.
+    let pairs = [(1u64, 2u64), (1, 3), (2, 3), (3, 1), (4, 3), (4, 5)];
+    let init = 1.0 / 6.0;
+    let algo = PageRankAlgorithm::new(1000, 0.85).with_tolerance(1e-8);
+    let local = PartitionedGraph::new(
+        (1..=6u64).map(|id| Vertex::new(id, init)).collect(),
+        pairs.iter().map(|(s, t)| Edge::new(*s, *t, 0u8)).collect(),
+        2,
+    )
+    .compute_algorithm(&algo, 2);
+    let mut expected: Vec<(u64, f64)> = local.vertices().iter().map(|v| (v.id, v.value)).collect();
+    expected.sort_by_key(|(id, _)| *id);
+
+    let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+    let addrs = [free_local_addr(), free_local_addr()];
+    for (addr, dir) in addrs.iter().zip(&dirs) {
+        tokio::spawn(run_worker(WorkerConfig {
+            listen_addr: *addr,
+            state_dir: PathBuf::from(dir.path()),
+            master_addr: None,
+        }));
+    }
+    let mut driver = DistributedDriver::connect(&addrs).await.unwrap();
+    driver
+        .load_graph(
+            (1..=6u64).map(|id| (enc(&id), enc(&init))).collect(),
+            pairs
+                .iter()
+                .map(|(s, t)| (enc(s), enc(t), enc(&0u8)))
+                .collect(),
+        )
+        .await
+        .unwrap();
+    let params = PageRankParams {
+        alpha: 0.85,
+        tolerance: Some(1e-8),
+    };
+    driver
+        .set_algorithm("pagerank".to_string(), 1000, enc(&params))
+        .await
+        .unwrap();
+    let executed = driver.execute(1000).await.unwrap();
+    assert!(executed < 1000, "{executed}");
+    let residual = driver.aggregated()[PAGERANK_DELTA].as_f64();
+    assert!(residual > 0.0 && residual < 1e-8, "{residual}");
+
+    let mut ranks: Vec<(u64, f64)> = driver
+        .fetch_vertices()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, value)| (dec(&id), dec(&value)))
+        .collect();
+    driver.shutdown().await.unwrap();
+    ranks.sort_by_key(|(id, _)| *id);
+
+    assert!((ranks.iter().map(|(_, r)| r).sum::<f64>() - 1.0).abs() < 1e-12);
+    assert_eq!(ranks.len(), expected.len());
+    for ((id, rank), (_, want)) in ranks.iter().zip(&expected) {
+        assert!((rank - want).abs() < 1e-12, "vertex {id}: {rank} != {want}");
+    }
+}
+
+/// Ranks after running PageRank on a fresh three-worker cluster.
+async fn distributed_ranks(pairs: &[(u64, u64)], vertex_count: u64) -> Vec<(u64, u64)> {
+    let dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+    let addrs: Vec<SocketAddr> = (0..3).map(|_| free_local_addr()).collect();
+    for (addr, dir) in addrs.iter().zip(&dirs) {
+        tokio::spawn(run_worker(WorkerConfig {
+            listen_addr: *addr,
+            state_dir: PathBuf::from(dir.path()),
+            master_addr: None,
+        }));
+    }
+    let mut driver = DistributedDriver::connect(&addrs).await.unwrap();
+    let init = 1.0 / vertex_count as f64;
+    driver
+        .load_graph(
+            (1..=vertex_count)
+                .map(|id| (enc(&id), enc(&init)))
+                .collect(),
+            pairs
+                .iter()
+                .map(|(s, t)| (enc(s), enc(t), enc(&0u8)))
+                .collect(),
+        )
+        .await
+        .unwrap();
+    let params = PageRankParams {
+        alpha: 0.85,
+        tolerance: None,
+    };
+    driver
+        .set_algorithm("pagerank".to_string(), 10, enc(&params))
+        .await
+        .unwrap();
+    driver.execute(10).await.unwrap();
+    let mut ranks: Vec<(u64, u64)> = driver
+        .fetch_vertices()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, value)| (dec(&id), dec::<f64>(&value).to_bits()))
+        .collect();
+    driver.shutdown().await.unwrap();
+    ranks.sort_unstable();
+    ranks
+}
+
+#[tokio::test]
+async fn test_distributed_pagerank_is_bit_identical_across_runs() {
+    // Every vertex points at the hub and a few others, so each worker sums many f64 messages
+    // of different magnitudes per target.
+    let n = 200u64;
+    let mut pairs = Vec::new();
+    for id in 2..=n {
+        pairs.push((id, 1));
+        for step in [3, 7, 31] {
+            if id % step == 0 {
+                pairs.push((id, (id * step) % n + 1));
+            }
+        }
+    }
+    let first = distributed_ranks(&pairs, n).await;
+    assert_eq!(first.len(), n as usize);
+    for _ in 0..3 {
+        assert_eq!(distributed_ranks(&pairs, n).await, first);
+    }
+}
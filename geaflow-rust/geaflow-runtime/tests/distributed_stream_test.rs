@@ -0,0 +1,105 @@
+use geaflow_api::stream::PStream;
+use geaflow_api::window::WatermarkStrategy;
+use geaflow_runtime::distributed::stream::{
+    create_window_aggregate, to_records, StreamRecord, WindowOperator, WindowResults, WindowSpec,
+};
+use geaflow_runtime::state::rocksdb_graph_state::RocksDbGraphState;
+use geaflow_runtime::stream::LocalStream;
+
+mod common;
+use common::{dec, enc, start_workers, stop_workers, temp_dirs};
+
+/// `(key, window start, window end, result)` for every fired window, in firing order.
+fn fired<R: serde::de::DeserializeOwned>(results: &WindowResults) -> Vec<(u64, u64, u64, R)> {
+    results
+        .fired
+        .iter()
+        .map(|f| (dec(&f.key), f.window.start, f.window.end, dec(&f.result)))
+        .collect()
+}
+
+/// `(user, timestamp)` clicks, keyed by user.
+fn clicks(events: &[(u64, u64)]) -> Vec<StreamRecord> {
+    let keyed = LocalStream::from_vec(events.to_vec()).key_by(|(user, _): &(u64, u64)| *user);
+    to_records(keyed, |(_, ts): &(u64, u64)| *ts).unwrap()
+}
+
+#[tokio::test]
+async fn test_distributed_keyed_tumbling_count() {
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    driver
+        .set_window_aggregate(
+            "count".to_string(),
+            Vec::new(),
+            WindowSpec::Tumbling { size: 10 },
+            WatermarkStrategy::bounded_out_of_orderness(5),
+            10,
+        )
+        .await
+        .unwrap();
+
+    // Watermark 7: nothing has closed yet.
+    let results = driver
+        .push_records(clicks(&[(1, 1), (2, 3), (1, 8), (2, 12)]))
+        .await
+        .unwrap();
+    assert_eq!(results, WindowResults::default());
+
+    // Watermark 13 closes [0, 10), including the out-of-order click at 4.
+    let results = driver
+        .push_records(clicks(&[(1, 18), (1, 4)]))
+        .await
+        .unwrap();
+    assert_eq!(fired::<u64>(&results), vec![(1, 0, 10, 3), (2, 0, 10, 1)]);
+
+    // Within the allowed lateness a closed window fires again with the update.
+    let results = driver.push_records(clicks(&[(2, 6)])).await.unwrap();
+    assert_eq!(fired::<u64>(&results), vec![(2, 0, 10, 2)]);
+
+    // Watermark 25 closes [10, 20) and drops [0, 10) for good.
+    let results = driver.push_records(clicks(&[(1, 30)])).await.unwrap();
+    assert_eq!(fired::<u64>(&results), vec![(1, 10, 20, 1), (2, 10, 20, 1)]);
+
+    let results = driver.push_records(clicks(&[(1, 2)])).await.unwrap();
+    assert!(results.fired.is_empty());
+    assert_eq!(results.late, clicks(&[(1, 2)]));
+
+    let results = driver.finish_stream().await.unwrap();
+    assert_eq!(fired::<u64>(&results), vec![(1, 30, 40, 1)]);
+
+    stop_workers(driver, handles).await;
+}
+
+#[test]
+fn test_window_state_survives_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let records: Vec<StreamRecord> = [(7u64, 1u64, 2u64), (7, 6, 3), (8, 9, 5)]
+        .iter()
+        .map(|&(key, ts, v)| StreamRecord::new(enc(&key), ts, enc(&v)))
+        .collect();
+    let sliding = WindowSpec::Sliding { size: 10, slide: 5 };
+
+    {
+        let state = RocksDbGraphState::open(dir.path()).unwrap();
+        let aggregate = create_window_aggregate("sum_u64", &[]).unwrap();
+        let mut operator = WindowOperator::new(aggregate, sliding, 0);
+        let results = operator.process(&state, records, 10).unwrap();
+        let mut out = fired::<u64>(&results);
+        out.sort();
+        assert_eq!(out, vec![(7, 0, 10, 5), (8, 0, 10, 5)]);
+    }
+
+    // The open [5, 15) windows were kept in RocksDB; the closed [0, 10) ones were dropped.
+    let state = RocksDbGraphState::open(dir.path()).unwrap();
+    let aggregate = create_window_aggregate("sum_u64", &[]).unwrap();
+    let mut operator = WindowOperator::new(aggregate, sliding, 0);
+    let results = operator.process(&state, Vec::new(), 15).unwrap();
+    let mut out = fired::<u64>(&results);
+    out.sort();
+    assert_eq!(out, vec![(7, 5, 15, 3), (8, 5, 15, 5)]);
+    assert_eq!(
+        operator.process(&state, Vec::new(), u64::MAX).unwrap(),
+        WindowResults::default()
+    );
+}
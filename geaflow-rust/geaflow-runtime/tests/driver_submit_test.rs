@@ -4,7 +4,7 @@ use geaflow_runtime::distributed::protocol::{
 };
 use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
 use geaflow_runtime::plan::job_spec::{
-    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, JobMode, JobSpec,
+    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, IdSpec, JobMode, JobSpec, PartitionerSpec,
 };
 use std::net::{Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
@@ -70,6 +70,7 @@ async fn test_submit_job_via_driver_service() {
             edges: FileSource::Csv {
                 path: edges_path.to_string_lossy().to_string(),
             },
+            ids: IdSpec::U64,
         },
         algorithm: AlgorithmSpec::Wcc { iterations: 10 },
         checkpoint: CheckpointSpec {
@@ -77,6 +78,7 @@ async fn test_submit_job_via_driver_service() {
             interval_iters: 0,
             base_dir: "/tmp/geaflow-checkpoints".to_string(),
         },
+        partitioner: PartitionerSpec::Hash,
     };
 
     let stream = loop {
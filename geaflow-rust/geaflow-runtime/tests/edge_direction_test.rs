@@ -0,0 +1,224 @@
+use geaflow_api::function::{
+    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+};
+use geaflow_api::graph::{EdgeDirection, PGraphWindow};
+use geaflow_api::mutation::GraphMutation;
+use geaflow_common::types::Edge;
+use geaflow_runtime::algorithms::wcc::WccAlgorithm;
+use geaflow_runtime::graph::mem_graph::InMemoryGraph;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use geaflow_runtime::state::rocksdb_graph_state::RocksDbGraphState;
+use geaflow_runtime::state::GraphState;
+
+mod common;
+use common::{
+    dec, directed, enc, enc_delta, fetch_sorted, load, run_algorithm, sorted, start_workers,
+    stop_workers, temp_dirs, Graph,
+};
+
+/// Stores `in * 10 + out` each superstep; the first superstep adds 2 -> 1, drops 4 -> 3 and
+/// wakes every vertex for a second look.
+struct DegreeFunction;
+
+impl VertexCentricComputeFunction<u64, u64, u8, ()> for DegreeFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        _messages: &mut dyn Iterator<Item = ()>,
+        ctx: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, ()>,
+    ) {
+        let out = ctx.edges_in_direction(EdgeDirection::Out).count() as u64;
+        let incoming = ctx.in_edges().count() as u64;
+        assert!(ctx.in_edges().all(|e| e.target_id == *vertex_id));
+        assert_eq!(ctx.both_edges().count() as u64, out + incoming);
+        ctx.set_new_vertex_value(incoming * 10 + out);
+        if ctx.iteration() == 1 {
+            ctx.send_message(*vertex_id, ());
+            match vertex_id {
+                2 => ctx.add_edge(2, 1, 0),
+                4 => ctx.remove_edge(4, 3),
+                _ => {}
+            }
+        }
+    }
+}
+
+struct DegreeAlgorithm;
+
+impl VertexCentricComputeAlgorithm<u64, u64, u8, ()> for DegreeAlgorithm {
+    fn name(&self) -> &str {
+        "degree"
+    }
+
+    fn iterations(&self) -> u64 {
+        2
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, u64, u8, ()>> {
+        Box::new(DegreeFunction)
+    }
+}
+
+fn degree_graph() -> Graph<u64> {
+    common::graph(1..=5, |_| 0, directed(&[(1, 2), (1, 3), (2, 3), (4, 3)]))
+}
+
+const DEGREES_AFTER_MUTATION: [(u64, u64); 5] = [(1, 12), (2, 12), (3, 20), (4, 0), (5, 0)];
+
+#[test]
+fn test_in_edges_follow_mutations() {
+    for (vertices, _) in run_algorithm(degree_graph, &DegreeAlgorithm, 3) {
+        assert_eq!(sorted(vertices), DEGREES_AFTER_MUTATION);
+    }
+}
+
+/// Stores the out-degree; 1 gains an edge to 5 in the first superstep.
+#[derive(Clone)]
+struct OutDegreeFunction;
+
+impl VertexCentricComputeFunction<u64, u64, u8, ()> for OutDegreeFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        _messages: &mut dyn Iterator<Item = ()>,
+        ctx: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, ()>,
+    ) {
+        ctx.set_new_vertex_value(ctx.edges().count() as u64);
+        if ctx.iteration() == 1 && *vertex_id == 1 {
+            ctx.add_edge(1, 5, 0);
+            ctx.send_message(1, ());
+        }
+        ctx.vote_to_halt();
+    }
+}
+
+#[test]
+fn test_in_edge_index_built_on_first_read() {
+    let (vertices, edges) = degree_graph();
+    let graph = InMemoryGraph::new(vertices, edges);
+    assert!(!graph.in_edges_indexed());
+    let graph = graph.compute_algorithm(&DegreeAlgorithm, 1);
+    assert!(graph.in_edges_indexed());
+    // Out-edge reads leave the index alone; the added edge drops it.
+    let graph = graph.compute(OutDegreeFunction, 1);
+    assert!(!graph.in_edges_indexed());
+    assert_eq!(sorted(graph.vertices())[0], (1, 3));
+
+    let (vertices, edges) = degree_graph();
+    let graph = PartitionedGraph::new(vertices, edges, 3).compute(OutDegreeFunction, 3);
+    assert!(!graph.in_edges_indexed());
+    let graph = graph.compute_algorithm(&DegreeAlgorithm, 3);
+    assert!(graph.in_edges_indexed());
+    assert_eq!(
+        sorted(graph.vertices()),
+        [(1, 13), (2, 12), (3, 20), (4, 0), (5, 10)]
+    );
+}
+
+/// Chain 1 -> 2 <- 3, pair 4 -> 5 and a lone 6, each edge stored once.
+fn wcc_graph() -> Graph<u64> {
+    common::graph(1..=6, |id| id, directed(&[(1, 2), (3, 2), (4, 5)]))
+}
+
+fn join_delta() -> Vec<GraphMutation<u64, u64, u8>> {
+    vec![GraphMutation::AddEdge(Edge::new(5, 3, 0))]
+}
+
+#[test]
+fn test_wcc_without_doubled_edges() {
+    let algo = WccAlgorithm::new(20);
+    let (vertices, edges) = wcc_graph();
+    let graph = InMemoryGraph::new(vertices, edges).compute_algorithm(&algo, 1);
+    assert_eq!(
+        sorted(graph.vertices()),
+        vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4), (6, 6)]
+    );
+    let joined = graph.compute_incremental(&algo, join_delta(), 1);
+    assert_eq!(
+        sorted(joined.vertices()),
+        vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 6)]
+    );
+
+    let (vertices, edges) = wcc_graph();
+    let graph = PartitionedGraph::new(vertices, edges, 3).compute_algorithm(&algo, 3);
+    let joined = graph.compute_incremental(&algo, join_delta(), 3);
+    assert_eq!(
+        sorted(joined.vertices()),
+        vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 6)]
+    );
+}
+
+#[test]
+fn test_rocksdb_in_edges() {
+    type S = RocksDbGraphState;
+    let dir = tempfile::tempdir().unwrap();
+    let state = RocksDbGraphState::open(dir.path()).unwrap();
+    let edges = vec![Edge::new(1, 3, 7), Edge::new(2, 3, 8), Edge::new(3, 1, 9)];
+    <S as GraphState<u64, u64, u8>>::put_in_edge_batch(&state, &edges).unwrap();
+
+    let srcs = |target: u64| -> Vec<u64> {
+        let mut out: Vec<u64> = <S as GraphState<u64, u64, u8>>::get_in_edges(&state, &target)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.src_id)
+            .collect();
+        out.sort();
+        out
+    };
+    assert_eq!(srcs(3), vec![1, 2]);
+    assert_eq!(srcs(1), vec![3]);
+    assert!(<S as GraphState<u64, u64, u8>>::get_out_edges(&state, &1)
+        .unwrap()
+        .is_empty());
+
+    <S as GraphState<u64, u64, u8>>::remove_in_edges(&state, &1, &3).unwrap();
+    assert_eq!(srcs(3), vec![2]);
+}
+
+#[tokio::test]
+async fn test_distributed_wcc_with_in_edges() {
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    driver
+        .set_algorithm("wcc".to_string(), 20, Vec::new())
+        .await
+        .unwrap();
+    load(&mut driver, &wcc_graph()).await;
+    driver.execute(20).await.unwrap();
+    assert_eq!(
+        fetch_sorted::<u64>(&mut driver).await,
+        vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4), (6, 6)]
+    );
+
+    driver.apply_delta(enc_delta(join_delta())).await.unwrap();
+    driver.execute(20).await.unwrap();
+    assert_eq!(
+        fetch_sorted::<u64>(&mut driver).await,
+        vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 6)]
+    );
+
+    // Removing 3 drops 3 -> 2 from 2's in-edges on whichever worker owns 2.
+    driver
+        .apply_delta(vec![GraphMutation::RemoveVertex(enc(&3u64))])
+        .await
+        .unwrap();
+    driver.execute(20).await.unwrap();
+    assert_eq!(
+        fetch_sorted::<u64>(&mut driver).await,
+        vec![(1, 1), (2, 1), (4, 4), (5, 4), (6, 6)]
+    );
+
+    stop_workers(driver, handles).await;
+
+    let mut srcs: Vec<u64> = Vec::new();
+    for dir in &dirs {
+        let state = RocksDbGraphState::open(dir.path()).unwrap();
+        let in_edges = <RocksDbGraphState as GraphState<Vec<u8>, Vec<u8>, Vec<u8>>>::get_in_edges(
+            &state,
+            &enc(&2u64),
+        )
+        .unwrap();
+        srcs.extend(in_edges.iter().map(|e| dec::<u64>(&e.src_id)));
+    }
+    assert_eq!(srcs, vec![1]);
+}
@@ -0,0 +1,144 @@
+use geaflow_api::window::{
+    EventTimeWindow, SessionWindow, SlidingWindow, TimeTumblingWindow, TimeWindow,
+    WatermarkStrategy,
+};
+use geaflow_runtime::stream::event_time::LocalTimedStream;
+use geaflow_runtime::stream::LocalStream;
+
+fn w(start: u64, end: u64) -> TimeWindow {
+    TimeWindow::new(start, end)
+}
+
+/// Records are their own timestamps.
+fn timed(records: Vec<u64>, max_delay: u64) -> LocalTimedStream<u64> {
+    LocalStream::from_vec(records).assign_timestamps(
+        |ts: &u64| *ts,
+        WatermarkStrategy::bounded_out_of_orderness(max_delay),
+    )
+}
+
+#[test]
+fn test_tumbling_event_time_with_late_side_output() {
+    let out = timed(vec![1, 12, 4, 16, 3, 25, 2, 31], 5)
+        .window(TimeTumblingWindow { size: 10 })
+        .collect_windows();
+    assert_eq!(
+        out.windows,
+        vec![
+            (w(0, 10), vec![1, 4]),
+            (w(10, 20), vec![12, 16]),
+            (w(20, 30), vec![25]),
+            (w(30, 40), vec![31]),
+        ]
+    );
+    assert_eq!(out.late, vec![3, 2]);
+}
+
+#[test]
+fn test_allowed_lateness_refires_window() {
+    let out = timed(vec![1, 12, 4, 16, 3, 25, 2, 31], 5)
+        .window(TimeTumblingWindow { size: 10 })
+        .allowed_lateness(5)
+        .collect_windows();
+    assert_eq!(
+        out.windows,
+        vec![
+            (w(0, 10), vec![1, 4]),
+            (w(0, 10), vec![1, 3, 4]),
+            (w(10, 20), vec![12, 16]),
+            (w(20, 30), vec![25]),
+            (w(30, 40), vec![31]),
+        ]
+    );
+    assert_eq!(out.late, vec![2]);
+}
+
+#[test]
+fn test_sliding_windows() {
+    let sliding = SlidingWindow { size: 10, slide: 5 };
+    assert_eq!(sliding.assign_windows(7), vec![w(0, 10), w(5, 15)]);
+    assert_eq!(sliding.assign_windows(3), vec![w(0, 10)]);
+
+    let out = timed(vec![1, 7, 12], 0).window(sliding).collect_windows();
+    assert_eq!(
+        out.windows,
+        vec![
+            (w(0, 10), vec![1, 7]),
+            (w(5, 15), vec![7, 12]),
+            (w(10, 20), vec![12]),
+        ]
+    );
+    assert!(out.late.is_empty());
+}
+
+#[test]
+fn test_zero_sized_windows_hold_one_tick() {
+    for slide in [0, 1] {
+        let sliding = SlidingWindow { size: 0, slide };
+        assert_eq!(sliding.assign_windows(7), vec![w(7, 8)]);
+    }
+    assert_eq!(
+        TimeTumblingWindow { size: 0 }.assign_windows(7),
+        vec![w(7, 8)]
+    );
+}
+
+#[test]
+fn test_windows_near_u64_max_clamp_their_end() {
+    let max = u64::MAX;
+    let sliding = SlidingWindow { size: 10, slide: 5 };
+    assert_eq!(
+        sliding.assign_windows(max - 3),
+        vec![w(max - 10, max), w(max - 5, max)]
+    );
+    assert_eq!(
+        SlidingWindow {
+            size: max,
+            slide: max
+        }
+        .assign_windows(max - 1),
+        vec![w(0, max)]
+    );
+    assert_eq!(
+        TimeTumblingWindow { size: 10 }.assign_windows(max - 3),
+        vec![w(max - 5, max)]
+    );
+    assert_eq!(
+        SessionWindow { gap: 5 }.assign_windows(max - 3),
+        vec![w(max - 3, max)]
+    );
+}
+
+#[test]
+fn test_session_windows_merge() {
+    let out = timed(vec![1, 3, 20, 10, 22], 0)
+        .window(SessionWindow { gap: 5 })
+        .collect_windows();
+    assert_eq!(
+        out.windows,
+        vec![(w(1, 8), vec![1, 3]), (w(20, 27), vec![20, 22])]
+    );
+    assert_eq!(out.late, vec![10]);
+
+    // 6 arrives late enough to be kept and bridges the sessions around 1 and 11.
+    let out = timed(vec![1, 11, 6], 20)
+        .window(SessionWindow { gap: 6 })
+        .collect_windows();
+    assert_eq!(out.windows, vec![(w(1, 17), vec![1, 6, 11])]);
+}
+
+#[test]
+fn test_windows_independent_of_arrival_order() {
+    let window = || TimeTumblingWindow { size: 10 };
+    let a = timed(vec![3, 1, 14, 12, 8], 10)
+        .window(window())
+        .collect_windows();
+    let b = timed(vec![12, 8, 1, 14, 3], 10)
+        .window(window())
+        .collect_windows();
+    assert_eq!(a, b);
+    assert_eq!(
+        a.windows,
+        vec![(w(0, 10), vec![1, 3, 8]), (w(10, 20), vec![12, 14])]
+    );
+}
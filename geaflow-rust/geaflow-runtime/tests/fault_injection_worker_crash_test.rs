@@ -1,7 +1,7 @@
 use geaflow_runtime::distributed::driver::DistributedDriver;
 use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
 use geaflow_runtime::plan::job_spec::{
-    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, JobMode, JobSpec,
+    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, IdSpec, JobMode, JobSpec, PartitionerSpec,
 };
 use geaflow_runtime::scheduler::cycle_scheduler::CycleScheduler;
 use std::net::{Ipv4Addr, SocketAddr};
@@ -50,6 +50,7 @@ async fn test_fault_injection_worker_crash_fail_fast() {
             edges: FileSource::Csv {
                 path: edges_path.to_string_lossy().to_string(),
             },
+            ids: IdSpec::U64,
         },
         algorithm: AlgorithmSpec::Wcc { iterations: 100 },
         checkpoint: CheckpointSpec {
@@ -57,6 +58,7 @@ async fn test_fault_injection_worker_crash_fail_fast() {
             interval_iters: 0,
             base_dir: "/tmp/geaflow-checkpoints".to_string(),
         },
+        partitioner: PartitionerSpec::Hash,
     };
 
     let mut driver = DistributedDriver::connect(&[w1_addr, w2_addr])
@@ -0,0 +1,134 @@
+use geaflow_api::function::{
+    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+};
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::state::rocksdb_graph_state::RocksDbGraphState;
+use geaflow_runtime::state::GraphState;
+
+mod common;
+use common::{directed, run_algorithm, run_function, sorted, Graph};
+
+#[derive(Clone)]
+struct RewireFunction;
+
+impl VertexCentricComputeFunction<u64, u64, u8, u64> for RewireFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        _message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
+    ) {
+        // Rewires once; the vertices it adds compute in the next superstep and halt too.
+        context.vote_to_halt();
+        if context.iteration() > 1 {
+            return;
+        }
+        if context.edges().next().is_none() {
+            context.remove_vertex(*vertex_id);
+        }
+        match vertex_id {
+            1 => {
+                context.add_vertex(6, 60);
+                context.add_vertex(3, 999);
+            }
+            3 => {
+                context.remove_vertex(2);
+                context.add_vertex(2, 20);
+            }
+            4 => {
+                context.remove_edge(4, 1);
+                context.add_edge(4, 6, 0);
+            }
+            _ => {}
+        }
+    }
+}
+
+struct RewireAlgorithm;
+
+impl VertexCentricComputeAlgorithm<u64, u64, u8, u64> for RewireAlgorithm {
+    fn name(&self) -> &str {
+        "rewire"
+    }
+
+    fn iterations(&self) -> u64 {
+        5
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, u64, u8, u64>> {
+        Box::new(RewireFunction)
+    }
+}
+
+fn graph() -> Graph<u64> {
+    common::graph(
+        1..=5,
+        |id| id * 10,
+        directed(&[(1, 2), (2, 3), (3, 1), (4, 1)]),
+    )
+}
+
+fn assert_rewired((vertices, edges): Graph<u64>) {
+    assert_eq!(
+        sorted(vertices),
+        vec![(1, 10), (2, 20), (3, 30), (4, 40), (6, 60)]
+    );
+    let mut edges: Vec<(u64, u64)> = edges.into_iter().map(|e| (e.src_id, e.target_id)).collect();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 2), (3, 1), (4, 6)]);
+}
+
+#[test]
+fn test_graphs_apply_mutations() {
+    for result in run_algorithm(graph, &RewireAlgorithm, 3) {
+        assert_rewired(result);
+    }
+    for result in run_function(graph, RewireFunction, 3) {
+        assert_rewired(result);
+    }
+}
+
+#[test]
+fn test_rocksdb_state_removes_vertices_and_edges() {
+    let dir = tempfile::tempdir().unwrap();
+    let state = RocksDbGraphState::open(dir.path()).unwrap();
+    type S = RocksDbGraphState;
+
+    <S as GraphState<u64, u64, u8>>::put_vertex_batch(
+        &state,
+        &[Vertex::new(1, 10), Vertex::new(2, 20)],
+    )
+    .unwrap();
+    <S as GraphState<u64, u64, u8>>::put_edge_batch(
+        &state,
+        &[
+            Edge::new(1, 2, 0),
+            Edge::new(1, 3, 0),
+            Edge::new(1, 2, 1),
+            Edge::new(2, 1, 0),
+        ],
+    )
+    .unwrap();
+    <S as GraphState<u64, u64, u8>>::update_active_batch(&state, &[1, 2], &[]).unwrap();
+
+    <S as GraphState<u64, u64, u8>>::remove_edges(&state, &1, &2).unwrap();
+    let out: Vec<u64> = <S as GraphState<u64, u64, u8>>::get_out_edges(&state, &1)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.target_id)
+        .collect();
+    assert_eq!(out, vec![3]);
+
+    <S as GraphState<u64, u64, u8>>::remove_vertex(&state, &2).unwrap();
+    assert_eq!(
+        <S as GraphState<u64, u64, u8>>::get_vertex(&state, &2).unwrap(),
+        None
+    );
+    assert!(<S as GraphState<u64, u64, u8>>::get_out_edges(&state, &2)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        <S as GraphState<u64, u64, u8>>::list_active_vertex_ids(&state).unwrap(),
+        vec![1]
+    );
+}
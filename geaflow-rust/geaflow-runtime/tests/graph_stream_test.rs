@@ -0,0 +1,130 @@
+use geaflow_api::graph::PGraphWindow;
+use geaflow_api::mutation::GraphMutation;
+use geaflow_api::stream::PStream;
+use geaflow_api::window::{SizeTumblingWindow, TimeTumblingWindow, TimeWindow, WatermarkStrategy};
+use geaflow_common::types::Edge;
+use geaflow_runtime::algorithms::wcc::WccAlgorithm;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use geaflow_runtime::stream::graph_stream::SnapshotMode;
+use geaflow_runtime::stream::LocalStream;
+
+type Update = GraphMutation<u64, u64, u8>;
+
+fn edge(src: u64, target: u64) -> Update {
+    GraphMutation::AddEdge(Edge::new(src, target, 0))
+}
+
+fn components(graph: &PartitionedGraph<u64, u64, u8>) -> Vec<(u64, u64)> {
+    let mut out: Vec<(u64, u64)> = graph
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id, v.value))
+        .collect();
+    out.sort();
+    out
+}
+
+/// Timestamped edge events, out of order within the first window.
+fn events() -> Vec<(u64, Update)> {
+    vec![
+        (3, edge(3, 4)),
+        (1, edge(1, 2)),
+        (12, edge(2, 3)),
+        (25, edge(5, 6)),
+    ]
+}
+
+fn run(mode: SnapshotMode) -> Vec<(TimeWindow, Vec<(u64, u64)>)> {
+    LocalStream::from_vec(events())
+        .assign_timestamps(
+            |(ts, _): &(u64, Update)| *ts,
+            WatermarkStrategy::bounded_out_of_orderness(5),
+        )
+        .window(TimeTumblingWindow { size: 10 })
+        .collect_windows()
+        .map(|(_, update)| update)
+        .into_graph(mode, 2)
+        .compute_algorithm(&WccAlgorithm::new(20), |id: &u64| *id)
+        .collect()
+        .into_iter()
+        .map(|s| (s.window, components(&s.graph)))
+        .collect()
+}
+
+#[test]
+fn test_accumulated_snapshots() {
+    assert_eq!(
+        run(SnapshotMode::Accumulate),
+        vec![
+            (TimeWindow::new(0, 10), vec![(1, 1), (2, 1), (3, 3), (4, 3)]),
+            (
+                TimeWindow::new(10, 20),
+                vec![(1, 1), (2, 1), (3, 1), (4, 1)]
+            ),
+            (
+                TimeWindow::new(20, 30),
+                vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 5), (6, 5)]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_fresh_snapshots() {
+    assert_eq!(
+        run(SnapshotMode::Fresh),
+        vec![
+            (TimeWindow::new(0, 10), vec![(1, 1), (2, 1), (3, 3), (4, 3)]),
+            (TimeWindow::new(10, 20), vec![(2, 2), (3, 2)]),
+            (TimeWindow::new(20, 30), vec![(5, 5), (6, 5)]),
+        ]
+    );
+}
+
+#[test]
+fn test_refired_window_accumulates_once() {
+    let snapshots = LocalStream::from_vec(vec![(1, edge(1, 2)), (12, edge(3, 4)), (5, edge(2, 3))])
+        .assign_timestamps(
+            |(ts, _): &(u64, Update)| *ts,
+            WatermarkStrategy::bounded_out_of_orderness(0),
+        )
+        .window(TimeTumblingWindow { size: 10 })
+        .allowed_lateness(20)
+        .collect_windows()
+        .map(|(_, update)| update)
+        .into_graph(SnapshotMode::Accumulate, 2)
+        .compute_algorithm(&WccAlgorithm::new(20), |id: &u64| *id)
+        .collect();
+
+    let edge_counts: Vec<usize> = snapshots.iter().map(|s| s.graph.edges().len()).collect();
+    assert_eq!(edge_counts, vec![1, 2, 3]);
+    assert_eq!(
+        components(&snapshots[2].graph),
+        vec![(1, 1), (2, 1), (3, 1), (4, 1)]
+    );
+}
+
+#[test]
+fn test_count_windows_into_graph() {
+    let updates = vec![
+        edge(1, 2),
+        edge(3, 4),
+        GraphMutation::RemoveEdge {
+            src_id: 1,
+            target_id: 2,
+        },
+        edge(4, 5),
+    ];
+    let snapshots = LocalStream::from_vec(updates)
+        .window_tumbling(SizeTumblingWindow { size: 2 })
+        .into_graph(SnapshotMode::Accumulate, 3)
+        .compute_algorithm(&WccAlgorithm::new(20), |id: &u64| *id)
+        .collect();
+
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[1].window, 1);
+    assert_eq!(
+        components(&snapshots[1].graph),
+        vec![(1, 1), (2, 2), (3, 3), (4, 3), (5, 3)]
+    );
+}
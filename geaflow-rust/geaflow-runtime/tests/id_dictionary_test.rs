@@ -0,0 +1,176 @@
+use geaflow_runtime::distributed::driver::DistributedDriver;
+use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
+use geaflow_runtime::io::file::{read_edges_dict_u8, read_vertices_dict};
+use geaflow_runtime::io::id_dictionary::IdDictionary;
+use geaflow_runtime::plan::job_spec::{
+    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, IdSpec, JobMode, JobSpec, PartitionerSpec,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+mod common;
+use common::{dec, enc, free_local_addr, load, start_workers, stop_workers, temp_dirs};
+
+fn spawn_workers(dirs: &[tempfile::TempDir]) -> Vec<SocketAddr> {
+    let addrs: Vec<SocketAddr> = dirs.iter().map(|_| free_local_addr()).collect();
+    for (addr, dir) in addrs.iter().zip(dirs) {
+        tokio::spawn(run_worker(WorkerConfig {
+            listen_addr: *addr,
+            state_dir: PathBuf::from(dir.path()),
+            master_addr: None,
+        }));
+    }
+    addrs
+}
+
+/// Two components of account ids: alice-bob-carol and dave-erin.
+fn write_graph(dir: &Path) -> (PathBuf, PathBuf) {
+    let vertices = dir.join("v.csv");
+    let edges = dir.join("e.csv");
+    std::fs::write(&vertices, "alice\nbob\ncarol\ndave\nerin\n").unwrap();
+    std::fs::write(
+        &edges,
+        "alice,bob\nbob,alice\nbob,carol\ncarol,bob\ndave,erin\nerin,dave\n",
+    )
+    .unwrap();
+    (vertices, edges)
+}
+
+#[test]
+fn test_dictionary_interns_external_ids() {
+    let mut dict = IdDictionary::new();
+    assert_eq!(dict.intern("acct-7"), 0);
+    assert_eq!(dict.intern("acct-3"), 1);
+    assert_eq!(dict.intern("acct-7"), 0);
+    assert_eq!(dict.get("acct-3"), Some(1));
+    assert_eq!(dict.get("acct-9"), None);
+    assert_eq!(dict.external(1), Some("acct-3"));
+    assert_eq!(dict.external(2), None);
+    assert_eq!(
+        dict.iter().collect::<Vec<_>>(),
+        vec![(0, "acct-7"), (1, "acct-3")]
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let (vertices, edges) = write_graph(dir.path());
+    let mut dict = IdDictionary::new();
+    let edges = read_edges_dict_u8(&edges, &mut dict, 0).unwrap();
+    let vertices = read_vertices_dict(&vertices, &mut dict, |id| id).unwrap();
+    assert_eq!(dict.len(), 5);
+    assert_eq!(edges.len(), 6);
+    assert_eq!((edges[2].src_id, edges[2].target_id), (1, 2));
+    let ids: Vec<(u64, u64)> = vertices.iter().map(|v| (v.id, v.value)).collect();
+    assert_eq!(ids, vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+}
+
+#[tokio::test]
+async fn test_run_job_returns_external_ids() {
+    let data = tempfile::tempdir().unwrap();
+    let checkpoints = tempfile::tempdir().unwrap();
+    let (vertices, edges) = write_graph(data.path());
+    let job = JobSpec {
+        job_id: "accounts".to_string(),
+        name: "wcc".to_string(),
+        mode: JobMode::Distributed,
+        graph: GraphSpec {
+            vertices: FileSource::Csv {
+                path: vertices.to_string_lossy().to_string(),
+            },
+            edges: FileSource::Csv {
+                path: edges.to_string_lossy().to_string(),
+            },
+            ids: IdSpec::Dictionary,
+        },
+        algorithm: AlgorithmSpec::Wcc { iterations: 5 },
+        checkpoint: CheckpointSpec {
+            enabled: false,
+            interval_iters: 0,
+            base_dir: checkpoints.path().to_string_lossy().to_string(),
+        },
+        partitioner: PartitionerSpec::Hash,
+    };
+
+    let dirs = temp_dirs(2);
+    let addrs = spawn_workers(&dirs);
+    let components: HashMap<String, String> = DistributedDriver::run_job(&addrs, &job)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, value)| (dec(&id), dec(&value)))
+        .collect();
+    // Labels are the external id of each component's first vertex.
+    assert_eq!(components.len(), 5);
+    assert_eq!(components["alice"], "alice");
+    assert_eq!(components["bob"], "alice");
+    assert_eq!(components["carol"], "alice");
+    assert_eq!(components["dave"], "dave");
+    assert_eq!(components["erin"], "dave");
+}
+
+#[tokio::test]
+async fn test_dump_writes_external_ids() {
+    let data = tempfile::tempdir().unwrap();
+    let (vertices, edges) = write_graph(data.path());
+    let mut dict = IdDictionary::new();
+    let edges = read_edges_dict_u8(&edges, &mut dict, 0).unwrap();
+    let vertices = read_vertices_dict(&vertices, &mut dict, |id| id).unwrap();
+
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    load(&mut driver, &(vertices, edges)).await;
+    driver.load_id_dictionary(&dict).await.unwrap();
+    driver
+        .set_algorithm("wcc".to_string(), 5, Vec::new())
+        .await
+        .unwrap();
+    driver.execute(5).await.unwrap();
+
+    let ids = driver.fetch_external_ids().await.unwrap();
+    assert_eq!(ids.len(), 5);
+    assert_eq!(ids[&enc(&3u64)], "dave");
+
+    let mut fetched: Vec<(String, String)> = driver
+        .fetch_vertices_by_external_id()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, value)| (id, dec(&value)))
+        .collect();
+    fetched.sort();
+    assert_eq!(
+        fetched,
+        vec![
+            ("alice".to_string(), "alice".to_string()),
+            ("bob".to_string(), "alice".to_string()),
+            ("carol".to_string(), "alice".to_string()),
+            ("dave".to_string(), "dave".to_string()),
+            ("erin".to_string(), "dave".to_string()),
+        ]
+    );
+
+    let out = tempfile::tempdir().unwrap();
+    let files = driver.dump_vertices_csv(out.path(), "wcc").await.unwrap();
+    let mut rows: Vec<String> = files
+        .iter()
+        .flat_map(|f| {
+            std::fs::read_to_string(f)
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            "alice,alice",
+            "bob,alice",
+            "carol,alice",
+            "dave,dave",
+            "erin,dave"
+        ]
+    );
+    stop_workers(driver, handles).await;
+}
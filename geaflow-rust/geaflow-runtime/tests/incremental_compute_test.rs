@@ -0,0 +1,132 @@
+use geaflow_api::graph::PGraphWindow;
+use geaflow_api::mutation::GraphMutation;
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::wcc::WccAlgorithm;
+use geaflow_runtime::distributed::driver::DistributedDriver;
+use geaflow_runtime::graph::mem_graph::InMemoryGraph;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use tempfile::TempDir;
+
+mod common;
+use common::{
+    enc_delta, fetch_sorted, load, sorted, start_workers, stop_workers, temp_dirs, undirected,
+    Graph, WorkerHandle,
+};
+
+type Delta = Vec<GraphMutation<u64, u64, u8>>;
+
+/// Chain 1-2-3-4 and pair 5-6.
+fn graph() -> Graph<u64> {
+    common::graph(
+        1..=6,
+        |id| id,
+        undirected(&[(1, 2), (2, 3), (3, 4), (5, 6)]),
+    )
+}
+
+/// Splits the chain between 2 and 3 and hangs a new vertex 0 off 4.
+fn split_delta() -> Delta {
+    vec![
+        GraphMutation::RemoveEdge {
+            src_id: 2,
+            target_id: 3,
+        },
+        GraphMutation::RemoveEdge {
+            src_id: 3,
+            target_id: 2,
+        },
+        GraphMutation::AddVertex(Vertex::new(0, 0)),
+        GraphMutation::AddEdge(Edge::new(0, 4, 0)),
+        GraphMutation::AddEdge(Edge::new(4, 0, 0)),
+    ]
+}
+
+/// Joins the two components of the original graph.
+fn join_delta() -> Delta {
+    undirected(&[(4, 5)])
+        .into_iter()
+        .map(GraphMutation::AddEdge)
+        .collect()
+}
+
+#[test]
+fn test_incremental_wcc_in_memory() {
+    let algo = WccAlgorithm::new(20);
+    let (vertices, edges) = graph();
+    let computed = InMemoryGraph::new(vertices, edges).compute_algorithm(&algo, 1);
+    assert_eq!(
+        sorted(computed.vertices()),
+        vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 5), (6, 5)]
+    );
+
+    let split = computed.compute_incremental(&algo, split_delta(), 1);
+    assert_eq!(
+        sorted(split.vertices()),
+        vec![(0, 0), (1, 1), (2, 1), (3, 0), (4, 0), (5, 5), (6, 5)]
+    );
+    assert_eq!(split.edges().len(), 8);
+
+    let joined = split.compute_incremental(&algo, join_delta(), 1);
+    assert_eq!(
+        sorted(joined.vertices()),
+        vec![(0, 0), (1, 1), (2, 1), (3, 0), (4, 0), (5, 0), (6, 0)]
+    );
+}
+
+#[test]
+fn test_incremental_wcc_partitioned() {
+    let algo = WccAlgorithm::new(20);
+    let (vertices, edges) = graph();
+    let computed = PartitionedGraph::new(vertices, edges, 3).compute_algorithm(&algo, 3);
+
+    let split = computed.compute_incremental(&algo, split_delta(), 3);
+    assert_eq!(
+        sorted(split.vertices()),
+        vec![(0, 0), (1, 1), (2, 1), (3, 0), (4, 0), (5, 5), (6, 5)]
+    );
+
+    let joined = split.compute_incremental(&algo, join_delta(), 3);
+    assert_eq!(
+        sorted(joined.vertices()),
+        vec![(0, 0), (1, 1), (2, 1), (3, 0), (4, 0), (5, 0), (6, 0)]
+    );
+}
+
+/// Starts one worker per state dir and connects a driver with WCC set.
+async fn start(dirs: &[TempDir]) -> (DistributedDriver, Vec<WorkerHandle>) {
+    let (mut driver, handles) = start_workers(dirs).await;
+    driver
+        .set_algorithm("wcc".to_string(), 20, Vec::new())
+        .await
+        .unwrap();
+    (driver, handles)
+}
+
+async fn finish(mut driver: DistributedDriver, handles: Vec<WorkerHandle>) -> Vec<(u64, u64)> {
+    let vertices = fetch_sorted(&mut driver).await;
+    stop_workers(driver, handles).await;
+    vertices
+}
+
+#[tokio::test]
+async fn test_incremental_wcc_distributed_from_stored_state() {
+    let dirs = temp_dirs(2);
+
+    let (mut driver, handles) = start(&dirs).await;
+    load(&mut driver, &graph()).await;
+    driver.execute(20).await.unwrap();
+    driver.apply_delta(enc_delta(split_delta())).await.unwrap();
+    driver.execute(20).await.unwrap();
+    assert_eq!(
+        finish(driver, handles).await,
+        vec![(0, 0), (1, 1), (2, 1), (3, 0), (4, 0), (5, 5), (6, 5)]
+    );
+
+    let (mut driver, handles) = start(&dirs).await;
+    driver.apply_delta(enc_delta(join_delta())).await.unwrap();
+    driver.execute(20).await.unwrap();
+    assert_eq!(
+        finish(driver, handles).await,
+        vec![(0, 0), (1, 1), (2, 1), (3, 0), (4, 0), (5, 0), (6, 0)]
+    );
+}
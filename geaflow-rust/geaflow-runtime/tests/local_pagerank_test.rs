@@ -1,6 +1,6 @@
 use geaflow_api::graph::PGraphWindow;
 use geaflow_common::types::{Edge, Vertex};
-use geaflow_runtime::algorithms::pagerank::PageRankAlgorithm;
+use geaflow_runtime::algorithms::pagerank::{PageRankAlgorithm, PAGERANK_DELTA};
 use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
 
 #[test]
@@ -19,3 +19,77 @@ fn test_local_pagerank_two_cycle() {
     assert!((vertices[0].value - 1.0).abs() < 1e-9);
     assert!((vertices[1].value - 1.0).abs() < 1e-9);
 }
+
+const ALPHA: f64 = 0.85;
+
+/// Vertex 5 has no out-edges and 6 no edges at all, so both hold dangling rank.
+fn dangling_edges() -> Vec<(u64, u64)> {
+    vec![(1, 2), (1, 3), (2, 3), (3, 1), (4, 3), (4, 5)]
+}
+
+/// `steps` rounds of power iteration with dangling rank spread evenly, from 1/N everywhere.
+fn reference(vertices: u64, edges: &[(u64, u64)], steps: usize) -> Vec<f64> {
+    let n = vertices as f64;
+    let mut out_degree = vec![0.0; vertices as usize + 1];
+    for (s, _) in edges {
+        out_degree[*s as usize] += 1.0;
+    }
+    let mut ranks = vec![1.0 / n; vertices as usize + 1];
+    ranks[0] = 0.0;
+    for _ in 0..steps {
+        let dangling: f64 = (1..=vertices as usize)
+            .filter(|v| out_degree[*v] == 0.0)
+            .map(|v| ranks[v])
+            .sum();
+        let mut next = vec![(1.0 - ALPHA) / n + ALPHA * dangling / n; vertices as usize + 1];
+        next[0] = 0.0;
+        for (s, t) in edges {
+            next[*t as usize] += ALPHA * ranks[*s as usize] / out_degree[*s as usize];
+        }
+        ranks = next;
+    }
+    ranks
+}
+
+fn run(algo: &PageRankAlgorithm, parallelism: usize) -> (Vec<f64>, Option<f64>) {
+    let vertices = (1..=6u64).map(|id| Vertex::new(id, 1.0 / 6.0)).collect();
+    let edges = dangling_edges()
+        .into_iter()
+        .map(|(s, t)| Edge::new(s, t, 0u8))
+        .collect();
+    let result =
+        PartitionedGraph::new(vertices, edges, parallelism).compute_algorithm(algo, parallelism);
+    let residual = result.aggregated().get(PAGERANK_DELTA).map(|v| v.as_f64());
+    let mut ranks = vec![0.0; 7];
+    for v in result.vertices() {
+        ranks[v.id as usize] = v.value;
+    }
+    (ranks, residual)
+}
+
+#[test]
+fn test_local_pagerank_redistributes_dangling_rank() {
+    // The first superstep only sends, so 21 supersteps make 20 rounds.
+    let expected = reference(6, &dangling_edges(), 20);
+    for parallelism in [1, 3] {
+        let (ranks, residual) = run(&PageRankAlgorithm::new(21, ALPHA), parallelism);
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-12, "{ranks:?}");
+        for (rank, want) in ranks.iter().zip(&expected) {
+            assert!((rank - want).abs() < 1e-12, "{ranks:?} != {expected:?}");
+        }
+        assert!(residual.unwrap() > 0.0);
+    }
+}
+
+#[test]
+fn test_local_pagerank_stops_at_tolerance() {
+    let converged = reference(6, &dangling_edges(), 500);
+    let algo = PageRankAlgorithm::new(1000, ALPHA).with_tolerance(1e-8);
+    let (ranks, residual) = run(&algo, 2);
+    let residual = residual.unwrap();
+    // Running all 1000 supersteps would leave no measurable change.
+    assert!(residual > 0.0 && residual < 1e-8, "{residual}");
+    for (rank, want) in ranks.iter().zip(&converged) {
+        assert!((rank - want).abs() < 1e-7, "{ranks:?} != {converged:?}");
+    }
+}
@@ -0,0 +1,153 @@
+use geaflow_api::aggregator::{AggregateOp, AggregateValue, AggregateValues, AggregatorSpec};
+use geaflow_api::function::{
+    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+};
+use geaflow_api::master::{MasterCompute, MasterContext, MasterState, MasterStep};
+use geaflow_common::types::Vertex;
+
+mod common;
+use common::{directed, run_algorithm, Graph};
+
+const RING: u64 = 12;
+
+fn ring() -> Graph<i64> {
+    let pairs: Vec<(u64, u64)> = (0..RING).map(|id| (id, (id + 1) % RING)).collect();
+    common::graph(0..RING, |_| -1, directed(&pairs))
+}
+
+/// Phase 0 counts vertices, phase 1 writes the broadcast total into every vertex.
+struct TwoPhaseFunction;
+
+impl VertexCentricComputeFunction<u64, i64, u8, u64> for TwoPhaseFunction {
+    fn compute(
+        &mut self,
+        _vertex_id: &u64,
+        _message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, i64, u8, u64>,
+    ) {
+        match context.phase() {
+            0 => context.aggregate("vertices", AggregateValue::Long(1)),
+            _ => {
+                let total = context.get_broadcast("total").unwrap().as_i64();
+                context.set_new_vertex_value(total);
+            }
+        }
+        context.vote_to_halt();
+    }
+
+    fn aggregators(&self) -> Vec<AggregatorSpec> {
+        vec![AggregatorSpec::new("vertices", AggregateOp::Count)]
+    }
+}
+
+struct TwoPhaseAlgorithm;
+
+impl VertexCentricComputeAlgorithm<u64, i64, u8, u64> for TwoPhaseAlgorithm {
+    fn name(&self) -> &str {
+        "two_phase"
+    }
+
+    fn iterations(&self) -> u64 {
+        10
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, i64, u8, u64>> {
+        Box::new(TwoPhaseFunction)
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(|ctx: &mut MasterContext| {
+            if ctx.phase() == 0 && ctx.iteration() > 1 && !ctx.messages_pending() {
+                let total = ctx.get_aggregated("vertices").unwrap();
+                ctx.broadcast("total", total);
+                ctx.set_phase(1);
+            }
+        }))
+    }
+}
+
+/// Keeps messaging around the ring forever; only the master stops it.
+struct EndlessFunction;
+
+impl VertexCentricComputeFunction<u64, i64, u8, u64> for EndlessFunction {
+    fn compute(
+        &mut self,
+        _vertex_id: &u64,
+        _message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, i64, u8, u64>,
+    ) {
+        context.set_new_vertex_value(context.iteration() as i64);
+        let edges: Vec<_> = context.edges().cloned().collect();
+        for e in edges {
+            context.send_message(e.target_id, 0);
+        }
+    }
+}
+
+struct EndlessAlgorithm;
+
+impl VertexCentricComputeAlgorithm<u64, i64, u8, u64> for EndlessAlgorithm {
+    fn name(&self) -> &str {
+        "endless"
+    }
+
+    fn iterations(&self) -> u64 {
+        100
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, i64, u8, u64>> {
+        Box::new(EndlessFunction)
+    }
+
+    fn master_compute(&self) -> Option<Box<dyn MasterCompute>> {
+        Some(Box::new(|ctx: &mut MasterContext| {
+            if ctx.iteration() > 3 {
+                ctx.halt();
+            }
+        }))
+    }
+}
+
+fn assert_all(vertices: Vec<Vertex<u64, i64>>, expected: i64) {
+    assert_eq!(vertices.len(), RING as usize);
+    for v in vertices {
+        assert_eq!(v.value, expected, "vertex {}", v.id);
+    }
+}
+
+#[test]
+fn test_master_phase_change_wakes_vertices() {
+    for (vertices, _) in run_algorithm(ring, &TwoPhaseAlgorithm, 3) {
+        assert_all(vertices, RING as i64);
+    }
+}
+
+#[test]
+fn test_master_halts_job() {
+    for (vertices, _) in run_algorithm(ring, &EndlessAlgorithm, 3) {
+        assert_all(vertices, 3);
+    }
+}
+
+#[test]
+fn test_master_state_without_master() {
+    let mut state = MasterState::default();
+    let aggregated = AggregateValues::new();
+    assert_eq!(
+        state.before_superstep(&mut None, 1, false, 0, &aggregated),
+        MasterStep::Continue { wake_all: false }
+    );
+    assert_eq!(
+        state.before_superstep(&mut None, 2, true, 0, &aggregated),
+        MasterStep::Continue { wake_all: false }
+    );
+    // Vertices that have not voted to halt keep the job going without messages.
+    assert_eq!(
+        state.before_superstep(&mut None, 3, false, 2, &aggregated),
+        MasterStep::Continue { wake_all: false }
+    );
+    assert_eq!(
+        state.before_superstep(&mut None, 4, false, 0, &aggregated),
+        MasterStep::Halt
+    );
+}
@@ -0,0 +1,172 @@
+use geaflow_api::function::{
+    MessageCombiner, VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext,
+    VertexCentricComputeFunction,
+};
+use geaflow_api::graph::PGraphWindow;
+use geaflow_runtime::distributed::algorithm::MinU64Combiner;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use geaflow_runtime::shuffle::{DriverShuffle, Inboxes, MessageShuffle};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+use common::{directed, run_algorithm, Graph};
+
+struct InDegreeAlgorithm {
+    max_inbox: Arc<AtomicUsize>,
+}
+
+struct InDegreeFunction {
+    max_inbox: Arc<AtomicUsize>,
+}
+
+impl VertexCentricComputeFunction<u64, u64, u8, u64> for InDegreeFunction {
+    fn compute(
+        &mut self,
+        _vertex_id: &u64,
+        message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
+    ) {
+        if context.iteration() == 1 {
+            let edges: Vec<_> = context.edges().cloned().collect();
+            for e in edges {
+                context.send_message(e.target_id, 1);
+            }
+            return;
+        }
+        let msgs: Vec<u64> = message_iterator.collect();
+        self.max_inbox.fetch_max(msgs.len(), Ordering::SeqCst);
+        context.set_new_vertex_value(msgs.into_iter().sum());
+    }
+}
+
+impl VertexCentricComputeAlgorithm<u64, u64, u8, u64> for InDegreeAlgorithm {
+    fn name(&self) -> &str {
+        "in_degree"
+    }
+
+    fn iterations(&self) -> u64 {
+        2
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, u64, u8, u64>> {
+        Box::new(InDegreeFunction {
+            max_inbox: self.max_inbox.clone(),
+        })
+    }
+
+    fn combiner(&self) -> Option<Box<dyn MessageCombiner<u64, u64>>> {
+        Some(Box::new(|a: u64, b: u64| a + b))
+    }
+}
+
+/// Leaves `1..=leaves` all pointing at hub 0.
+fn star_graph<VV: Clone>(leaves: u64, value: VV) -> Graph<VV> {
+    let pairs: Vec<(u64, u64)> = (1..=leaves).map(|id| (id, 0)).collect();
+    common::graph(0..=leaves, |_| value.clone(), directed(&pairs))
+}
+
+#[test]
+fn test_graphs_combine_messages_per_target() {
+    let max_inbox = Arc::new(AtomicUsize::new(0));
+    let algo = InDegreeAlgorithm {
+        max_inbox: max_inbox.clone(),
+    };
+
+    for (vertices, _) in run_algorithm(|| star_graph(100, 0), &algo, 4) {
+        let hub = vertices.into_iter().find(|v| v.id == 0).unwrap();
+        assert_eq!(hub.value, 100);
+    }
+    assert_eq!(max_inbox.load(Ordering::SeqCst), 1);
+}
+
+struct FloatSumAlgorithm;
+
+struct FloatSumFunction;
+
+impl VertexCentricComputeFunction<u64, f64, u8, f64> for FloatSumFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        message_iterator: &mut dyn Iterator<Item = f64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, f64, u8, f64>,
+    ) {
+        if context.iteration() == 1 {
+            // Magnitudes far apart, so the sum depends on the order it is taken in.
+            let msg = 10f64.powi((*vertex_id % 17) as i32) / *vertex_id as f64;
+            let edges: Vec<_> = context.edges().cloned().collect();
+            for e in edges {
+                context.send_message(e.target_id, msg);
+            }
+            return;
+        }
+        context.set_new_vertex_value(message_iterator.sum());
+    }
+}
+
+impl VertexCentricComputeAlgorithm<u64, f64, u8, f64> for FloatSumAlgorithm {
+    fn name(&self) -> &str {
+        "float_sum"
+    }
+
+    fn iterations(&self) -> u64 {
+        2
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, f64, u8, f64>> {
+        Box::new(FloatSumFunction)
+    }
+
+    fn combiner(&self) -> Option<Box<dyn MessageCombiner<u64, f64>>> {
+        Some(Box::new(|a: f64, b: f64| a + b))
+    }
+}
+
+#[test]
+fn test_partitioned_graph_combines_in_a_reproducible_order() {
+    let hub_value = || {
+        let (vertices, edges) = star_graph(500, 0.0);
+        PartitionedGraph::new(vertices, edges, 4)
+            .compute_algorithm(&FloatSumAlgorithm, 4)
+            .vertices()
+            .into_iter()
+            .find(|v| v.id == 0)
+            .unwrap()
+            .value
+    };
+    let first = hub_value();
+    for _ in 0..10 {
+        assert_eq!(hub_value().to_bits(), first.to_bits());
+    }
+}
+
+#[test]
+fn test_driver_shuffle_applies_bytes_combiner() {
+    let enc = |v: u64| bincode::serialize(&v).unwrap();
+    let outbox = vec![
+        (enc(1), enc(7)),
+        (enc(1), enc(3)),
+        (enc(2), enc(5)),
+        (enc(1), enc(9)),
+    ];
+    let mut inboxes: Inboxes = (0..2).map(|_| HashMap::new()).collect();
+    DriverShuffle::new(Some(&MinU64Combiner))
+        .route_outbox(outbox, 2, &mut inboxes)
+        .unwrap();
+
+    let mut delivered: Vec<(u64, Vec<u64>)> = inboxes
+        .into_iter()
+        .flatten()
+        .map(|(k, msgs)| {
+            let k: u64 = bincode::deserialize(&k).unwrap();
+            let msgs = msgs
+                .iter()
+                .map(|m| bincode::deserialize(m).unwrap())
+                .collect();
+            (k, msgs)
+        })
+        .collect();
+    delivered.sort();
+    assert_eq!(delivered, vec![(1, vec![3]), (2, vec![5])]);
+}
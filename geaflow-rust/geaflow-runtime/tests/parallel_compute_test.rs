@@ -0,0 +1,93 @@
+use geaflow_api::function::{VertexCentricComputeFuncContext, VertexCentricComputeFunction};
+use geaflow_api::graph::PGraphWindow;
+use geaflow_runtime::graph::mem_graph::InMemoryGraph;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+use common::{directed, sorted, Graph};
+
+/// Spreads the smallest id along out-edges, counting how many copies get initialized and
+/// finished.
+#[derive(Clone)]
+struct MinLabelFunction {
+    inits: Arc<AtomicUsize>,
+    finishes: Arc<AtomicUsize>,
+    initialized: bool,
+}
+
+impl VertexCentricComputeFunction<u64, u64, u8, u64> for MinLabelFunction {
+    fn init(&mut self, _context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>) {
+        assert!(!self.initialized);
+        self.initialized = true;
+        self.inits.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn finish(&mut self, _context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>) {
+        assert!(self.initialized);
+        self.finishes.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn compute(
+        &mut self,
+        _vertex_id: &u64,
+        messages: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
+    ) {
+        assert!(self.initialized, "compute before init");
+        let current = context.vertex_value().copied().unwrap_or(u64::MAX);
+        let label = messages.min().map_or(current, |m| m.min(current));
+        if context.iteration() == 1 || label < current {
+            context.set_new_vertex_value(label);
+            let targets: Vec<u64> = context.edges().map(|e| e.target_id).collect();
+            for target in targets {
+                context.send_message(target, label);
+            }
+        }
+        context.vote_to_halt();
+    }
+}
+
+/// Two rings, 1..=20 and 21..=40.
+fn graph() -> Graph<u64> {
+    let pairs: Vec<(u64, u64)> = (1..=40)
+        .map(|id| match id {
+            20 => (id, 1),
+            40 => (id, 21),
+            _ => (id, id + 1),
+        })
+        .collect();
+    common::graph(1..=40, |id| id, directed(&pairs))
+}
+
+/// Every partition computes with its own clone of the function, and each clone gets `init`
+/// before its first superstep and `finish` after its last.
+#[test]
+fn test_compute_runs_a_function_per_partition() {
+    let expected: Vec<(u64, u64)> = (1..=40)
+        .map(|id| (id, if id <= 20 { 1 } else { 21 }))
+        .collect();
+
+    for parallelism in [1, 4] {
+        let inits = Arc::new(AtomicUsize::new(0));
+        let finishes = Arc::new(AtomicUsize::new(0));
+        let func = MinLabelFunction {
+            inits: inits.clone(),
+            finishes: finishes.clone(),
+            initialized: false,
+        };
+        let (vertices, edges) = graph();
+        let result = PartitionedGraph::new(vertices, edges, 1).compute(func.clone(), parallelism);
+        assert_eq!(sorted(result.vertices()), expected);
+        assert_eq!(inits.swap(0, Ordering::SeqCst), parallelism);
+        assert_eq!(finishes.swap(0, Ordering::SeqCst), parallelism);
+
+        let (vertices, edges) = graph();
+        let result = InMemoryGraph::new(vertices, edges).compute(func, parallelism);
+        assert_eq!(sorted(result.vertices()), expected);
+        assert_eq!(result.edges().len(), 40);
+        assert_eq!(inits.load(Ordering::SeqCst), parallelism);
+        assert_eq!(finishes.load(Ordering::SeqCst), parallelism);
+    }
+}
@@ -0,0 +1,169 @@
+use geaflow_common::error::GeaFlowError;
+use geaflow_runtime::distributed::driver::DistributedDriver;
+use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
+use geaflow_runtime::plan::job_spec::{
+    AlgorithmSpec, CheckpointSpec, FileSource, GraphSpec, IdSpec, JobMode, JobSpec, PartitionerSpec,
+};
+use geaflow_runtime::shuffle::partitioner::{
+    create_partitioner, HashPartitioner, MappingPartitioner, Partitioner, RangePartitioner,
+};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+mod common;
+use common::{dec, enc, free_local_addr};
+
+#[test]
+fn test_hash_partitioner_is_stable() {
+    // Pinned: a change here moves keys between workers and breaks existing checkpoints.
+    let assigned: Vec<usize> = [0u64, 1, 2, 3, 42, 1000]
+        .iter()
+        .map(|&id| HashPartitioner.partition(&enc(&id), 4))
+        .collect();
+    assert_eq!(assigned, vec![1, 2, 3, 1, 0, 1]);
+    assert_eq!(HashPartitioner.id(), "hash:xxh3");
+}
+
+#[test]
+fn test_range_partitioner() {
+    let range = RangePartitioner {
+        upper_bounds: vec![10, 20],
+    };
+    let assigned: Vec<usize> = [0u64, 9, 10, 19, 20, 1000]
+        .iter()
+        .map(|&id| range.partition(&enc(&id), 3))
+        .collect();
+    assert_eq!(assigned, vec![0, 0, 1, 1, 2, 2]);
+    // Fewer partitions than ranges: the overflow lands in the last one.
+    assert_eq!(range.partition(&enc(&1000u64), 2), 1);
+    // Keys that are not u64 ids are hashed.
+    assert_eq!(
+        range.partition(b"user", 3),
+        HashPartitioner.partition(b"user", 3)
+    );
+    assert_eq!(range.id(), "range:10,20");
+}
+
+#[test]
+fn test_mapping_partitioner_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mapping.csv");
+    std::fs::write(&path, "# id,partition\n1,1\n2, 0\n").unwrap();
+
+    let mapping = MappingPartitioner::from_file(&path).unwrap();
+    assert_eq!(mapping.partition(&enc(&1u64), 2), 1);
+    assert_eq!(mapping.partition(&enc(&2u64), 2), 0);
+    assert_eq!(
+        mapping.partition(&enc(&3u64), 2),
+        HashPartitioner.partition(&enc(&3u64), 2)
+    );
+
+    let spec = PartitionerSpec::Mapping {
+        path: path.to_string_lossy().to_string(),
+    };
+    assert_eq!(create_partitioner(&spec).unwrap().id(), mapping.id());
+    std::fs::write(&path, "1,0\n").unwrap();
+    assert_ne!(create_partitioner(&spec).unwrap().id(), mapping.id());
+
+    std::fs::write(&path, "1;0\n").unwrap();
+    assert!(matches!(
+        MappingPartitioner::from_file(&path),
+        Err(GeaFlowError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        create_partitioner(&PartitionerSpec::Range {
+            upper_bounds: vec![20, 10]
+        }),
+        Err(GeaFlowError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn test_job_spec_defaults_to_hash() {
+    let json = r#"{
+        "job_id": "j", "name": "wcc", "mode": "Distributed",
+        "graph": {"vertices": {"Csv": {"path": "v"}}, "edges": {"Csv": {"path": "e"}}},
+        "algorithm": {"Wcc": {"iterations": 3}},
+        "checkpoint": {"enabled": false, "interval_iters": 0, "base_dir": "/tmp"}
+    }"#;
+    let job: JobSpec = serde_json::from_str(json).unwrap();
+    assert_eq!(job.partitioner, PartitionerSpec::Hash);
+}
+
+async fn run_on_fresh_workers(job: &JobSpec) -> Vec<(u64, u64)> {
+    let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+    let addrs: Vec<SocketAddr> = dirs.iter().map(|_| free_local_addr()).collect();
+    for (addr, dir) in addrs.iter().zip(&dirs) {
+        tokio::spawn(run_worker(WorkerConfig {
+            listen_addr: *addr,
+            state_dir: PathBuf::from(dir.path()),
+            master_addr: None,
+        }));
+    }
+    let result = DistributedDriver::run_job(&addrs, job).await;
+    let mut out: Vec<(u64, u64)> = result
+        .unwrap()
+        .into_iter()
+        .map(|(id, value)| (dec(&id), dec(&value)))
+        .collect();
+    out.sort();
+    out
+}
+
+fn wcc_job(data: &Path, checkpoints: &Path, partitioner: PartitionerSpec) -> JobSpec {
+    let vertices = data.join("v.csv");
+    let edges = data.join("e.csv");
+    std::fs::write(&vertices, "1\n2\n3\n4\n").unwrap();
+    std::fs::write(&edges, "1,2,0\n2,1,0\n3,4,0\n4,3,0\n").unwrap();
+    JobSpec {
+        job_id: "partitioned".to_string(),
+        name: "wcc".to_string(),
+        mode: JobMode::Distributed,
+        graph: GraphSpec {
+            vertices: FileSource::Csv {
+                path: vertices.to_string_lossy().to_string(),
+            },
+            edges: FileSource::Csv {
+                path: edges.to_string_lossy().to_string(),
+            },
+            ids: IdSpec::U64,
+        },
+        algorithm: AlgorithmSpec::Wcc { iterations: 3 },
+        checkpoint: CheckpointSpec {
+            enabled: true,
+            interval_iters: 1,
+            base_dir: checkpoints.to_string_lossy().to_string(),
+        },
+        partitioner,
+    }
+}
+
+#[tokio::test]
+async fn test_restore_requires_same_partitioner() {
+    let data = tempfile::tempdir().unwrap();
+    let checkpoints = tempfile::tempdir().unwrap();
+    let range = PartitionerSpec::Range {
+        upper_bounds: vec![3],
+    };
+    let job = wcc_job(data.path(), checkpoints.path(), range.clone());
+    let components = vec![(1, 1), (2, 1), (3, 3), (4, 3)];
+    assert_eq!(run_on_fresh_workers(&job).await, components);
+
+    // Resuming under the same partitioner restores the latest checkpoint.
+    assert_eq!(run_on_fresh_workers(&job).await, components);
+
+    let rehashed = wcc_job(data.path(), checkpoints.path(), PartitionerSpec::Hash);
+    let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+    let addrs: Vec<SocketAddr> = dirs.iter().map(|_| free_local_addr()).collect();
+    for (addr, dir) in addrs.iter().zip(&dirs) {
+        tokio::spawn(run_worker(WorkerConfig {
+            listen_addr: *addr,
+            state_dir: PathBuf::from(dir.path()),
+            master_addr: None,
+        }));
+    }
+    let err = DistributedDriver::run_job(&addrs, &rehashed)
+        .await
+        .unwrap_err();
+    assert!(format!("{err}").contains("range:3"), "{err}");
+}
@@ -0,0 +1,223 @@
+use geaflow_api::function::{
+    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+};
+use geaflow_api::graph::PGraphWindow;
+use geaflow_common::property::{Properties, PropertyType, PropertyValue};
+use geaflow_common::schema::{GraphSchema, LabelSchema, PropertySchema};
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::graph::mem_graph::InMemoryGraph;
+use geaflow_runtime::io::file::{read_property_edges, read_property_vertices};
+use geaflow_runtime::state::rocksdb_graph_state::RocksDbGraphState;
+use geaflow_runtime::state::{GraphState, PropertyGraphState};
+use std::io::Write;
+
+type PropertyGraph = (Vec<Vertex<u64, Properties>>, Vec<Edge<u64, Properties>>);
+
+fn schema() -> GraphSchema {
+    GraphSchema::new(
+        vec![
+            LabelSchema::new(
+                "account",
+                vec![
+                    PropertySchema::required("name", PropertyType::String),
+                    PropertySchema::new("frozen", PropertyType::Bool),
+                    PropertySchema::new("opened", PropertyType::Timestamp),
+                ],
+            ),
+            LabelSchema::new(
+                "device",
+                vec![PropertySchema::required("os", PropertyType::String)],
+            ),
+        ],
+        vec![
+            LabelSchema::new(
+                "transfer",
+                vec![
+                    PropertySchema::required("amount", PropertyType::Double),
+                    PropertySchema::new("count", PropertyType::Int),
+                ],
+            ),
+            LabelSchema::new("uses", vec![]),
+        ],
+    )
+}
+
+fn write_csv(dir: &tempfile::TempDir, name: &str, body: &str) -> std::path::PathBuf {
+    let path = dir.path().join(name);
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(body.as_bytes())
+        .unwrap();
+    path
+}
+
+fn load_graph(dir: &tempfile::TempDir) -> PropertyGraph {
+    let vertices = write_csv(
+        dir,
+        "v.csv",
+        "1,account,alice,false,1700000000000\n2,account,bob,,\n3,account,carol,true\n10,device,android\n",
+    );
+    let edges = write_csv(
+        dir,
+        "e.csv",
+        "1,2,transfer,12.5,1\n1,3,transfer,7.5\n2,3,transfer,1.0,2\n1,10,uses\n",
+    );
+    (
+        read_property_vertices(&vertices, &schema()).unwrap(),
+        read_property_edges(&edges, &schema()).unwrap(),
+    )
+}
+
+#[test]
+fn test_property_csv_loaders() {
+    let dir = tempfile::tempdir().unwrap();
+    let (vertices, edges) = load_graph(&dir);
+
+    assert_eq!(vertices.len(), 4);
+    let alice = &vertices[0].value;
+    assert_eq!(alice.label, "account");
+    assert_eq!(alice.get("name").and_then(|v| v.as_str()), Some("alice"));
+    assert_eq!(alice.get("frozen").and_then(|v| v.as_bool()), Some(false));
+    assert_eq!(
+        alice.get("opened").and_then(|v| v.as_timestamp()),
+        Some(1_700_000_000_000)
+    );
+    assert!(vertices[1].value.get("frozen").is_none());
+    assert_eq!(vertices[3].value.label, "device");
+
+    assert_eq!(edges.len(), 4);
+    assert_eq!(
+        edges[0].value,
+        Properties::new("transfer")
+            .with("amount", PropertyValue::Double(12.5))
+            .with("count", PropertyValue::Int(1))
+    );
+    assert_eq!(edges[3].value, Properties::new("uses"));
+
+    let bad_type = write_csv(&dir, "bad_type.csv", "1,2,transfer,lots\n");
+    assert!(read_property_edges(&bad_type, &schema()).is_err());
+    let missing = write_csv(&dir, "missing.csv", "4,device,\n");
+    assert!(read_property_vertices(&missing, &schema()).is_err());
+    let unknown = write_csv(&dir, "unknown.csv", "4,merchant,acme\n");
+    assert!(read_property_vertices(&unknown, &schema()).is_err());
+}
+
+#[test]
+fn test_schema_validation() {
+    let schema = schema();
+    let ok = Properties::new("device").with("os", PropertyValue::String("ios".to_string()));
+    assert!(schema.validate_vertex(&ok).is_ok());
+    assert!(schema.validate_edge(&ok).is_err());
+
+    let wrong_type = Properties::new("device").with("os", PropertyValue::Int(1));
+    assert!(schema.validate_vertex(&wrong_type).is_err());
+    let extra = ok
+        .clone()
+        .with("vendor", PropertyValue::String("x".to_string()));
+    assert!(schema.validate_vertex(&extra).is_err());
+}
+
+#[test]
+fn test_rocksdb_property_graph() {
+    type S = RocksDbGraphState;
+    let dir = tempfile::tempdir().unwrap();
+    let (vertices, edges) = load_graph(&dir);
+    let state_dir = tempfile::tempdir().unwrap();
+    {
+        let state = RocksDbGraphState::open(state_dir.path()).unwrap();
+        assert_eq!(PropertyGraphState::<u64>::get_schema(&state).unwrap(), None);
+        PropertyGraphState::<u64>::put_schema(&state, &schema()).unwrap();
+        state.put_property_vertex_batch(&vertices).unwrap();
+        state.put_property_edge_batch(&edges).unwrap();
+
+        let rejected = Vertex::new(99u64, Properties::new("merchant"));
+        assert!(state.put_property_vertex_batch(&[rejected]).is_err());
+    }
+
+    let state = RocksDbGraphState::open(state_dir.path()).unwrap();
+    assert_eq!(
+        PropertyGraphState::<u64>::get_schema(&state).unwrap(),
+        Some(schema())
+    );
+    let mut accounts: Vec<u64> =
+        PropertyGraphState::<u64>::list_vertices_by_label(&state, "account")
+            .unwrap()
+            .into_iter()
+            .map(|v| v.id)
+            .collect();
+    accounts.sort();
+    assert_eq!(accounts, vec![1, 2, 3]);
+    assert_eq!(
+        <S as GraphState<u64, Properties, Properties>>::get_vertex(&state, &10).unwrap(),
+        Some(vertices[3].value.clone())
+    );
+    let out = <S as GraphState<u64, Properties, Properties>>::get_out_edges(&state, &1).unwrap();
+    assert_eq!(out.len(), 3);
+}
+
+/// Sets `received` on each account to the total amount transferred into it.
+struct ReceivedFunction;
+
+impl VertexCentricComputeFunction<u64, Properties, Properties, f64> for ReceivedFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        messages: &mut dyn Iterator<Item = f64>,
+        ctx: &mut dyn VertexCentricComputeFuncContext<u64, Properties, Properties, f64>,
+    ) {
+        if ctx.iteration() == 1 {
+            let transfers: Vec<(u64, f64)> = ctx
+                .edges()
+                .filter(|e| e.value.label == "transfer")
+                .filter_map(|e| Some((e.target_id, e.value.get("amount")?.as_double()?)))
+                .collect();
+            for (target, amount) in transfers {
+                ctx.send_message(target, amount);
+            }
+            ctx.send_message(*vertex_id, 0.0);
+            return;
+        }
+        let mut value = ctx.vertex_value().cloned().unwrap_or_default();
+        if value.label == "account" {
+            value.set("received", PropertyValue::Double(messages.sum()));
+            ctx.set_new_vertex_value(value);
+        }
+        ctx.vote_to_halt();
+    }
+}
+
+struct ReceivedAlgorithm;
+
+impl VertexCentricComputeAlgorithm<u64, Properties, Properties, f64> for ReceivedAlgorithm {
+    fn name(&self) -> &str {
+        "received"
+    }
+
+    fn iterations(&self) -> u64 {
+        2
+    }
+
+    fn create_function(
+        &self,
+    ) -> Box<dyn VertexCentricComputeFunction<u64, Properties, Properties, f64>> {
+        Box::new(ReceivedFunction)
+    }
+}
+
+#[test]
+fn test_compute_over_property_graph() {
+    let dir = tempfile::tempdir().unwrap();
+    let (vertices, edges) = load_graph(&dir);
+    let graph = InMemoryGraph::new(vertices, edges).compute_algorithm(&ReceivedAlgorithm, 1);
+
+    let mut received: Vec<(u64, Option<f64>)> = graph
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id, v.value.get("received").and_then(|p| p.as_double())))
+        .collect();
+    received.sort_by_key(|(id, _)| *id);
+    assert_eq!(
+        received,
+        vec![(1, Some(0.0)), (2, Some(12.5)), (3, Some(8.5)), (10, None)]
+    );
+}
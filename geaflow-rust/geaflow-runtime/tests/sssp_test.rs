@@ -1,7 +1,16 @@
 use geaflow_api::function::{VertexCentricComputeFuncContext, VertexCentricComputeFunction};
 use geaflow_api::graph::PGraphWindow;
+use geaflow_api::mutation::GraphMutation;
 use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::sssp::{SsspAlgorithm, SsspTreeValue, SsspWeights};
+use geaflow_runtime::distributed::algorithm::{SsspParams, SsspTreeParams};
 use geaflow_runtime::graph::mem_graph::InMemoryGraph;
+use std::collections::HashMap;
+
+mod common;
+use common::{
+    enc, fetch_sorted, load, run_algorithm, sorted, start_workers, stop_workers, temp_dirs, Graph,
+};
 
 #[derive(Clone)]
 struct SSSPAlgorithm {
@@ -63,3 +72,216 @@ fn test_sssp() {
     let v3 = vertices.iter().find(|v| v.id == 3).unwrap();
     assert_eq!(v3.value, 30); // 1->2->3 (10+20=30) < 1->3 (100)
 }
+
+/// 1 -> 2 -> 3 is cheaper than 1 -> 3; 4 -> 1 points in, so 4 stays unreachable.
+fn weighted_graph() -> Graph<f64> {
+    let edges = vec![
+        Edge::new(1, 2, 10),
+        Edge::new(2, 3, 20),
+        Edge::new(1, 3, 100),
+        Edge::new(4, 1, 1),
+    ];
+    common::graph(1..=4, |_| f64::INFINITY, edges)
+}
+
+#[test]
+fn test_builtin_sssp() {
+    let algo = SsspAlgorithm::new(1, 10, SsspWeights::EdgeValue);
+    for (vertices, _) in run_algorithm(weighted_graph, &algo, 3) {
+        assert_eq!(
+            sorted(vertices),
+            vec![(1, 0.0), (2, 10.0), (3, 30.0), (4, f64::INFINITY)]
+        );
+    }
+
+    let algo = SsspAlgorithm::new(1, 10, SsspWeights::Unit);
+    for (vertices, _) in run_algorithm(weighted_graph, &algo, 3) {
+        assert_eq!(
+            sorted(vertices),
+            vec![(1, 0.0), (2, 1.0), (3, 1.0), (4, f64::INFINITY)]
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_distributed_sssp() {
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    load(&mut driver, &weighted_graph()).await;
+    let params = SsspParams {
+        source: 1,
+        weights: SsspWeights::EdgeValue,
+    };
+    driver
+        .set_algorithm("sssp".to_string(), 10, enc(&params))
+        .await
+        .unwrap();
+    driver.execute(10).await.unwrap();
+
+    assert_eq!(
+        fetch_sorted::<f64>(&mut driver).await,
+        vec![(1, 0.0), (2, 10.0), (3, 30.0), (4, f64::INFINITY)]
+    );
+
+    // Dropping 2 -> 3 falls back to the direct edge.
+    driver
+        .apply_delta(vec![GraphMutation::RemoveEdge {
+            src_id: enc(&2u64),
+            target_id: enc(&3u64),
+        }])
+        .await
+        .unwrap();
+    driver.execute(10).await.unwrap();
+    assert_eq!(fetch_sorted::<f64>(&mut driver).await[2], (3, 100.0));
+    stop_workers(driver, handles).await;
+}
+
+#[tokio::test]
+async fn test_distributed_sssp_on_generated_vertices() {
+    let data = tempfile::tempdir().unwrap();
+    let edges_path = data.path().join("e.txt");
+    std::fs::write(&edges_path, "0 1\n# comment\n\n1 2\n2 3\n").unwrap();
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    // Vertices 4 and 5 have no edges and only exist because of the vertex count.
+    driver
+        .load_graph500_streaming_generated_vertices(
+            6,
+            &edges_path,
+            |_| enc(&f64::INFINITY),
+            4,
+            4,
+            false,
+        )
+        .await
+        .unwrap();
+    let params = SsspParams {
+        source: 0,
+        weights: SsspWeights::Unit,
+    };
+    driver
+        .set_algorithm("sssp".to_string(), 10, enc(&params))
+        .await
+        .unwrap();
+    driver.execute(10).await.unwrap();
+    assert_eq!(
+        fetch_sorted::<f64>(&mut driver).await,
+        vec![
+            (0, 0.0),
+            (1, 1.0),
+            (2, 2.0),
+            (3, 3.0),
+            (4, f64::INFINITY),
+            (5, f64::INFINITY)
+        ]
+    );
+    stop_workers(driver, handles).await;
+}
+
+/// Eighths in 0..=9/8, the same both ways; some edges are free.
+fn pair_weight(u: u64, v: u64) -> f64 {
+    ((u.min(v) * 31 + u.max(v) * 17) % 10) as f64 / 8.0
+}
+
+#[tokio::test]
+async fn test_distributed_sssp_tree_records_parents() {
+    let data = tempfile::tempdir().unwrap();
+    let vertices_path = data.path().join("v.txt");
+    let edges_path = data.path().join("e.txt");
+    let ids: Vec<u64> = (0..40).chain([99]).collect();
+    let mut lines = Vec::new();
+    let mut seed: u64 = 5;
+    for _ in 0..120 {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        lines.push(((seed >> 33) % 40, (seed >> 17) % 40));
+    }
+    let text = |rows: Vec<String>| rows.join("\n") + "\n";
+    std::fs::write(
+        &vertices_path,
+        text(ids.iter().map(u64::to_string).collect()),
+    )
+    .unwrap();
+    std::fs::write(
+        &edges_path,
+        text(lines.iter().map(|(u, v)| format!("{u} {v}")).collect()),
+    )
+    .unwrap();
+
+    // Dijkstra over the undirected edges.
+    let mut expected: HashMap<u64, f64> = HashMap::from([(0, 0.0)]);
+    let mut done = Vec::new();
+    while let Some((&next, &d)) = expected
+        .iter()
+        .filter(|(id, _)| !done.contains(*id))
+        .min_by(|a, b| a.1.total_cmp(b.1))
+    {
+        done.push(next);
+        for &(u, v) in &lines {
+            let other = match (u == next, v == next) {
+                (true, _) => v,
+                (_, true) => u,
+                _ => continue,
+            };
+            let candidate = d + pair_weight(u, v);
+            if expected.get(&other).is_none_or(|old| candidate < *old) {
+                expected.insert(other, candidate);
+            }
+        }
+    }
+
+    let dirs = temp_dirs(3);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    driver
+        .load_graph500_streaming_weighted(
+            &vertices_path,
+            &edges_path,
+            |_| enc(&SsspTreeValue::unreached()),
+            |u, v| enc(&pair_weight(u, v)),
+            16,
+            true,
+        )
+        .await
+        .unwrap();
+    driver
+        .set_algorithm(
+            "sssp_tree".to_string(),
+            100,
+            enc(&SsspTreeParams { source: 0 }),
+        )
+        .await
+        .unwrap();
+    driver.execute(100).await.unwrap();
+    let tree: HashMap<u64, SsspTreeValue> = fetch_sorted(&mut driver).await.into_iter().collect();
+    stop_workers(driver, handles).await;
+
+    assert_eq!(tree.len(), ids.len());
+    assert!(expected.len() > 30);
+    assert_eq!(tree[&99], SsspTreeValue::unreached());
+    assert_eq!(
+        tree[&0],
+        SsspTreeValue {
+            distance: 0.0,
+            parent: 0
+        }
+    );
+    for (id, value) in &tree {
+        match expected.get(id) {
+            None => assert_eq!(value.distance, f64::INFINITY, "vertex {id}"),
+            Some(d) => {
+                assert_eq!(value.distance, *d, "vertex {id}");
+                if *id != 0 {
+                    let parent = value.parent;
+                    assert!(
+                        lines.iter().any(|&(u, v)| {
+                            ((u, v) == (parent, *id) || (v, u) == (parent, *id))
+                                && tree[&parent].distance + pair_weight(u, v) == value.distance
+                        }),
+                        "vertex {id}"
+                    );
+                }
+            }
+        }
+    }
+}
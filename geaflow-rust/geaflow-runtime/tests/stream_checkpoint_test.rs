@@ -0,0 +1,213 @@
+use geaflow_api::stream::PStream;
+use geaflow_api::window::{TimeWindow, WatermarkStrategy};
+use geaflow_common::error::GeaFlowResult;
+use geaflow_runtime::distributed::driver::DistributedDriver;
+use geaflow_runtime::distributed::stream::{
+    to_records, FileWindowSink, FiredWindow, WindowResults, WindowSink, WindowSpec,
+};
+use geaflow_runtime::distributed::worker::{run_worker, WorkerConfig};
+use geaflow_runtime::io::source::FileOffsetSource;
+use geaflow_runtime::plan::job_spec::CheckpointSpec;
+use geaflow_runtime::scheduler::stream_scheduler::{SourceOffsets, StreamScheduler};
+use geaflow_runtime::stream::LocalStream;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use tokio::task::JoinHandle;
+
+const CLICKS: &str = "1,1\n2,2\n1,5\n2,9\n1,11\n1,13\n2,14\n1,22\n2,25\n1,27\n2,33\n1,41\n";
+
+fn free_local_addr() -> SocketAddr {
+    let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    addr
+}
+
+fn spawn_workers(state_dirs: &[PathBuf]) -> (Vec<SocketAddr>, Vec<JoinHandle<GeaFlowResult<()>>>) {
+    let addrs: Vec<SocketAddr> = state_dirs.iter().map(|_| free_local_addr()).collect();
+    let handles = addrs
+        .iter()
+        .zip(state_dirs)
+        .map(|(addr, dir)| {
+            tokio::spawn(run_worker(WorkerConfig {
+                listen_addr: *addr,
+                state_dir: dir.clone(),
+                master_addr: None,
+            }))
+        })
+        .collect();
+    (addrs, handles)
+}
+
+/// Counts clicks per user over 10ms tumbling windows, two lines per batch and a checkpoint
+/// every two batches. Stops without finishing after `crash_after` batches, as if the job died.
+async fn run_job(
+    state_dirs: &[PathBuf],
+    input: &Path,
+    checkpoint_dir: &Path,
+    output_dir: &Path,
+    crash_after: Option<usize>,
+) {
+    let (addrs, handles) = spawn_workers(state_dirs);
+    let mut driver = DistributedDriver::connect(&addrs).await.unwrap();
+    driver
+        .set_window_aggregate(
+            "count".to_string(),
+            Vec::new(),
+            WindowSpec::Tumbling { size: 10 },
+            WatermarkStrategy::bounded_out_of_orderness(5),
+            0,
+        )
+        .await
+        .unwrap();
+    let mut sink = FileWindowSink::new(output_dir).unwrap();
+    let mut scheduler = StreamScheduler::new(
+        "clicks-job",
+        CheckpointSpec {
+            enabled: true,
+            interval_iters: 2,
+            base_dir: checkpoint_dir.to_string_lossy().to_string(),
+        },
+    );
+    let offsets = scheduler.recover(&mut driver, &mut sink).await.unwrap();
+    let mut source = FileOffsetSource::new(input, offsets.get("clicks").copied().unwrap_or(0));
+
+    let mut batches = 0;
+    loop {
+        let lines = source.read_batch(2).await.unwrap();
+        if lines.is_empty() {
+            break;
+        }
+        let clicks: Vec<(u64, u64)> = lines
+            .iter()
+            .map(|line| {
+                let (user, ts) = line.split_once(',').unwrap();
+                (user.parse().unwrap(), ts.parse().unwrap())
+            })
+            .collect();
+        let keyed = LocalStream::from_vec(clicks).key_by(|(user, _): &(u64, u64)| *user);
+        let records = to_records(keyed, |(_, ts): &(u64, u64)| *ts).unwrap();
+        let offsets = SourceOffsets::from([("clicks".to_string(), source.offset())]);
+        scheduler
+            .process(&mut driver, records, &offsets, &mut sink)
+            .await
+            .unwrap();
+        batches += 1;
+        if crash_after == Some(batches) {
+            for h in handles {
+                h.abort();
+                let _ = h.await;
+            }
+            return;
+        }
+    }
+    let offsets = SourceOffsets::from([("clicks".to_string(), source.offset())]);
+    scheduler
+        .finish(&mut driver, &offsets, &mut sink)
+        .await
+        .unwrap();
+    driver.shutdown().await.unwrap();
+    for h in handles {
+        h.await.unwrap().unwrap();
+    }
+}
+
+/// `(user, window start, count)` in commit order.
+fn committed(output_dir: &Path) -> Vec<(u64, u64, u64)> {
+    FileWindowSink::read_committed(output_dir)
+        .unwrap()
+        .iter()
+        .map(|f| {
+            (
+                bincode::deserialize(&f.key).unwrap(),
+                f.window.start,
+                bincode::deserialize(&f.result).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_recovery_is_exactly_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("clicks.csv");
+    std::fs::write(&input, CLICKS).unwrap();
+    let state_dirs = |name: &str| -> Vec<PathBuf> {
+        (0..2)
+            .map(|i| dir.path().join(format!("{name}_worker_{i}")))
+            .collect()
+    };
+
+    let expected = vec![
+        (1, 0, 2),
+        (2, 0, 2),
+        (1, 10, 2),
+        (2, 10, 1),
+        (1, 20, 2),
+        (2, 20, 1),
+        (2, 30, 1),
+        (1, 40, 1),
+    ];
+    let clean_out = dir.path().join("clean_out");
+    run_job(
+        &state_dirs("clean"),
+        &input,
+        &dir.path().join("clean_cp"),
+        &clean_out,
+        None,
+    )
+    .await;
+    assert_eq!(committed(&clean_out), expected);
+
+    // Die after five batches: the fifth batch's output is never committed and gets replayed.
+    let workers = state_dirs("failing");
+    let cp = dir.path().join("failing_cp");
+    let out = dir.path().join("failing_out");
+    run_job(&workers, &input, &cp, &out, Some(5)).await;
+    assert_eq!(committed(&out), expected[..2].to_vec());
+
+    // Die again right after recovering, then finish.
+    run_job(&workers, &input, &cp, &out, Some(1)).await;
+    run_job(&workers, &input, &cp, &out, None).await;
+    assert_eq!(committed(&out), expected);
+}
+
+fn fired(key: u8) -> WindowResults {
+    WindowResults {
+        fired: vec![FiredWindow {
+            key: vec![key],
+            window: TimeWindow::new(0, 10),
+            result: Vec::new(),
+        }],
+        late: Vec::new(),
+    }
+}
+
+#[test]
+fn test_file_sink_recovers_staged_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let keys = |dir: &Path| -> Vec<u8> {
+        FileWindowSink::read_committed(dir)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.key[0])
+            .collect()
+    };
+
+    let mut sink = FileWindowSink::new(dir.path()).unwrap();
+    sink.write(&fired(1)).unwrap();
+    sink.pre_commit(1).unwrap();
+    sink.commit(1).unwrap();
+    // Checkpoint 2 was durable but the job died before committing; checkpoint 3 never was.
+    sink.write(&fired(2)).unwrap();
+    sink.pre_commit(2).unwrap();
+    sink.write(&fired(3)).unwrap();
+    sink.pre_commit(3).unwrap();
+    sink.write(&fired(4)).unwrap();
+    assert_eq!(keys(dir.path()), vec![1]);
+
+    let mut sink = FileWindowSink::new(dir.path()).unwrap();
+    sink.recover(Some(2)).unwrap();
+    assert_eq!(keys(dir.path()), vec![1, 2]);
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+}
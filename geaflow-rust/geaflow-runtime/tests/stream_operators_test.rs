@@ -0,0 +1,200 @@
+use geaflow_api::function::AggregateFunction;
+use geaflow_api::graph::PGraphWindow;
+use geaflow_api::stream::{PKeyedStream, PStream};
+use geaflow_api::window::TimeTumblingWindow;
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::wcc::WccAlgorithm;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use geaflow_runtime::stream::{Barrier, LocalStream};
+
+#[test]
+fn test_flat_map_union_and_key_by() {
+    let lines = LocalStream::from_vec(vec!["1 2", "3"]);
+    let more = LocalStream::from_vec(vec!["4 5 6"]);
+    let numbers = lines
+        .union(more)
+        .flat_map(|line: &str| {
+            line.split_whitespace()
+                .map(|t| t.parse::<u64>().unwrap())
+                .collect()
+        })
+        .collect();
+    assert_eq!(numbers, vec![1, 2, 3, 4, 5, 6]);
+
+    let keyed = LocalStream::from_vec(vec![1u64, 2, 3, 4])
+        .key_by(|x: &u64| x % 2)
+        .collect();
+    assert_eq!(keyed, vec![(1, 1), (0, 2), (1, 3), (0, 4)]);
+}
+
+#[test]
+fn test_barriers_follow_records_through_operators() {
+    let out = LocalStream::from_vec((1u64..=7).collect())
+        .checkpoint_every(3)
+        .filter(|x: &u64| x % 2 == 1)
+        .flat_map(|x: u64| vec![x; x as usize % 3])
+        .map(|x: u64| x * 10);
+    assert_eq!(
+        out.barriers(),
+        [
+            Barrier {
+                checkpoint_id: 1,
+                offset: 1
+            },
+            Barrier {
+                checkpoint_id: 2,
+                offset: 3
+            }
+        ]
+    );
+    assert_eq!(
+        out.collect_checkpoints(),
+        vec![
+            (Some(1), vec![10]),
+            (Some(2), vec![50, 50]),
+            (None, vec![70]),
+        ]
+    );
+}
+
+#[test]
+fn test_union_aligns_barriers_per_input() {
+    // The left side reaches each barrier sooner; its later records wait for the right side.
+    let left = LocalStream::from_vec(vec![1u64, 2, 3, 4]).checkpoint_every(2);
+    let right = LocalStream::from_vec(vec![10u64, 20, 30, 40, 50, 60]).checkpoint_every(3);
+    assert_eq!(
+        left.union(right).collect_checkpoints(),
+        vec![
+            (Some(1), vec![1, 2, 10, 20, 30]),
+            (Some(2), vec![3, 4, 40, 50, 60]),
+            (None, vec![]),
+        ]
+    );
+
+    // Checkpoint 2 never reaches the right side, so it cannot complete.
+    let left = LocalStream::from_vec(vec![1u64, 2, 3, 4]).checkpoint_every(2);
+    let right = LocalStream::from_vec(vec![10u64, 20, 30]).checkpoint_every(2);
+    assert_eq!(
+        left.union(right).collect_checkpoints(),
+        vec![(Some(1), vec![1, 2, 10, 20]), (None, vec![3, 4, 30])]
+    );
+
+    let sums = LocalStream::from_vec(vec![(1u64, 1u64), (2, 2), (1, 3)])
+        .checkpoint_every(1)
+        .key_by(|(k, _): &(u64, u64)| *k)
+        .reduce(|(k, a): (u64, u64), (_, b): (u64, u64)| (k, a + b))
+        .collect_checkpoints();
+    assert_eq!(
+        sums,
+        vec![
+            (Some(1), vec![]),
+            (Some(2), vec![]),
+            (Some(3), vec![]),
+            (None, vec![(1, (1, 4)), (2, (2, 2))]),
+        ]
+    );
+}
+
+#[test]
+fn test_keyed_reduce() {
+    let sums = LocalStream::from_vec(vec![(7u64, 1u64), (3, 2), (7, 3), (3, 4), (9, 5)])
+        .key_by(|(k, _): &(u64, u64)| *k)
+        .reduce(|(k, a): (u64, u64), (_, b): (u64, u64)| (k, a + b))
+        .map(|(_, record): (u64, (u64, u64))| record)
+        .collect();
+    assert_eq!(sums, vec![(7, 4), (3, 6), (9, 5)]);
+}
+
+/// Mean of the readings per key.
+struct Mean;
+
+impl AggregateFunction<(u64, f64), (f64, u64), f64> for Mean {
+    fn create_accumulator(&self) -> (f64, u64) {
+        (0.0, 0)
+    }
+
+    fn add(&self, acc: &mut (f64, u64), value: (u64, f64)) {
+        acc.0 += value.1;
+        acc.1 += 1;
+    }
+
+    fn get_result(&self, acc: (f64, u64)) -> f64 {
+        acc.0 / acc.1 as f64
+    }
+}
+
+#[test]
+fn test_keyed_aggregate() {
+    let means = LocalStream::from_vec(vec![(1u64, 2.0f64), (2, 10.0), (1, 4.0)])
+        .key_by(|(k, _): &(u64, f64)| *k)
+        .aggregate(Mean)
+        .collect();
+    assert_eq!(means, vec![(1, 3.0), (2, 10.0)]);
+}
+
+#[test]
+fn test_windowed_join() {
+    let accounts =
+        LocalStream::from_vec(vec![(1u64, "alice", 0u64), (2, "bob", 1), (3, "carol", 12)])
+            .key_by(|(id, _, _): &(u64, &str, u64)| *id);
+    let transfers =
+        LocalStream::from_vec(vec![(2u64, 5u64, 3u64), (1, 7, 14), (3, 9, 15), (1, 1, 4)])
+            .key_by(|(id, _, _): &(u64, u64, u64)| *id);
+
+    // [0, 10): alice x 1:1, bob x 2:5. [10, 20): carol x 3:9; 1:7 has no account there.
+    let joined = accounts
+        .join(
+            transfers,
+            TimeTumblingWindow { size: 10 },
+            (
+                |(_, _, ts): &(u64, &str, u64)| *ts,
+                |(_, _, ts): &(u64, u64, u64)| *ts,
+            ),
+            |(_, name, _): &(u64, &str, u64), (_, amount, _): &(u64, u64, u64)| {
+                (name.to_string(), *amount)
+            },
+        )
+        .collect();
+    assert_eq!(
+        joined,
+        vec![
+            ("alice".to_string(), 1),
+            ("bob".to_string(), 5),
+            ("carol".to_string(), 9)
+        ]
+    );
+}
+
+#[test]
+fn test_edge_ingest_pipeline() {
+    // Raw "src dst" lines with duplicates and self-loops, deduplicated per pair.
+    let raw = vec!["1 2", "2 1", "2 3", "3 3", "5 6", "1 2"];
+    let edges: Vec<Edge<u64, u8>> = LocalStream::from_vec(raw)
+        .flat_map(|line: &str| {
+            let ids: Vec<u64> = line.split(' ').map(|t| t.parse().unwrap()).collect();
+            vec![(ids[0], ids[1])]
+        })
+        .filter(|(s, t): &(u64, u64)| s != t)
+        .key_by(|(s, t): &(u64, u64)| (*s.min(t), *s.max(t)))
+        .reduce(|a: (u64, u64), _| a)
+        .map(|((s, t), _): ((u64, u64), (u64, u64))| Edge::new(s, t, 0u8))
+        .collect();
+    assert_eq!(edges.len(), 3);
+
+    let vertices: Vec<Vertex<u64, u64>> = LocalStream::from_vec(edges.clone())
+        .flat_map(|e: Edge<u64, u8>| vec![e.src_id, e.target_id])
+        .key_by(|id: &u64| *id)
+        .reduce(|a: u64, _| a)
+        .map(|(id, _): (u64, u64)| Vertex::new(id, id))
+        .collect();
+
+    let graph =
+        PartitionedGraph::new(vertices, edges, 2).compute_algorithm(&WccAlgorithm::new(10), 2);
+    let mut components: Vec<(u64, u64)> = graph
+        .vertices()
+        .into_iter()
+        .map(|v| (v.id, v.value))
+        .collect();
+    components.sort();
+    assert_eq!(components, vec![(1, 1), (2, 1), (3, 1), (5, 5), (6, 5)]);
+}
@@ -0,0 +1,122 @@
+use futures::StreamExt;
+use geaflow_api::stream::PStream;
+use geaflow_runtime::io::socket::tcp_lines;
+use geaflow_runtime::io::source::{tail_file, watch_dir, LineSource};
+use std::io::Write;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+const POLL: Duration = Duration::from_millis(10);
+
+async fn take(source: &mut LineSource, n: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    while out.len() < n {
+        let line = tokio::time::timeout(Duration::from_secs(10), source.next())
+            .await
+            .expect("source stalled")
+            .expect("source ended")
+            .unwrap();
+        out.push(line);
+    }
+    out
+}
+
+fn append(path: &Path, text: &str) {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap()
+        .write_all(text.as_bytes())
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_tcp_source_accepts_many_clients() {
+    let mut source = tcp_lines(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), 4)
+        .await
+        .unwrap();
+    let addr = source.local_addr().unwrap();
+
+    let mut first = TcpStream::connect(addr).await.unwrap();
+    let mut second = TcpStream::connect(addr).await.unwrap();
+    first.write_all(b"1 2\n3 4\n").await.unwrap();
+    second.write_all(b"5 6\n").await.unwrap();
+    drop(first);
+    let mut lines = take(&mut source, 3).await;
+    lines.sort();
+    assert_eq!(lines, vec!["1 2", "3 4", "5 6"]);
+
+    // A client arriving later is still picked up, and batches feed a `PStream`.
+    let mut third = TcpStream::connect(addr).await.unwrap();
+    third.write_all(b"7 8\n").await.unwrap();
+    second.write_all(b"9 10\n").await.unwrap();
+    let mut sums = Vec::new();
+    while sums.len() < 2 {
+        let batch = source.next_batch(16).await.unwrap().unwrap();
+        sums.extend(
+            batch
+                .map(|line: String| {
+                    line.split(' ')
+                        .map(|t| t.parse::<u64>().unwrap())
+                        .sum::<u64>()
+                })
+                .collect(),
+        );
+    }
+    sums.sort();
+    assert_eq!(sums, vec![15, 19]);
+}
+
+#[tokio::test]
+async fn test_tail_follows_appends_and_rotation() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("edges.log");
+    append(&log, "1 2\n3 4\n");
+
+    let mut source = tail_file(&log, false, POLL, 2);
+    assert_eq!(take(&mut source, 2).await, vec!["1 2", "3 4"]);
+
+    append(&log, "5 ");
+    append(&log, "6\n");
+    assert_eq!(take(&mut source, 1).await, vec!["5 6"]);
+
+    // Rotate by rename: the last line of the old file still arrives before the new file's.
+    append(&log, "7 8\n");
+    std::fs::rename(&log, dir.path().join("edges.log.1")).unwrap();
+    append(&log, "9 10\n");
+    assert_eq!(take(&mut source, 2).await, vec!["7 8", "9 10"]);
+
+    // Truncation in place is noticed by size, once the tail has polled the shorter file.
+    std::fs::write(&log, "").unwrap();
+    tokio::time::sleep(POLL * 5).await;
+    append(&log, "11 12\n");
+    assert_eq!(take(&mut source, 1).await, vec!["11 12"]);
+}
+
+#[tokio::test]
+async fn test_tail_from_end_skips_existing_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("edges.log");
+    append(&log, "old\n");
+
+    let mut source = tail_file(&log, true, POLL, 2);
+    tokio::time::sleep(POLL * 5).await;
+    append(&log, "new\n");
+    assert_eq!(take(&mut source, 1).await, vec!["new"]);
+}
+
+#[tokio::test]
+async fn test_watch_dir_reads_new_files() {
+    let dir = tempfile::tempdir().unwrap();
+    append(&dir.path().join("a.csv"), "1,2\n2,3");
+
+    let mut source = watch_dir(dir.path(), POLL, 1);
+    assert_eq!(take(&mut source, 2).await, vec!["1,2", "2,3"]);
+
+    append(&dir.path().join("b.csv"), "4,5\n");
+    assert_eq!(take(&mut source, 1).await, vec!["4,5"]);
+}
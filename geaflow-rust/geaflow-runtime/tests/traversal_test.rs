@@ -0,0 +1,76 @@
+use geaflow_api::function::{TraversalRequest, TraversalResponse};
+use geaflow_api::graph::PGraphWindow;
+use geaflow_runtime::algorithms::khop::KHopAlgorithm;
+use geaflow_runtime::graph::mem_graph::InMemoryGraph;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+
+mod common;
+use common::{directed, sorted, Graph};
+
+/// 1 -> 2 -> 3 -> 4 -> 5 -> 1 with a shortcut 1 -> 3.
+fn graph() -> Graph<u64> {
+    let pairs = [(1, 2), (2, 3), (3, 4), (4, 5), (5, 1), (1, 3)];
+    common::graph(1..=5, |id| id * 100, directed(&pairs))
+}
+
+fn requests() -> Vec<TraversalRequest<u64>> {
+    vec![
+        TraversalRequest::new(10, 1),
+        TraversalRequest::new(11, 4),
+        TraversalRequest::new(12, 99),
+    ]
+}
+
+fn by_request(responses: Vec<TraversalResponse<(u64, u64)>>, request_id: u64) -> Vec<(u64, u64)> {
+    let mut out: Vec<(u64, u64)> = responses
+        .into_iter()
+        .filter(|r| r.request_id == request_id)
+        .map(|r| r.response)
+        .collect();
+    out.sort();
+    out
+}
+
+fn assert_two_hops(responses: Vec<TraversalResponse<(u64, u64)>>) {
+    assert_eq!(
+        by_request(responses.clone(), 10),
+        vec![(1, 0), (2, 1), (3, 1), (4, 2)]
+    );
+    assert_eq!(
+        by_request(responses.clone(), 11),
+        vec![(1, 2), (4, 0), (5, 1)]
+    );
+    assert!(by_request(responses, 12).is_empty());
+}
+
+#[test]
+fn test_in_memory_khop_requests() {
+    let (vertices, edges) = graph();
+    let graph = InMemoryGraph::new(vertices, edges);
+    assert_two_hops(graph.traverse(&KHopAlgorithm::new(2), requests(), 1));
+
+    let one_hop = graph.traverse(&KHopAlgorithm::new(1), requests(), 1);
+    assert_eq!(by_request(one_hop, 11), vec![(4, 0), (5, 1)]);
+
+    assert_eq!(
+        sorted(graph.vertices()),
+        vec![(1, 100), (2, 200), (3, 300), (4, 400), (5, 500)]
+    );
+}
+
+#[test]
+fn test_partitioned_khop_requests() {
+    let (vertices, edges) = graph();
+    let graph = PartitionedGraph::new(vertices, edges, 3);
+    assert_two_hops(graph.traverse(&KHopAlgorithm::new(2), requests(), 3));
+
+    let all = graph.traverse(
+        &KHopAlgorithm::new(10),
+        vec![TraversalRequest::new(1, 3)],
+        3,
+    );
+    assert_eq!(
+        by_request(all, 1),
+        vec![(1, 3), (2, 4), (3, 0), (4, 1), (5, 2)]
+    );
+}
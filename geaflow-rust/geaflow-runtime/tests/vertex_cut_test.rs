@@ -0,0 +1,163 @@
+use geaflow_api::graph::PGraphWindow;
+use geaflow_api::mutation::GraphMutation;
+use geaflow_common::error::GeaFlowError;
+use geaflow_common::types::{Edge, Vertex};
+use geaflow_runtime::algorithms::pagerank::PageRankAlgorithm;
+use geaflow_runtime::algorithms::wcc::WccAlgorithm;
+use geaflow_runtime::distributed::algorithm::PageRankParams;
+use geaflow_runtime::graph::partitioned_graph::PartitionedGraph;
+use geaflow_runtime::shuffle::vertex_cut::EdgePartitioning;
+
+mod common;
+use common::{enc, fetch_sorted, sorted, start_workers, stop_workers, temp_dirs};
+
+const HDRF: EdgePartitioning = EdgePartitioning::Hdrf { lambda: 4.0 };
+
+/// Hub 0 points at spokes 1..=40 and back; spokes 41..=44 form a separate ring.
+fn hub_edges() -> Vec<(u64, u64)> {
+    let mut edges = Vec::new();
+    for spoke in 1..=40 {
+        edges.push((0, spoke));
+        edges.push((spoke, 0));
+    }
+    for spoke in 41..=44 {
+        edges.push((spoke, if spoke == 44 { 41 } else { spoke + 1 }));
+    }
+    edges
+}
+
+fn hub_graph<VV: Clone + Send + Sync + 'static>(
+    value: impl Fn(u64) -> VV,
+    mode: EdgePartitioning,
+) -> PartitionedGraph<u64, VV, u8> {
+    let vertices = (0..=44).map(|id| Vertex::new(id, value(id))).collect();
+    let edges = hub_edges()
+        .into_iter()
+        .map(|(s, t)| Edge::new(s, t, 0u8))
+        .collect();
+    PartitionedGraph::with_edge_partitioning(vertices, edges, 4, mode)
+}
+
+#[test]
+fn test_vertex_cut_spreads_a_hub() {
+    let edge_cut = hub_graph(|id| id, EdgePartitioning::EdgeCut);
+    assert!(edge_cut.mirror_counts().iter().all(|&m| m == 0));
+    let edge_cut_max = *edge_cut.edge_counts().iter().max().unwrap();
+    for mode in [EdgePartitioning::Grid2D, HDRF] {
+        let graph = hub_graph(|id| id, mode);
+        assert_eq!(graph.edge_partitioning(), mode);
+        assert!(graph.mirror_counts().iter().sum::<usize>() > 0, "{mode:?}");
+        let counts = graph.edge_counts();
+        assert_eq!(counts.iter().sum::<usize>(), hub_edges().len());
+        assert!(
+            *counts.iter().max().unwrap() < edge_cut_max,
+            "{mode:?}: {counts:?}"
+        );
+    }
+    // HDRF weighs load directly, so it stays close to even.
+    let counts = hub_graph(|id| id, HDRF).edge_counts();
+    let (max, min) = (counts.iter().max().unwrap(), counts.iter().min().unwrap());
+    assert!(max - min <= 2, "{counts:?}");
+}
+
+#[test]
+fn test_vertex_cut_matches_edge_cut() {
+    let wcc = WccAlgorithm::new(10);
+    let expected = sorted(
+        hub_graph(|id| id, EdgePartitioning::EdgeCut)
+            .compute_algorithm(&wcc, 4)
+            .vertices(),
+    );
+    let pagerank = PageRankAlgorithm::new(5, 0.85);
+    let expected_ranks = sorted(
+        hub_graph(|_| 1.0, EdgePartitioning::EdgeCut)
+            .compute_algorithm(&pagerank, 4)
+            .vertices(),
+    );
+
+    for mode in [EdgePartitioning::Grid2D, HDRF] {
+        let components = sorted(
+            hub_graph(|id| id, mode)
+                .compute_algorithm(&wcc, 4)
+                .vertices(),
+        );
+        assert_eq!(components, expected, "{mode:?}");
+
+        let ranks = sorted(
+            hub_graph(|_| 1.0, mode)
+                .compute_algorithm(&pagerank, 4)
+                .vertices(),
+        );
+        for ((id, rank), (_, want)) in ranks.iter().zip(&expected_ranks) {
+            assert!(
+                (rank - want).abs() < 1e-9,
+                "{mode:?} vertex {id}: {rank} != {want}"
+            );
+        }
+    }
+}
+
+async fn distributed_pagerank(mode: EdgePartitioning) -> Vec<(u64, f64)> {
+    let dirs = temp_dirs(4);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    driver.set_edge_partitioning(mode);
+    let vertices = (0..=44u64).map(|id| (enc(&id), enc(&1.0f64))).collect();
+    let edges = hub_edges()
+        .into_iter()
+        .map(|(s, t)| (enc(&s), enc(&t), enc(&0u8)))
+        .collect();
+    driver.load_graph(vertices, edges).await.unwrap();
+    assert_eq!(driver.mirrors().is_empty(), !mode.is_vertex_cut());
+
+    driver
+        .set_algorithm(
+            "pagerank".to_string(),
+            5,
+            enc(&PageRankParams {
+                alpha: 0.85,
+                tolerance: None,
+            }),
+        )
+        .await
+        .unwrap();
+    driver.execute(5).await.unwrap();
+    let ranks = fetch_sorted(&mut driver).await;
+    stop_workers(driver, handles).await;
+    ranks
+}
+
+#[tokio::test]
+async fn test_distributed_vertex_cut_matches_edge_cut() {
+    let expected = distributed_pagerank(EdgePartitioning::EdgeCut).await;
+    assert_eq!(expected.len(), 45);
+    for mode in [EdgePartitioning::Grid2D, HDRF] {
+        let ranks = distributed_pagerank(mode).await;
+        assert_eq!(ranks.len(), expected.len(), "{mode:?}");
+        for ((id, rank), (_, want)) in ranks.iter().zip(&expected) {
+            assert!(
+                (rank - want).abs() < 1e-9,
+                "{mode:?} vertex {id}: {rank} != {want}"
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_distributed_vertex_cut_rejects_deltas() {
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    driver.set_edge_partitioning(EdgePartitioning::Grid2D);
+    let vertices = (0..=44u64).map(|id| (enc(&id), enc(&id))).collect();
+    let edges = hub_edges()
+        .into_iter()
+        .map(|(s, t)| (enc(&s), enc(&t), enc(&0u8)))
+        .collect();
+    driver.load_graph(vertices, edges).await.unwrap();
+
+    let delta = vec![GraphMutation::RemoveVertex(enc(&0u64))];
+    assert!(matches!(
+        driver.apply_delta(delta).await,
+        Err(GeaFlowError::InvalidArgument(_))
+    ));
+    stop_workers(driver, handles).await;
+}
@@ -0,0 +1,208 @@
+use geaflow_api::function::{
+    VertexCentricComputeAlgorithm, VertexCentricComputeFuncContext, VertexCentricComputeFunction,
+};
+use geaflow_common::types::Vertex;
+use geaflow_runtime::distributed::algorithm::PageRankParams;
+use geaflow_runtime::state::rocksdb_graph_state::RocksDbGraphState;
+use geaflow_runtime::state::GraphState;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+use common::{
+    directed, enc, load, run_algorithm, run_function, start_workers, stop_workers, temp_dirs, Graph,
+};
+
+const CHAIN_LEN: u64 = 50;
+
+#[derive(Clone)]
+struct HopCountFunction {
+    calls: Arc<AtomicUsize>,
+}
+
+impl VertexCentricComputeFunction<u64, u64, u8, u64> for HopCountFunction {
+    fn compute(
+        &mut self,
+        vertex_id: &u64,
+        message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
+    ) {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        context.vote_to_halt();
+
+        let hops = if context.iteration() == 1 {
+            if *vertex_id != 1 {
+                return;
+            }
+            0
+        } else {
+            match message_iterator.min() {
+                Some(hops) => hops,
+                None => return,
+            }
+        };
+        context.set_new_vertex_value(hops);
+        let edges: Vec<_> = context.edges().cloned().collect();
+        for e in edges {
+            context.send_message(e.target_id, hops + 1);
+        }
+    }
+}
+
+struct HopCountAlgorithm {
+    calls: Arc<AtomicUsize>,
+}
+
+impl VertexCentricComputeAlgorithm<u64, u64, u8, u64> for HopCountAlgorithm {
+    fn name(&self) -> &str {
+        "hop_count"
+    }
+
+    fn iterations(&self) -> u64 {
+        CHAIN_LEN * 2
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, u64, u8, u64>> {
+        Box::new(HopCountFunction {
+            calls: self.calls.clone(),
+        })
+    }
+}
+
+fn chain() -> Graph<u64> {
+    let pairs: Vec<(u64, u64)> = (1..CHAIN_LEN).map(|id| (id, id + 1)).collect();
+    common::graph(1..=CHAIN_LEN, |_| u64::MAX, directed(&pairs))
+}
+
+fn assert_hops(vertices: Vec<Vertex<u64, u64>>) {
+    assert_eq!(vertices.len(), CHAIN_LEN as usize);
+    for v in vertices {
+        assert_eq!(v.value, v.id - 1, "vertex {}", v.id);
+    }
+}
+
+// One full superstep, then only the vertex woken by the frontier message.
+const EXPECTED_CALLS: usize = (CHAIN_LEN + CHAIN_LEN - 1) as usize;
+
+#[test]
+fn test_graphs_skip_halted_vertices() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let algo = HopCountAlgorithm {
+        calls: calls.clone(),
+    };
+    for (vertices, _) in run_algorithm(chain, &algo, 4) {
+        assert_hops(vertices);
+    }
+    assert_eq!(calls.swap(0, Ordering::SeqCst), 2 * EXPECTED_CALLS);
+
+    let func = HopCountFunction {
+        calls: calls.clone(),
+    };
+    for (vertices, _) in run_function(chain, func, 4) {
+        assert_hops(vertices);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 2 * EXPECTED_CALLS);
+}
+
+/// Counts its value down to zero one superstep at a time, without sending messages.
+#[derive(Clone)]
+struct CountdownFunction;
+
+impl VertexCentricComputeFunction<u64, u64, u8, u64> for CountdownFunction {
+    fn compute(
+        &mut self,
+        _vertex_id: &u64,
+        _message_iterator: &mut dyn Iterator<Item = u64>,
+        context: &mut dyn VertexCentricComputeFuncContext<u64, u64, u8, u64>,
+    ) {
+        let left = context
+            .vertex_value()
+            .copied()
+            .unwrap_or(0)
+            .saturating_sub(1);
+        context.set_new_vertex_value(left);
+        if left == 0 {
+            context.vote_to_halt();
+        }
+    }
+}
+
+struct CountdownAlgorithm;
+
+impl VertexCentricComputeAlgorithm<u64, u64, u8, u64> for CountdownAlgorithm {
+    fn name(&self) -> &str {
+        "countdown"
+    }
+
+    fn iterations(&self) -> u64 {
+        100
+    }
+
+    fn create_function(&self) -> Box<dyn VertexCentricComputeFunction<u64, u64, u8, u64>> {
+        Box::new(CountdownFunction)
+    }
+}
+
+fn countdowns() -> Graph<u64> {
+    common::graph(1..=6, |id| id * 3, Vec::new())
+}
+
+fn assert_counted_down(vertices: Vec<Vertex<u64, u64>>) {
+    assert_eq!(vertices.len(), 6);
+    for v in vertices {
+        assert_eq!(v.value, 0, "vertex {}", v.id);
+    }
+}
+
+#[test]
+fn test_active_vertices_run_without_messages() {
+    for (vertices, _) in run_algorithm(countdowns, &CountdownAlgorithm, 4) {
+        assert_counted_down(vertices);
+    }
+    // A bare function stops at the first superstep that sends no messages, votes or not.
+    for (vertices, _) in run_function(countdowns, CountdownFunction, 4) {
+        assert_eq!(
+            common::sorted(vertices),
+            (1..=6).map(|id| (id, id * 3 - 1)).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_distributed_active_vertices_run_without_messages() {
+    // PageRank never votes to halt, so an edgeless graph runs every iteration.
+    let dirs = temp_dirs(2);
+    let (mut driver, handles) = start_workers(&dirs).await;
+    load(&mut driver, &common::graph(1..=4, |_| 0.25f64, Vec::new())).await;
+    let params = PageRankParams {
+        alpha: 0.85,
+        tolerance: None,
+    };
+    driver
+        .set_algorithm("pagerank".to_string(), 5, enc(&params))
+        .await
+        .unwrap();
+    assert_eq!(driver.execute(5).await.unwrap(), 5);
+    assert_eq!(driver.active_vertices(), 4);
+    stop_workers(driver, handles).await;
+}
+
+#[test]
+fn test_rocksdb_active_set() {
+    let dir = tempfile::tempdir().unwrap();
+    let state = RocksDbGraphState::open(dir.path()).unwrap();
+
+    <RocksDbGraphState as GraphState<u64, u64, u8>>::update_active_batch(
+        &state,
+        &[1, 2, 3, 4],
+        &[],
+    )
+    .unwrap();
+    <RocksDbGraphState as GraphState<u64, u64, u8>>::update_active_batch(&state, &[5], &[2, 4, 9])
+        .unwrap();
+
+    let mut active =
+        <RocksDbGraphState as GraphState<u64, u64, u8>>::list_active_vertex_ids(&state).unwrap();
+    active.sort();
+    assert_eq!(active, vec![1, 3, 5]);
+}